@@ -2,6 +2,7 @@
     pbr_fragment::pbr_input_from_standard_material,
     pbr_functions::{alpha_discard, apply_pbr_lighting, main_pass_post_lighting_processing},
     forward_io::{VertexOutput, FragmentOutput},
+    mesh_view_bindings::globals,
 }
 
 #ifndef DEPTH_PREPASS
@@ -11,6 +12,7 @@ struct UnlitParams {
     blend_color: vec4<f32>,
     blend_factor: f32,
     grey_threshold: f32,
+    pulse_speed: f32,
 }
 
 @group(2) @binding(100) var<uniform> params: UnlitParams;
@@ -40,7 +42,10 @@ fn fragment(
     // Your custom logic when depth prepass is not active
     let blended_color = mix(material_color, params.blend_color, params.blend_factor);
     let is_grey_pixel = is_grey(blended_color.rgb, params.grey_threshold);
-    let final_intensity = select(params.intensity, 1.0, is_grey_pixel);
+    // only the glow above 1.0 pulses, so pulse_speed 0 or an unpowered intensity looks the same as before
+    let pulse = 1.0 + 0.15 * sin(globals.time * params.pulse_speed);
+    let pulsed_intensity = 1.0 + (params.intensity - 1.0) * pulse;
+    let final_intensity = select(pulsed_intensity, 1.0, is_grey_pixel);
     
     out.color = vec4<f32>(blended_color.rgb * final_intensity, material_color.a * params.alpha);
 #endif
@@ -1,37 +1,58 @@
+use std::collections::HashSet;
+
 use avian3d::prelude::{
     Collider, CollisionEventsEnabled, CollisionLayers, OnCollisionStart, RigidBody, Sensor,
 };
-use bevy::{asset::LoadState, color::palettes::css::WHITE, pbr::ExtendedMaterial, prelude::*};
+use bevy::{
+    asset::LoadState, color::palettes::css::WHITE, pbr::ExtendedMaterial, prelude::*,
+    render::mesh::VertexAttributeValues,
+};
 
 use crate::{
-    asset_management::asset_tag_components::{BehindFinalDoor, FancyMesh, WeightedCube},
+    asset_management::asset_tag_components::{
+        BehindFinalDoor, ColliderKind, CompoundCollider, FancyMesh, LevelMetadata, NoOutline,
+        WeightedCube,
+    },
     game::GameLayer,
     rendering::{
         section_color_prepass::{DrawSection, ATTRIBUTE_SECTION_COLOR},
         unlit_material::{UnlitMaterial, UnlitMaterialExtension, UnlitParams},
     },
-    GameState,
+    ColliderDistanceConfig, GameState,
 };
 
-use super::asset_tag_components::{
-    CubeSpitter, Door, DoorPole, Inert, NeedsRigidBody, PowerButton, SignalSpitter,
-    StandingCubeSpitter,
+use super::{
+    asset_tag_components::{
+        CubeSpitter, Door, DoorPole, HoldButton, Inert, NeedsRigidBody, PowerButton, SignalSpitter,
+        StandingCubeSpitter, WeightedCubeColors,
+    },
+    level_manager::LevelRoot,
 };
 
 pub(crate) fn assets_plugin(app: &mut App) {
     app.init_state::<AssetLoaderState>()
         .init_resource::<GameAssets>()
         .init_resource::<GameSounds>()
+        .init_resource::<CurrentLevelMeta>()
+        .init_resource::<PostprocessedScenes>()
+        .init_resource::<ScenePostprocessSettings>()
         .add_systems(
             Update,
             (
                 check_asset_loading.run_if(in_state(AssetLoaderState::Loading)),
-                (assign_colliders_to_meshes, add_rigidbodies_to_colliders).chain(),
+                postprocess_pending_scenes,
+                (
+                    assign_colliders_to_meshes,
+                    merge_compound_colliders,
+                    add_rigidbodies_to_colliders,
+                )
+                    .chain(),
             ),
         )
         .add_systems(OnEnter(AssetLoaderState::Loading), on_start_loading)
         .add_systems(OnEnter(AssetLoaderState::Postprocess), postprocess_assets)
-        .add_observer(register_final_door);
+        .add_observer(register_final_door)
+        .add_observer(read_level_metadata);
 }
 
 #[derive(SubStates, Default, Debug, Clone, PartialEq, Eq, Hash)]
@@ -51,9 +72,13 @@ pub struct GameAssets {
     pub weighted_cube_cyan: Handle<Scene>,
 
     // meshes
+    pub preview_beam_mesh: Handle<Mesh>,
+    pub signal_charge_mesh: Handle<Mesh>,
 
     // materials
     pub cyan_signal_material: Handle<UnlitMaterial>,
+    pub preview_beam_valid_material: Handle<UnlitMaterial>,
+    pub preview_beam_invalid_material: Handle<UnlitMaterial>,
 
     // audio
 
@@ -61,6 +86,14 @@ pub struct GameAssets {
     pub font: Handle<Font>,
 }
 
+impl GameAssets {
+    pub fn signal_material(&self, color: WeightedCubeColors) -> Handle<UnlitMaterial> {
+        match color {
+            WeightedCubeColors::Cyan => self.cyan_signal_material.clone(),
+        }
+    }
+}
+
 #[derive(Resource, Default)]
 pub struct GameSounds {
     pub song: Handle<AudioSource>,
@@ -69,17 +102,52 @@ pub struct GameSounds {
     pub door_open: Handle<AudioSource>,
     pub pressure_plate_up: Handle<AudioSource>,
     pub pressure_plate_down: Handle<AudioSource>,
+    pub cube_impact: Handle<AudioSource>,
 }
 
+// Metadata of the level currently loaded, if the scene provided any
+#[derive(Resource, Default)]
+pub struct CurrentLevelMeta(pub Option<LevelMetadata>);
+
 #[derive(Component)]
 pub struct LoadingAsset(pub UntypedHandle);
 
+/// Spawn this with a scene handle instead of a SceneRoot for anything loaded at runtime.
+/// Once the scene finishes loading it gets postprocessed and the SceneRoot is inserted.
+#[derive(Component)]
+pub struct NeedsScenePostprocess(pub Handle<Scene>);
+
+// Scenes only need converting once, no matter how many times they get spawned
+#[derive(Resource, Default)]
+pub struct PostprocessedScenes(pub HashSet<AssetId<Scene>>);
+
+// Knobs for postprocess_scene, shared by every scene that goes through it
+#[derive(Resource)]
+pub struct ScenePostprocessSettings {
+    // leave Mesh::ATTRIBUTE_COLOR in place after copying it into the section color, for
+    // materials that want vertex colors for shading too
+    pub keep_vertex_colors: bool,
+    // section color filled in for meshes that came without vertex colors, so they still get
+    // outlined. None leaves them without outlines.
+    pub fallback_section_color: Option<LinearRgba>,
+}
+
+impl Default for ScenePostprocessSettings {
+    fn default() -> Self {
+        Self {
+            keep_vertex_colors: false,
+            fallback_section_color: Some(LinearRgba::WHITE),
+        }
+    }
+}
+
 fn on_start_loading(
     mut commands: Commands,
     mut game_assets: ResMut<GameAssets>,
     mut game_sounds: ResMut<GameSounds>,
     asset_server: Res<AssetServer>,
     mut unlit_materials: ResMut<Assets<UnlitMaterial>>,
+    mut meshes: ResMut<Assets<Mesh>>,
 ) {
     game_assets.main_menu_environment =
         asset_server.load(GltfAssetLabel::Scene(0).from_asset("scenes/jam6scene1.glb"));
@@ -111,6 +179,13 @@ fn on_start_loading(
         },
     });
 
+    game_assets.preview_beam_mesh = meshes.add(Cuboid::new(1.0, 1.0, 1.0));
+    game_assets.signal_charge_mesh = meshes.add(Sphere::new(1.0));
+    game_assets.preview_beam_valid_material =
+        unlit_materials.add(preview_beam_material(LinearRgba::rgb(0.0, 1.0, 0.0)));
+    game_assets.preview_beam_invalid_material =
+        unlit_materials.add(preview_beam_material(LinearRgba::rgb(1.0, 0.2, 0.2)));
+
     game_sounds.song = asset_server.load("sounds/bevyjam6songfix.mp3");
     commands.spawn(LoadingAsset(game_sounds.song.clone().into()));
 
@@ -129,9 +204,31 @@ fn on_start_loading(
     game_sounds.pressure_plate_down = asset_server.load("sounds/pressure_plate_down.mp3");
     commands.spawn(LoadingAsset(game_sounds.pressure_plate_down.clone().into()));
 
+    game_sounds.cube_impact = asset_server.load("sounds/cube_impact.wav");
+    commands.spawn(LoadingAsset(game_sounds.cube_impact.clone().into()));
+
     commands.set_state(AssetLoaderState::Loading);
 }
 
+fn preview_beam_material(color: LinearRgba) -> UnlitMaterial {
+    UnlitMaterial {
+        base: StandardMaterial {
+            base_color: color.into(),
+            alpha_mode: AlphaMode::Blend,
+            ..default()
+        },
+        extension: UnlitMaterialExtension {
+            params: UnlitParams {
+                intensity: 1.0,
+                alpha: 0.3,
+                blend_color: color,
+                blend_factor: 1.0,
+                ..default()
+            },
+        },
+    }
+}
+
 fn check_asset_loading(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
@@ -160,6 +257,8 @@ fn postprocess_assets(
     mut standard_materials: ResMut<Assets<StandardMaterial>>,
     mut unlit_materials: ResMut<Assets<UnlitMaterial>>,
     mut meshes: ResMut<Assets<Mesh>>,
+    mut postprocessed_scenes: ResMut<PostprocessedScenes>,
+    postprocess_settings: Res<ScenePostprocessSettings>,
 ) {
     // set up materials and colliders for everything
     let scenes_to_process = vec![
@@ -169,97 +268,14 @@ fn postprocess_assets(
 
     for scene_handle in scenes_to_process {
         if let Some(scene) = scenes.get_mut(&scene_handle) {
-            let mut materials_to_process = Vec::new();
-            for entity_ref in scene.world.iter_entities() {
-                if let Some(material_handle) = scene
-                    .world
-                    .get::<MeshMaterial3d<StandardMaterial>>(entity_ref.id())
-                {
-                    materials_to_process.push((entity_ref.id(), material_handle.0.clone()));
-                }
-            }
-
-            for (entity, material_handle) in materials_to_process {
-                if let Some(old_material) = standard_materials.get_mut(&material_handle) {
-                    //old_material.reflectance = 0.0;
-
-                    let default_new_material = ExtendedMaterial {
-                        base: old_material.clone(),
-                        extension: UnlitMaterialExtension {
-                            params: UnlitParams {
-                                intensity: 1.0,
-                                alpha: 1.0,
-                                blend_color: WHITE.into(),
-                                blend_factor: 0.0,
-                                grey_threshold: 0.2,
-                            },
-                        },
-                    };
-
-                    // Example of singling out a specific marked object to modify the material
-                    // // marker components are on the mesh parent
-                    // let new_material = if let Some(child_of) = scene.world.entity(entity).get::<ChildOf>() {
-                    //     if scene.world.entity(child_of.0).contains::<RoomWalls>() {
-                    //         let mut new_old_material = old_material.clone();
-                    //         new_old_material.cull_mode = None;
-
-                    //         ExtendedMaterial {
-                    //             base: new_old_material,
-                    //             extension: UnlitMaterialExtension { foo: 0.0 },
-                    //         }
-                    //     } else {
-                    //         default_new_material
-                    //     }
-                    // } else {
-                    //     default_new_material
-                    // };
-
-                    scene
-                        .world
-                        .entity_mut(entity)
-                        .remove::<MeshMaterial3d<StandardMaterial>>()
-                        .insert(MeshMaterial3d(unlit_materials.add(default_new_material)));
-                }
-            }
-
-            // Do any mesh postprocessing we need
-            let mut entities_to_process = Vec::new();
-            for entity_ref in scene.world.iter_entities() {
-                let entity = entity_ref.id();
-                if let Some(mesh_handle) = scene.world.get::<Mesh3d>(entity) {
-                    entities_to_process.push((entity, mesh_handle.clone()));
-                }
-            }
-
-            for (entity, mesh_handle) in entities_to_process.iter() {
-                if let Some(mesh) = meshes.get_mut(mesh_handle) {
-                    // convert vertex colors to the section color our outline effect expects
-                    // TODO: Should we remove the vertex color attribute afterwards?
-                    if let Some(vertex_colors) = mesh.attribute(Mesh::ATTRIBUTE_COLOR).cloned() {
-                        mesh.insert_attribute(ATTRIBUTE_SECTION_COLOR, vertex_colors);
-
-                        // Configure entities with the attribute to be drawn with section outlines
-                        scene.world.entity_mut(*entity).insert(DrawSection);
-                    } else {
-                        // warn!(
-                        //     "Mesh on entity {:?} doesn't have vertex colors to convert",
-                        //     entity
-                        // );
-                    }
-
-                    scene.world.entity_mut(*entity).insert(NeedsRigidBody {
-                        kind: RigidBody::Static,
-                    });
-                }
-            }
-
-            for (_, mesh_handle) in entities_to_process {
-                if let Some(mesh) = meshes.get_mut(&mesh_handle) {
-                    if mesh.attribute(Mesh::ATTRIBUTE_COLOR).cloned().is_some() {
-                        mesh.remove_attribute(Mesh::ATTRIBUTE_COLOR);
-                    }
-                }
-            }
+            postprocess_scene(
+                scene,
+                &mut standard_materials,
+                &mut unlit_materials,
+                &mut meshes,
+                &postprocess_settings,
+            );
+            postprocessed_scenes.0.insert(scene_handle.id());
         }
     }
 
@@ -285,22 +301,202 @@ fn postprocess_assets(
     //     }
     // }
 
-    commands.spawn(SceneRoot(game_assets.main_menu_environment.clone()));
-    //commands.set_state(GameState::MainMenu);
-    commands.set_state(GameState::Playing);
+    commands.spawn((
+        SceneRoot(game_assets.main_menu_environment.clone()),
+        LevelRoot,
+    ));
+    commands.set_state(GameState::MainMenu);
+}
+
+fn postprocess_pending_scenes(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    q_pending_scenes: Query<(Entity, &NeedsScenePostprocess)>,
+    mut scenes: ResMut<Assets<Scene>>,
+    mut standard_materials: ResMut<Assets<StandardMaterial>>,
+    mut unlit_materials: ResMut<Assets<UnlitMaterial>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut postprocessed_scenes: ResMut<PostprocessedScenes>,
+    postprocess_settings: Res<ScenePostprocessSettings>,
+) {
+    for (entity, pending_scene) in &q_pending_scenes {
+        match asset_server.get_load_state(&pending_scene.0) {
+            Some(LoadState::Loaded) => {}
+            Some(LoadState::Failed(err)) => {
+                error!("failed to load scene: {}", err);
+                commands.entity(entity).remove::<NeedsScenePostprocess>();
+                continue;
+            }
+            _ => continue,
+        }
+
+        if !postprocessed_scenes.0.contains(&pending_scene.0.id()) {
+            if let Some(scene) = scenes.get_mut(&pending_scene.0) {
+                postprocess_scene(
+                    scene,
+                    &mut standard_materials,
+                    &mut unlit_materials,
+                    &mut meshes,
+                    &postprocess_settings,
+                );
+                postprocessed_scenes.0.insert(pending_scene.0.id());
+            }
+        }
+
+        commands
+            .entity(entity)
+            .remove::<NeedsScenePostprocess>()
+            .insert(SceneRoot(pending_scene.0.clone()));
+    }
+}
+
+// Swaps materials over to UnlitMaterial, moves vertex colors into the section color attribute
+// and marks every mesh as needing a static body
+pub(crate) fn postprocess_scene(
+    scene: &mut Scene,
+    standard_materials: &mut Assets<StandardMaterial>,
+    unlit_materials: &mut Assets<UnlitMaterial>,
+    meshes: &mut Assets<Mesh>,
+    settings: &ScenePostprocessSettings,
+) {
+    let mut materials_to_process = Vec::new();
+    for entity_ref in scene.world.iter_entities() {
+        if let Some(material_handle) = scene
+            .world
+            .get::<MeshMaterial3d<StandardMaterial>>(entity_ref.id())
+        {
+            materials_to_process.push((entity_ref.id(), material_handle.0.clone()));
+        }
+    }
+
+    for (entity, material_handle) in materials_to_process {
+        if let Some(old_material) = standard_materials.get_mut(&material_handle) {
+            //old_material.reflectance = 0.0;
+
+            let default_new_material = ExtendedMaterial {
+                base: old_material.clone(),
+                extension: UnlitMaterialExtension {
+                    params: UnlitParams {
+                        intensity: 1.0,
+                        alpha: 1.0,
+                        blend_color: WHITE.into(),
+                        blend_factor: 0.0,
+                        grey_threshold: 0.2,
+                        pulse_speed: 0.0,
+                    },
+                },
+            };
+
+            // Example of singling out a specific marked object to modify the material
+            // // marker components are on the mesh parent
+            // let new_material = if let Some(child_of) = scene.world.entity(entity).get::<ChildOf>() {
+            //     if scene.world.entity(child_of.0).contains::<RoomWalls>() {
+            //         let mut new_old_material = old_material.clone();
+            //         new_old_material.cull_mode = None;
+
+            //         ExtendedMaterial {
+            //             base: new_old_material,
+            //             extension: UnlitMaterialExtension { foo: 0.0 },
+            //         }
+            //     } else {
+            //         default_new_material
+            //     }
+            // } else {
+            //     default_new_material
+            // };
+
+            scene
+                .world
+                .entity_mut(entity)
+                .remove::<MeshMaterial3d<StandardMaterial>>()
+                .insert(MeshMaterial3d(unlit_materials.add(default_new_material)));
+        }
+    }
+
+    // Do any mesh postprocessing we need
+    let mut entities_to_process = Vec::new();
+    for entity_ref in scene.world.iter_entities() {
+        let entity = entity_ref.id();
+        if let Some(mesh_handle) = scene.world.get::<Mesh3d>(entity) {
+            entities_to_process.push((entity, mesh_handle.clone()));
+        }
+    }
+
+    for (entity, mesh_handle) in entities_to_process.iter() {
+        if let Some(mesh) = meshes.get_mut(mesh_handle) {
+            // convert vertex colors to the section color our outline effect expects
+            if let Some(vertex_colors) = mesh.attribute(Mesh::ATTRIBUTE_COLOR).cloned() {
+                mesh.insert_attribute(ATTRIBUTE_SECTION_COLOR, vertex_colors);
+
+                // Configure entities with the attribute to be drawn with section outlines
+                scene.world.entity_mut(*entity).insert(DrawSection);
+            } else if let Some(fallback_color) = settings.fallback_section_color {
+                if !skips_outline(&scene.world, *entity) {
+                    debug!(
+                        "Mesh on entity {:?} doesn't have vertex colors to convert, using the fallback section color",
+                        entity
+                    );
+
+                    let section_colors = vec![fallback_color.to_f32_array(); mesh.count_vertices()];
+                    mesh.insert_attribute(
+                        ATTRIBUTE_SECTION_COLOR,
+                        VertexAttributeValues::Float32x4(section_colors),
+                    );
+                    scene.world.entity_mut(*entity).insert(DrawSection);
+                }
+            } else {
+                debug!(
+                    "Mesh on entity {:?} doesn't have vertex colors to convert",
+                    entity
+                );
+            }
+
+            scene.world.entity_mut(*entity).insert(NeedsRigidBody {
+                kind: RigidBody::Static,
+            });
+        }
+    }
+
+    if settings.keep_vertex_colors {
+        return;
+    }
+
+    for (_, mesh_handle) in entities_to_process {
+        if let Some(mesh) = meshes.get_mut(&mesh_handle) {
+            if mesh.attribute(Mesh::ATTRIBUTE_COLOR).cloned().is_some() {
+                mesh.remove_attribute(Mesh::ATTRIBUTE_COLOR);
+            }
+        }
+    }
+}
+
+// gltf puts each mesh primitive on a child of the tagged node, so look one level up too
+fn skips_outline(world: &World, entity: Entity) -> bool {
+    if world.get::<NoOutline>(entity).is_some() {
+        return true;
+    }
+
+    world
+        .get::<ChildOf>(entity)
+        .is_some_and(|child_of| world.get::<NoOutline>(child_of.parent()).is_some())
 }
 
 fn assign_colliders_to_meshes(
     mut commands: Commands,
     // Query for mesh entities that don't have colliders yet
-    mesh_entities: Query<(Entity, &Mesh3d, Option<&ChildOf>), (Without<Collider>, Added<Mesh3d>)>,
+    mesh_entities: Query<
+        (Entity, &Mesh3d, Option<&ChildOf>),
+        (Without<Collider>, Without<NoAutoCollider>, Added<Mesh3d>),
+    >,
     // Query for entities that should use trimesh colliders
     trimesh_entities: Query<(), Or<(With<Door>, With<FancyMesh>)>>,
+    collider_kinds: Query<&ColliderKind>,
     // Query for entities with WeightedCube component
     weighted_cube_entities: Query<(), With<WeightedCube>>,
     // Query for parent relationships
     parent_query: Query<&ChildOf>,
     meshes: Res<Assets<Mesh>>,
+    collider_distance_config: Res<ColliderDistanceConfig>,
 ) {
     for (entity, mesh_handle, parent) in &mesh_entities {
         if let Some(mesh) = meshes.get(&mesh_handle.0) {
@@ -321,27 +517,177 @@ fn assign_colliders_to_meshes(
                 &parent_query,
             );
 
-            let collider = if entity_needs_trimesh || parent_needs_trimesh {
-                Collider::trimesh_from_mesh(mesh)
-            } else {
-                Collider::convex_hull_from_mesh(mesh)
+            // an authored ColliderKind beats the tags, the mesh's own over its parent's
+            let authored_kind = collider_kinds
+                .get(entity)
+                .ok()
+                .or_else(|| parent.and_then(|parent| collider_kinds.get(parent.parent()).ok()));
+
+            let use_trimesh = match authored_kind {
+                Some(collider_kind) => collider_kind.trimesh,
+                None => entity_needs_trimesh || parent_needs_trimesh,
             };
 
-            if let Some(collider) = collider {
-                let mut entity_commands = commands.entity(entity);
-                entity_commands.insert(collider);
+            // cubes are dynamic and need their collider right away or they fall through the floor
+            if collider_distance_config.lazy_generation && !has_weighted_cube_parent {
+                commands
+                    .entity(entity)
+                    .insert(PendingCollider { use_trimesh });
+                continue;
+            }
 
-                // Only add RigidBody if no WeightedCube parent exists
-                if !has_weighted_cube_parent {
-                    entity_commands.insert(NeedsRigidBody {
-                        kind: RigidBody::Static,
-                    });
-                }
-            } else {
-                warn!("Failed to create collider for mesh on entity {:?}", entity);
+            insert_collider_from_mesh(
+                &mut commands,
+                entity,
+                mesh,
+                use_trimesh,
+                !has_weighted_cube_parent,
+            );
+        }
+    }
+}
+
+/// Purely visual meshes spawned at runtime that shouldn't get a collider generated for them
+#[derive(Component)]
+pub struct NoAutoCollider;
+
+/// Marks a mesh entity whose collider will be generated once the player comes within range
+#[derive(Component)]
+pub struct PendingCollider {
+    pub use_trimesh: bool,
+}
+
+pub fn insert_collider_from_mesh(
+    commands: &mut Commands,
+    entity: Entity,
+    mesh: &Mesh,
+    use_trimesh: bool,
+    needs_rigid_body: bool,
+) {
+    let collider = if use_trimesh {
+        Collider::trimesh_from_mesh(mesh)
+    } else {
+        Collider::convex_hull_from_mesh(mesh)
+    }
+    .or_else(|| {
+        // flat or otherwise degenerate meshes have no hull, but they should still collide
+        warn!(
+            "Failed to create collider for mesh on entity {:?}, falling back to its bounding box",
+            entity
+        );
+        aabb_collider_from_mesh(mesh)
+    });
+
+    if let Some(collider) = collider {
+        let mut entity_commands = commands.entity(entity);
+        entity_commands.insert(collider);
+
+        // Only add RigidBody if no WeightedCube parent exists
+        if needs_rigid_body {
+            entity_commands.insert(NeedsRigidBody {
+                kind: RigidBody::Static,
+            });
+        }
+    } else {
+        warn!(
+            "Mesh on entity {:?} has no positions to build a collider from",
+            entity
+        );
+    }
+}
+
+// the smallest a box side can be before the physics gets upset about it
+const MIN_FALLBACK_COLLIDER_SIZE: f32 = 0.01;
+
+fn aabb_collider_from_mesh(mesh: &Mesh) -> Option<Collider> {
+    let aabb = mesh.compute_aabb()?;
+    let size = (Vec3::from(aabb.half_extents) * 2.).max(Vec3::splat(MIN_FALLBACK_COLLIDER_SIZE));
+
+    Some(Collider::compound(vec![(
+        Vec3::from(aabb.center),
+        Quat::IDENTITY,
+        Collider::cuboid(size.x, size.y, size.z),
+    )]))
+}
+
+// Waits until every mesh under the parent has its collider, then swaps them all out for one
+// compound collider on the parent
+fn merge_compound_colliders(
+    mut commands: Commands,
+    q_compound_parents: Query<Entity, (With<CompoundCollider>, Without<Collider>)>,
+    q_children: Query<&Children>,
+    q_meshes: Query<(Option<&Collider>, Has<NoAutoCollider>), With<Mesh3d>>,
+    q_transforms: Query<&Transform>,
+    q_child_of: Query<&ChildOf>,
+) {
+    'parents: for parent in &q_compound_parents {
+        let mut shapes = vec![];
+        let mut merged_children = vec![];
+
+        for descendant in q_children.iter_descendants(parent) {
+            let Ok((maybe_collider, no_auto_collider)) = q_meshes.get(descendant) else {
+                continue;
+            };
+
+            if no_auto_collider {
+                continue;
             }
+
+            // still being generated (or lazily deferred), try again next frame
+            let Some(collider) = maybe_collider else {
+                continue 'parents;
+            };
+
+            let Some(relative) =
+                transform_relative_to(descendant, parent, &q_transforms, &q_child_of)
+            else {
+                continue;
+            };
+
+            let mut shape = collider.clone();
+            if relative.scale != Vec3::ONE {
+                shape.set_scale(relative.scale, 10);
+            }
+
+            shapes.push((relative.translation, relative.rotation, shape));
+            merged_children.push(descendant);
         }
+
+        if shapes.is_empty() {
+            continue;
+        }
+
+        for child in merged_children {
+            commands
+                .entity(child)
+                .remove::<(Collider, NeedsRigidBody)>();
+        }
+
+        commands.entity(parent).insert((
+            Collider::compound(shapes),
+            NeedsRigidBody {
+                kind: RigidBody::Static,
+            },
+        ));
+    }
+}
+
+// GlobalTransform isn't propagated yet for freshly spawned scenes, so walk the local ones
+fn transform_relative_to(
+    entity: Entity,
+    ancestor: Entity,
+    q_transforms: &Query<&Transform>,
+    q_child_of: &Query<&ChildOf>,
+) -> Option<Transform> {
+    let mut relative = *q_transforms.get(entity).ok()?;
+    let mut current = q_child_of.get(entity).ok()?.parent();
+
+    while current != ancestor {
+        relative = q_transforms.get(current).ok()?.mul_transform(relative);
+        current = q_child_of.get(current).ok()?.parent();
     }
+
+    Some(relative)
 }
 
 // ok we dont need this but im too scared to break anything sooo
@@ -379,6 +725,7 @@ fn add_rigidbodies_to_colliders(
             With<Inert>,
             With<StandingCubeSpitter>,
             With<PowerButton>,
+            With<HoldButton>,
             With<WeightedCube>,
             With<BehindFinalDoor>,
         )>,
@@ -438,3 +785,59 @@ fn register_final_door(
 fn win(_trigger: Trigger<OnCollisionStart>, mut commands: Commands) {
     commands.set_state(GameState::Win);
 }
+
+fn read_level_metadata(
+    trigger: Trigger<OnAdd, LevelMetadata>,
+    q_level_metadata: Query<&LevelMetadata>,
+    mut current_level_meta: ResMut<CurrentLevelMeta>,
+) {
+    if let Ok(level_metadata) = q_level_metadata.get(trigger.target()) {
+        info!("Loaded level metadata for {:?}", level_metadata.name);
+        current_level_meta.0 = Some(level_metadata.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::RunSystemOnce;
+
+    #[test]
+    fn flat_mesh_falls_back_to_a_box_collider() {
+        let mut world = World::new();
+        world.init_resource::<Assets<Mesh>>();
+        world.init_resource::<ColliderDistanceConfig>();
+
+        // every vertex on one plane, there's no hull to build
+        let flat_mesh = world
+            .resource_mut::<Assets<Mesh>>()
+            .add(Mesh::from(Plane3d::default()));
+        let flat = world.spawn(Mesh3d(flat_mesh)).id();
+
+        world.run_system_once(assign_colliders_to_meshes).unwrap();
+
+        let collider = world.get::<Collider>(flat).expect("no fallback collider");
+        let compound = collider.shape().as_compound().expect("not a compound");
+        assert_eq!(compound.shapes().len(), 1);
+        assert!(compound.shapes()[0].1.as_cuboid().is_some());
+    }
+
+    #[test]
+    fn level_metadata_becomes_current() {
+        let mut world = World::new();
+        world.init_resource::<CurrentLevelMeta>();
+        world.add_observer(read_level_metadata);
+
+        world.spawn(LevelMetadata {
+            name: "Test Chamber".to_string(),
+            par_time: 42.0,
+            ..default()
+        });
+        world.flush();
+
+        let current_level_meta = world.resource::<CurrentLevelMeta>();
+        let level_metadata = current_level_meta.0.as_ref().expect("no level metadata");
+        assert_eq!(level_metadata.name, "Test Chamber");
+        assert_eq!(level_metadata.par_time, 42.0);
+    }
+}
@@ -1,7 +1,9 @@
 use avian3d::prelude::RigidBody;
 use bevy::prelude::*;
 
-use crate::game::dissolve_gate::Dissolveable;
+use crate::game::{dissolve_gate::Dissolveable, signals::DEFAULT_SIGNAL_SPEED};
+
+use super::named_target::NamedTarget;
 
 // Thanks to a bug with Bevity, we need to make these tag components with any random field
 // whoops
@@ -24,11 +26,34 @@ pub struct WeightedCube {
     pub color: WeightedCubeColors,
 }
 
-#[derive(Reflect)]
+#[derive(Reflect, Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum WeightedCubeColors {
+    #[default]
     Cyan,
 }
 
+// What color the signals coming out of this emitter are. Emitters without one fire cyan.
+#[derive(Component, Reflect, Debug, Clone, Copy)]
+#[reflect(Component)]
+pub struct SignalColor {
+    pub color: WeightedCubeColors,
+}
+
+// Optional companion to WeightedCube for "big battery" cubes that discharge from further away
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct DischargeRadius {
+    pub radius: f32,
+}
+
+// Optional companion to WeightedCube; the cube hangs in the air while powered so it can be
+// used as a temporary platform
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct FreezeOnPower {
+    pub unused: bool,
+}
+
 #[derive(Component, Reflect)]
 #[reflect(Component)]
 pub struct CubeSpitter {
@@ -72,6 +97,20 @@ pub struct ChargePad {
     pub unused: bool,
 }
 
+// Dissolve and discharge gates leave this alone, whether it goes through on its own or carried
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct GateImmune {
+    pub unused: bool,
+}
+
+// Put on a ChargePad to have it charge everything sitting on it instead of one thing at a time
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct MultiCharge {
+    pub unused: bool,
+}
+
 #[derive(Component, Reflect, Debug)]
 #[reflect(Component)]
 pub struct DissolveGate {
@@ -84,10 +123,31 @@ pub struct DischargeGate {
     pub unused: bool,
 }
 
+// Two gates on the same channel send bodies back and forth
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct TeleportGate {
+    pub channel: u32,
+}
+
+// Solid while powered, something you can walk through while it isn't
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct ForceField {
+    pub unused: bool,
+}
+
 #[derive(Component, Reflect, Debug)]
 #[reflect(Component)]
 pub struct Door {
     pub unused: bool,
+    // how far up it slides when opened, levels authored before this existed get the old 20
+    #[reflect(default = "default_door_lift_height")]
+    pub lift_height: f32,
+}
+
+fn default_door_lift_height() -> f32 {
+    20.
 }
 
 #[derive(Component, Reflect)]
@@ -96,6 +156,40 @@ pub struct ExtraDoorPowerRequired {
     pub amount: u32,
 }
 
+// Put on a signal spitter to make its signals shrink away to nothing over `distance`
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct SignalFalloff {
+    pub distance: f32,
+}
+
+// Explicit wiring between two devices anywhere in the level, referenced by their Name
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Conduit {
+    pub from: NamedTarget,
+    pub to: NamedTarget,
+}
+
+/// Once the door finishes opening it stays open for `seconds`, then closes even if it's still
+/// powered. It won't open again until its poles lose power and get powered again.
+/// This overrides power entirely while active, so it also wins over any close delay on the door.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct AutoCloseAfter {
+    pub seconds: f32,
+}
+
+/// The door latches instead of needing its poles powered all at once: every pole that has ever
+/// been powered counts toward opening it, and once enough have been it stays open until the
+/// level is reloaded. With ExtraDoorPowerRequired that's `amount + 1` distinct poles.
+/// With AutoCloseAfter it closes once and never reopens, since it never loses power.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct CumulativePower {
+    pub unused: bool,
+}
+
 #[derive(Component, Reflect, Debug)]
 #[reflect(Component)]
 pub struct DoorPole {
@@ -108,24 +202,142 @@ pub struct Inert {
     pub unused: bool,
 }
 
+// Flashes like an Inert when hit, then sends a fresh signal out of its own front
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct SignalRelay {
+    pub unused: bool,
+}
+
+// Any signal that reaches this cuts the power to everything in the level. Cubes hang on to their
+// charge like they do everywhere else unless `depower_cubes` is set.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct MasterSwitch {
+    pub depower_cubes: bool,
+}
+
 #[derive(Component, Reflect, Debug)]
 #[reflect(Component)]
 pub struct Immobile {
     pub unused: bool,
 }
 
+// How big a signal spitter's signals are, where they leave from (in the spitter's space with
+// +Z being the way it fires) and how fast they go in units per second. Spitters without one get
+// STANDARD, or IMMOBILE if they're Immobile.
+#[derive(Component, Reflect, Debug, Clone, Copy)]
+#[reflect(Component)]
+pub struct SpitterConfig {
+    pub signal_size: f32,
+    pub launch_offset: Vec3,
+    #[reflect(default = "default_signal_speed")]
+    pub signal_speed: f32,
+}
+
+fn default_signal_speed() -> f32 {
+    DEFAULT_SIGNAL_SPEED
+}
+
+impl SpitterConfig {
+    pub const STANDARD: SpitterConfig = SpitterConfig {
+        signal_size: 10.,
+        launch_offset: Vec3::new(0., 10., 10.),
+        signal_speed: DEFAULT_SIGNAL_SPEED,
+    };
+
+    pub const IMMOBILE: SpitterConfig = SpitterConfig {
+        signal_size: 30.,
+        launch_offset: Vec3::new(0., 20., 10.),
+        signal_speed: DEFAULT_SIGNAL_SPEED,
+    };
+}
+
+// How many cubes a spitter keeps around at once, the oldest goes when it spits past that.
+// Spitters without one keep a single cube.
+#[derive(Component, Reflect, Debug, Clone, Copy)]
+#[reflect(Component)]
+pub struct MaxOwned {
+    #[reflect(default = "default_max_owned")]
+    pub count: usize,
+}
+
+fn default_max_owned() -> usize {
+    1
+}
+
+impl Default for MaxOwned {
+    fn default() -> Self {
+        Self {
+            count: default_max_owned(),
+        }
+    }
+}
+
 #[derive(Component, Reflect, Debug)]
 #[reflect(Component)]
 pub struct PowerButton {
     pub unused: bool,
 }
 
+// Like a PowerButton, but its targets stay powered only while Use is held down on it
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct HoldButton {
+    pub unused: bool,
+}
+
 #[derive(Component, Reflect)]
 #[reflect(Component)]
 pub struct PermanentlyPowered {
     pub unused: bool,
 }
 
+// Signals fired by this also hit the player, for hazard rooms. They still power devices as usual.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct HostileSignals {
+    pub unused: bool,
+}
+
+// A signal spitter the player can switch on and off by hand, whether or not it's powered
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct HandToggleable {
+    pub unused: bool,
+}
+
+// Any registerable device with this begins the level powered instead of waiting on a signal
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct StartsPowered {
+    pub unused: bool,
+}
+
+// Picks trimesh or convex hull for this object's generated colliders, on the mesh or its parent,
+// instead of going off the Door/FancyMesh tags
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct ColliderKind {
+    pub trimesh: bool,
+}
+
+// Put on the parent of a multi-mesh object to have all of its generated colliders merged into
+// one compound collider on the parent, so it acts as a single body
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct CompoundCollider {
+    pub unused: bool,
+}
+
+// Keeps meshes without vertex colors (on this object or its direct children) from getting the
+// fallback section color, so they're drawn without outlines
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct NoOutline {
+    pub unused: bool,
+}
+
 #[derive(Component, Reflect)]
 #[reflect(Component)]
 pub struct FancyMesh {
@@ -144,11 +356,23 @@ pub struct BehindFinalDoor {
     pub unused: bool,
 }
 
+#[derive(Component, Reflect, Clone, Default, Debug)]
+#[reflect(Component)]
+pub struct LevelMetadata {
+    pub name: String,
+    pub author: String,
+    pub par_time: f32,
+    pub music_track: String,
+}
+
 pub fn asset_tag_components_plugin(app: &mut App) {
     app.register_type::<RoomWall>()
         .register_type::<BigRedButton>()
         .register_type::<WeightedCube>()
         .register_type::<WeightedCubeColors>()
+        .register_type::<SignalColor>()
+        .register_type::<DischargeRadius>()
+        .register_type::<FreezeOnPower>()
         .register_type::<CubeSpitter>()
         .register_type::<StandingCubeSpitter>()
         .register_type::<SignalSpitter>()
@@ -157,16 +381,37 @@ pub fn asset_tag_components_plugin(app: &mut App) {
         .register_type::<PressurePlate>()
         .register_type::<DissolveGate>()
         .register_type::<DischargeGate>()
+        .register_type::<TeleportGate>()
+        .register_type::<ForceField>()
         .register_type::<Dissolveable>()
         .register_type::<ChargePad>()
+        .register_type::<MultiCharge>()
+        .register_type::<GateImmune>()
         .register_type::<Door>()
         .register_type::<DoorPole>()
         .register_type::<Inert>()
+        .register_type::<SignalRelay>()
+        .register_type::<MasterSwitch>()
         .register_type::<Immobile>()
+        .register_type::<SpitterConfig>()
+        .register_type::<MaxOwned>()
         .register_type::<PowerButton>()
+        .register_type::<HoldButton>()
         .register_type::<PermanentlyPowered>()
+        .register_type::<StartsPowered>()
+        .register_type::<HandToggleable>()
+        .register_type::<HostileSignals>()
         .register_type::<ExtraDoorPowerRequired>()
+        .register_type::<AutoCloseAfter>()
+        .register_type::<CumulativePower>()
+        .register_type::<Conduit>()
+        .register_type::<SignalFalloff>()
+        .register_type::<NamedTarget>()
         .register_type::<FancyMesh>()
+        .register_type::<ColliderKind>()
+        .register_type::<CompoundCollider>()
+        .register_type::<NoOutline>()
         .register_type::<FinalDoor>()
-        .register_type::<BehindFinalDoor>();
+        .register_type::<BehindFinalDoor>()
+        .register_type::<LevelMetadata>();
 }
@@ -0,0 +1,241 @@
+use std::{collections::HashMap, time::Duration};
+
+use avian3d::prelude::LinearVelocity;
+use bevy::prelude::*;
+
+use crate::{
+    game::{
+        level_timer::LevelTimer,
+        player::{Player, PlayerSpawnPoint, RightHand},
+        signals::Signal,
+        standing_cube_spitter::Tombstone,
+    },
+    GameState,
+};
+
+use super::{
+    asset_loading::{CurrentLevelMeta, NeedsScenePostprocess},
+    asset_tag_components::WeightedCube,
+};
+
+pub fn level_manager_plugin(app: &mut App) {
+    app.init_resource::<LevelManager>()
+        .init_resource::<LevelProgress>()
+        .add_systems(Startup, load_progress)
+        .add_systems(Update, move_player_to_new_spawn)
+        .add_systems(OnEnter(GameState::Win), unlock_next_level)
+        .add_observer(load_level)
+        .add_observer(restart_level);
+}
+
+#[derive(Resource)]
+pub struct LevelManager {
+    pub levels: Vec<String>,
+    pub current: usize,
+}
+
+impl Default for LevelManager {
+    fn default() -> Self {
+        Self {
+            levels: vec![String::from("scenes/jam6scene1.glb")],
+            current: 0,
+        }
+    }
+}
+
+impl LevelManager {
+    pub fn next_level(&self) -> usize {
+        (self.current + 1) % self.levels.len()
+    }
+
+    // Just the file name, for menus
+    pub fn level_label(&self, index: usize) -> String {
+        self.levels
+            .get(index)
+            .and_then(|path| std::path::Path::new(path).file_stem())
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    }
+}
+
+// Levels unlock in order; beating one opens up the next
+#[derive(Resource, Default)]
+pub struct LevelProgress {
+    pub unlocked_through: usize,
+    pub best_times: HashMap<usize, Duration>,
+}
+
+impl LevelProgress {
+    pub fn is_unlocked(&self, index: usize) -> bool {
+        index <= self.unlocked_through
+    }
+}
+
+pub fn unlock_next_level(level_manager: Res<LevelManager>, mut progress: ResMut<LevelProgress>) {
+    progress.unlocked_through = progress.unlocked_through.max(level_manager.current + 1);
+}
+
+// Best times are keyed by level path in the save so reordering levels doesn't shuffle them.
+// There's no filesystem on the web, so progress just lasts the session there.
+#[cfg(not(target_arch = "wasm32"))]
+const PROGRESS_SAVE_PATH: &str = "save/progress.json";
+
+#[cfg(not(target_arch = "wasm32"))]
+fn load_progress(level_manager: Res<LevelManager>, mut progress: ResMut<LevelProgress>) {
+    let Ok(contents) = std::fs::read_to_string(PROGRESS_SAVE_PATH) else {
+        return;
+    };
+
+    let save = match serde_json::from_str::<serde_json::Value>(&contents) {
+        Ok(save) => save,
+        Err(err) => {
+            warn!(?err, "couldn't parse saved progress");
+            return;
+        }
+    };
+
+    if let Some(unlocked_through) = save.get("unlocked_through").and_then(|v| v.as_u64()) {
+        progress.unlocked_through = unlocked_through as usize;
+    }
+
+    if let Some(best_times) = save.get("best_times").and_then(|v| v.as_object()) {
+        for (index, level_path) in level_manager.levels.iter().enumerate() {
+            if let Some(millis) = best_times.get(level_path).and_then(|v| v.as_u64()) {
+                progress
+                    .best_times
+                    .insert(index, Duration::from_millis(millis));
+            }
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn load_progress() {}
+
+pub fn save_progress(level_manager: &LevelManager, progress: &LevelProgress) {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let best_times: serde_json::Map<String, serde_json::Value> = progress
+            .best_times
+            .iter()
+            .filter_map(|(index, time)| {
+                let level_path = level_manager.levels.get(*index)?;
+                Some((level_path.clone(), (time.as_millis() as u64).into()))
+            })
+            .collect();
+
+        let save = serde_json::json!({
+            "unlocked_through": progress.unlocked_through,
+            "best_times": best_times,
+        });
+
+        let path = std::path::Path::new(PROGRESS_SAVE_PATH);
+        if let Some(directory) = path.parent() {
+            let _ = std::fs::create_dir_all(directory);
+        }
+
+        if let Err(err) = std::fs::write(path, save.to_string()) {
+            warn!(?err, "couldn't save progress");
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    let _ = (level_manager, progress);
+}
+
+// The SceneRoot of whatever level is currently loaded
+#[derive(Component)]
+pub struct LevelRoot;
+
+#[derive(Event)]
+pub struct LoadLevel(pub usize);
+
+// Reload the current level from scratch, player included
+#[derive(Event)]
+pub struct RestartLevel;
+
+fn load_level(
+    trigger: Trigger<LoadLevel>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut level_manager: ResMut<LevelManager>,
+    mut current_level_meta: ResMut<CurrentLevelMeta>,
+    q_level_roots: Query<Entity, With<LevelRoot>>,
+    // cubes from spitters and signals in flight are spawned loose, not under the level
+    q_loose_level_entities: Query<
+        Entity,
+        (Or<(With<WeightedCube>, With<Signal>)>, Without<ChildOf>),
+    >,
+    q_children: Query<&Children>,
+) {
+    let index = trigger.event().0;
+    let Some(level_path) = level_manager.levels.get(index) else {
+        warn!("no level at index {}", index);
+        return;
+    };
+
+    let scene_handle = asset_server.load(GltfAssetLabel::Scene(0).from_asset(level_path.clone()));
+
+    // despawning fires the OnRemove<Powered> observers, which shouldn't go tweening anything
+    // that's on its way out
+    for level_root in &q_level_roots {
+        for entity in q_children.iter_descendants(level_root) {
+            commands.entity(entity).try_insert(Tombstone);
+        }
+        commands.entity(level_root).despawn();
+    }
+
+    for loose_entity in &q_loose_level_entities {
+        commands
+            .entity(loose_entity)
+            .try_insert(Tombstone)
+            .try_despawn();
+    }
+
+    info!("Loading level {}: {}", index, level_path);
+    level_manager.current = index;
+    current_level_meta.0 = None;
+    commands.spawn((NeedsScenePostprocess(scene_handle), LevelRoot));
+}
+
+// The player is the only StateScoped(GameState::Playing) thing that outlives a level, and we
+// stay in Playing here, so it gets despawned by hand. player.rs spawns a new one at the
+// reloaded PlayerSpawnPoint.
+fn restart_level(
+    _trigger: Trigger<RestartLevel>,
+    mut commands: Commands,
+    level_manager: Res<LevelManager>,
+    mut level_timer: ResMut<LevelTimer>,
+    q_player: Query<Entity, With<Player>>,
+) {
+    for player in &q_player {
+        commands.entity(player).despawn();
+    }
+
+    level_timer.stopwatch.reset();
+    level_timer.new_best = false;
+
+    commands.trigger(LoadLevel(level_manager.current));
+}
+
+// The player outlives level loads, so put them at the new level's spawn when it shows up
+fn move_player_to_new_spawn(
+    q_new_spawn_points: Query<&Transform, Added<PlayerSpawnPoint>>,
+    maybe_player: Option<
+        Single<
+            (&mut Transform, &mut LinearVelocity, &mut RightHand),
+            (With<Player>, Without<PlayerSpawnPoint>),
+        >,
+    >,
+) {
+    let Some(spawn_point) = q_new_spawn_points.iter().next() else {
+        return;
+    };
+
+    if let Some(mut player) = maybe_player {
+        let (transform, velocity, right_hand) = &mut *player;
+        **transform = *spawn_point;
+        **velocity = LinearVelocity::ZERO;
+        right_hand.clear();
+    }
+}
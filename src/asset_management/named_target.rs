@@ -0,0 +1,123 @@
+use std::marker::PhantomData;
+
+use bevy::prelude::*;
+
+/// Bevity can't express Entity references since ids aren't stable across loads, so authored
+/// components point at other entities by Name with this instead
+#[derive(Reflect, Debug, Clone, Default)]
+pub struct NamedTarget {
+    pub name: String,
+}
+
+/// Authored components holding NamedTargets. Once every name is found in the world,
+/// resolve_named_targets inserts `Resolved` alongside the authored component.
+pub trait ResolveNamedTargets: Component {
+    type Resolved: Component;
+
+    fn targets(&self) -> Vec<&NamedTarget>;
+
+    // `entities` lines up with `targets()`
+    fn resolve(&self, entities: &[Entity]) -> Self::Resolved;
+}
+
+/// On an authored component whose targets weren't all around yet (a later scene, something not
+/// spawned yet...). It keeps getting retried until they are.
+#[derive(Component)]
+pub struct PendingNamedTargets<T: ResolveNamedTargets>(PhantomData<T>);
+
+impl<T: ResolveNamedTargets> Default for PendingNamedTargets<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+pub fn resolve_named_targets<T: ResolveNamedTargets>(
+    mut commands: Commands,
+    q_unresolved: Query<
+        (Entity, &T, Has<PendingNamedTargets<T>>),
+        Or<(Added<T>, With<PendingNamedTargets<T>>)>,
+    >,
+    q_names: Query<(Entity, &Name)>,
+) {
+    for (entity, authored, already_pending) in &q_unresolved {
+        let mut resolved_entities = Vec::new();
+
+        for target in authored.targets() {
+            match q_names
+                .iter()
+                .find(|(_, name)| name.as_str() == target.name)
+            {
+                Some((target_entity, _)) => resolved_entities.push(target_entity),
+                // only complain the first time, it gets checked again every run
+                None if !already_pending => warn!(
+                    "{} on {:?} points at {:?}, but nothing has that name yet",
+                    std::any::type_name::<T>(),
+                    entity,
+                    target.name
+                ),
+                None => {}
+            }
+        }
+
+        if resolved_entities.len() == authored.targets().len() {
+            commands
+                .entity(entity)
+                .insert(authored.resolve(&resolved_entities))
+                .remove::<PendingNamedTargets<T>>();
+        } else if !already_pending {
+            commands
+                .entity(entity)
+                .insert(PendingNamedTargets::<T>::default());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Component)]
+    struct PointsAt(NamedTarget);
+
+    #[derive(Component)]
+    struct ResolvedPointsAt(Entity);
+
+    impl ResolveNamedTargets for PointsAt {
+        type Resolved = ResolvedPointsAt;
+
+        fn targets(&self) -> Vec<&NamedTarget> {
+            vec![&self.0]
+        }
+
+        fn resolve(&self, entities: &[Entity]) -> Self::Resolved {
+            ResolvedPointsAt(entities[0])
+        }
+    }
+
+    #[test]
+    fn target_spawned_later_still_resolves() {
+        let mut app = App::new();
+        app.add_systems(Update, resolve_named_targets::<PointsAt>);
+
+        let pointer = app
+            .world_mut()
+            .spawn(PointsAt(NamedTarget {
+                name: "Later".to_string(),
+            }))
+            .id();
+        app.update();
+        assert!(app.world().get::<ResolvedPointsAt>(pointer).is_none());
+
+        let target = app.world_mut().spawn(Name::new("Later")).id();
+        app.update();
+
+        assert_eq!(
+            app.world().get::<ResolvedPointsAt>(pointer).unwrap().0,
+            target
+        );
+        assert!(!app
+            .world()
+            .entity(pointer)
+            .contains::<PendingNamedTargets<PointsAt>>());
+    }
+}
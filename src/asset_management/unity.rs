@@ -1,13 +1,22 @@
 #[cfg(not(target_arch = "wasm32"))]
 #[cfg(feature = "dev")]
 use bevy::remote::{http::RemoteHttpPlugin, RemotePlugin};
-use bevy::{ecs::reflect::ReflectCommandExt, prelude::*, reflect::serde::ReflectDeserializer};
+use bevy::{
+    ecs::reflect::ReflectCommandExt, gltf::GltfSceneExtras, prelude::*,
+    reflect::serde::ReflectDeserializer,
+};
 use serde::de::DeserializeSeed;
 use serde_json::Value;
 #[cfg(not(target_arch = "wasm32"))]
 #[cfg(feature = "dev")]
 use std::net::Ipv4Addr;
 
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(feature = "dev")]
+use crate::dev::remote_methods::with_dev_methods;
+
+use super::asset_tag_components::LevelMetadata;
+
 pub struct UnityPlugin {
     #[cfg(not(target_arch = "wasm32"))]
     #[cfg(feature = "dev")]
@@ -30,26 +39,48 @@ impl Plugin for UnityPlugin {
         #[cfg(feature = "dev")]
         if self.brp {
             app.add_plugins((
-                RemotePlugin::default(),
+                with_dev_methods(RemotePlugin::default()),
                 RemoteHttpPlugin::default()
                     .with_address(Ipv4Addr::LOCALHOST)
                     .with_port(5309),
             ));
         }
+        #[cfg(feature = "dev")]
+        app.init_resource::<BevityLoadErrors>();
+
         app.add_observer(apply_bevity_components);
     }
 }
 
+// Components from exported scenes that failed to deserialize, shown in a dev panel so
+// typos in the exporter don't just silently do nothing
+#[cfg(feature = "dev")]
+#[derive(Resource, Default)]
+pub struct BevityLoadErrors(pub Vec<BevityLoadError>);
+
+#[cfg(feature = "dev")]
+pub struct BevityLoadError {
+    pub entity: Entity,
+    pub name: Option<String>,
+    pub json: String,
+    pub error: String,
+}
+
+// Scene extras end up on the scene's root entity, which is where level-wide data like
+// LevelMetadata gets authored
 fn apply_bevity_components(
-    trigger: Trigger<OnAdd, (GltfExtras,)>,
+    trigger: Trigger<OnAdd, (GltfExtras, GltfSceneExtras)>,
     type_registry: Res<AppTypeRegistry>,
     gltf_extras: Query<&GltfExtras>,
+    gltf_scene_extras: Query<&GltfSceneExtras>,
     names: Query<&Name>,
     mut commands: Commands,
+    #[cfg(feature = "dev")] mut load_errors: ResMut<BevityLoadErrors>,
 ) {
     let entity = trigger.target();
     let gltf_extra = gltf_extras.get(entity).map(|v| &v.value);
-    for extras in [gltf_extra].iter().filter_map(|p| p.ok()) {
+    let gltf_scene_extra = gltf_scene_extras.get(entity).map(|v| &v.value);
+    for extras in [gltf_extra, gltf_scene_extra].iter().filter_map(|p| p.ok()) {
         let obj = match serde_json::from_str(extras) {
             Ok(Value::Object(obj)) => obj,
             Ok(Value::Null) => {
@@ -79,10 +110,26 @@ fn apply_bevity_components(
                     ?err,
                     "gltf extras which could not be parsed as a serde_json::Value::Object"
                 );
+
+                // broken JSON means none of the entity's components made it in either
+                #[cfg(feature = "dev")]
+                load_errors.0.push(BevityLoadError {
+                    entity,
+                    name: name.map(|name| name.to_string()),
+                    json: extras.to_string(),
+                    error: err.to_string(),
+                });
+
                 continue;
             }
         };
 
+        if let Some(Value::Object(level_metadata)) = obj.get("levelMetadata") {
+            commands
+                .entity(entity)
+                .insert(parse_level_metadata(level_metadata));
+        }
+
         let bevity = match obj.get("bevity") {
             Some(Value::Array(components)) => components,
             _ => continue,
@@ -100,6 +147,15 @@ fn apply_bevity_components(
                         ?obj,
                         "failed to instantiate component data from glTF data"
                     );
+
+                    #[cfg(feature = "dev")]
+                    load_errors.0.push(BevityLoadError {
+                        entity,
+                        name: names.get(entity).ok().map(|name| name.to_string()),
+                        json: json_component.to_string(),
+                        error: err.to_string(),
+                    });
+
                     continue;
                 }
             };
@@ -108,3 +164,23 @@ fn apply_bevity_components(
         }
     }
 }
+
+// Missing fields just fall back to their defaults
+fn parse_level_metadata(obj: &serde_json::Map<String, Value>) -> LevelMetadata {
+    let string_field = |key: &str| {
+        obj.get(key)
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string()
+    };
+
+    LevelMetadata {
+        name: string_field("name"),
+        author: string_field("author"),
+        par_time: obj
+            .get("par_time")
+            .and_then(Value::as_f64)
+            .unwrap_or_default() as f32,
+        music_track: string_field("music_track"),
+    }
+}
@@ -0,0 +1,36 @@
+use bevy::prelude::*;
+use bevy_inspector_egui::bevy_egui::{egui, EguiContextPass, EguiContexts};
+
+use crate::asset_management::unity::BevityLoadErrors;
+
+pub fn bevity_errors_plugin(app: &mut App) {
+    app.add_systems(EguiContextPass, bevity_errors_window);
+}
+
+fn bevity_errors_window(mut contexts: EguiContexts, mut load_errors: ResMut<BevityLoadErrors>) {
+    if load_errors.0.is_empty() {
+        return;
+    }
+
+    let Some(ctx) = contexts.try_ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new(format!("Bevity Load Errors ({})", load_errors.0.len())).show(ctx, |ui| {
+        if ui.button("Clear").clicked() {
+            load_errors.0.clear();
+        }
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            for load_error in &load_errors.0 {
+                match &load_error.name {
+                    Some(name) => ui.strong(format!("{} ({})", load_error.entity, name)),
+                    None => ui.strong(format!("{}", load_error.entity)),
+                };
+                ui.monospace(&load_error.json);
+                ui.colored_label(egui::Color32::LIGHT_RED, &load_error.error);
+                ui.separator();
+            }
+        });
+    });
+}
@@ -0,0 +1,185 @@
+use avian3d::prelude::{Collider, CollisionLayers, DebugRender, PhysicsDebugPlugin, PhysicsGizmos};
+use bevy::{color::palettes::css::LIME, prelude::*};
+use bevy_enhanced_input::{
+    events::Completed,
+    prelude::{Actions, Binding, InputAction},
+};
+
+use crate::game::{input::UpdateInputContext, GameLayer};
+
+pub fn collider_overlay_plugin(app: &mut App) {
+    app.add_plugins(PhysicsDebugPlugin::default())
+        .init_resource::<ColliderOverlay>()
+        .add_systems(Startup, disable_physics_gizmos)
+        .add_systems(
+            Update,
+            (
+                apply_collider_overlay.run_if(resource_changed::<ColliderOverlay>),
+                mark_device_colliders,
+            )
+                .chain(),
+        )
+        .add_observer(bind_collider_overlay)
+        .add_observer(toggle_collider_overlay);
+}
+
+#[derive(Resource, Default)]
+pub struct ColliderOverlay {
+    pub enabled: bool,
+}
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+pub struct ToggleColliderOverlay;
+
+fn bind_collider_overlay(
+    trigger: Trigger<Binding<UpdateInputContext>>,
+    mut q_update_input_manager: Query<&mut Actions<UpdateInputContext>>,
+) {
+    if let Ok(mut actions) = q_update_input_manager.get_mut(trigger.target()) {
+        actions.bind::<ToggleColliderOverlay>().to(KeyCode::F3);
+    }
+}
+
+// The debug plugin is only here for the overlay, so its gizmos stay off until it's turned on.
+// Whatever PhysicsGizmos were configured are left alone for when it's switched back off.
+fn disable_physics_gizmos(mut config_store: ResMut<GizmoConfigStore>) {
+    let (config, _) = config_store.config_mut::<PhysicsGizmos>();
+    config.enabled = false;
+}
+
+// Set on the colliders the overlay put a DebugRender on, so it never touches anyone else's
+#[derive(Component)]
+struct OverlayDebugRender;
+
+fn device_debug_render(overlay_enabled: bool) -> DebugRender {
+    if overlay_enabled {
+        DebugRender::collider(LIME.into())
+    } else {
+        DebugRender::none()
+    }
+}
+
+fn is_device_collider(layers: &CollisionLayers) -> bool {
+    layers.memberships.has_all(GameLayer::Device)
+}
+
+fn mark_device_colliders(
+    mut commands: Commands,
+    overlay: Res<ColliderOverlay>,
+    q_colliders: Query<(Entity, &CollisionLayers), (With<Collider>, Without<DebugRender>)>,
+) {
+    for (entity, layers) in &q_colliders {
+        if is_device_collider(layers) {
+            commands
+                .entity(entity)
+                .try_insert((device_debug_render(overlay.enabled), OverlayDebugRender));
+        }
+    }
+}
+
+// Only the marked device colliders draw while the overlay is on (no shape casts, contacts,
+// axes...), and the gizmo config goes back to how it was when it's turned off
+fn apply_collider_overlay(
+    overlay: Res<ColliderOverlay>,
+    mut previous_gizmos: Local<Option<(GizmoConfig, PhysicsGizmos)>>,
+    mut config_store: ResMut<GizmoConfigStore>,
+    mut q_overlay_renders: Query<&mut DebugRender, With<OverlayDebugRender>>,
+) {
+    let (config, physics_gizmos) = config_store.config_mut::<PhysicsGizmos>();
+
+    if overlay.enabled {
+        if previous_gizmos.is_none() {
+            *previous_gizmos = Some((config.clone(), physics_gizmos.clone()));
+        }
+        config.enabled = true;
+        config.depth_test = false;
+        *physics_gizmos = PhysicsGizmos::none();
+    } else if let Some((previous_config, previous_physics_gizmos)) = previous_gizmos.take() {
+        *config = previous_config;
+        *physics_gizmos = previous_physics_gizmos;
+    }
+
+    for mut debug_render in &mut q_overlay_renders {
+        *debug_render = device_debug_render(overlay.enabled);
+    }
+}
+
+fn toggle_collider_overlay(
+    _trigger: Trigger<Completed<ToggleColliderOverlay>>,
+    mut overlay: ResMut<ColliderOverlay>,
+) {
+    overlay.enabled = !overlay.enabled;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use avian3d::prelude::LayerMask;
+
+    #[test]
+    fn overlay_marks_device_colliders_and_restores_gizmos() {
+        let mut app = App::new();
+        app.init_resource::<ColliderOverlay>()
+            .init_resource::<GizmoConfigStore>()
+            .add_systems(Startup, disable_physics_gizmos)
+            .add_systems(
+                Update,
+                (
+                    apply_collider_overlay.run_if(resource_changed::<ColliderOverlay>),
+                    mark_device_colliders,
+                )
+                    .chain(),
+            );
+        let configured_gizmos = PhysicsGizmos {
+            shapecast_color: Some(LIME.into()),
+            ..default()
+        };
+        app.world_mut()
+            .resource_mut::<GizmoConfigStore>()
+            .insert(GizmoConfig::default(), configured_gizmos.clone());
+
+        let device_collider = app
+            .world_mut()
+            .spawn((
+                Collider::cuboid(1.0, 1.0, 1.0),
+                CollisionLayers::new(GameLayer::Device, LayerMask::ALL),
+            ))
+            .id();
+        let wall_collider = app
+            .world_mut()
+            .spawn((
+                Collider::cuboid(1.0, 1.0, 1.0),
+                CollisionLayers::new(GameLayer::Default, LayerMask::ALL),
+            ))
+            .id();
+        app.update();
+
+        app.world_mut().resource_mut::<ColliderOverlay>().enabled = true;
+        app.update();
+
+        let device_render = app.world().get::<DebugRender>(device_collider).unwrap();
+        assert_eq!(device_render.collider_color, Some(LIME.into()));
+        assert!(app.world().get::<DebugRender>(wall_collider).is_none());
+        let (config, _) = app
+            .world()
+            .resource::<GizmoConfigStore>()
+            .config::<PhysicsGizmos>();
+        assert!(config.enabled);
+
+        app.world_mut().resource_mut::<ColliderOverlay>().enabled = false;
+        app.update();
+
+        let device_render = app.world().get::<DebugRender>(device_collider).unwrap();
+        assert_eq!(device_render.collider_color, None);
+        let (config, physics_gizmos) = app
+            .world()
+            .resource::<GizmoConfigStore>()
+            .config::<PhysicsGizmos>();
+        assert!(!config.enabled);
+        assert_eq!(
+            physics_gizmos.shapecast_color,
+            configured_gizmos.shapecast_color
+        );
+    }
+}
@@ -0,0 +1,52 @@
+use bevy::prelude::*;
+use bevy_enhanced_input::{
+    events::Completed,
+    prelude::{Actions, Binding, InputAction},
+};
+
+use crate::game::{
+    gravity::{GravitySettings, DEFAULT_GRAVITY},
+    input::UpdateInputContext,
+};
+
+// fractions of normal gravity, F10 steps through them and wraps around
+const GRAVITY_STEPS: [f32; 4] = [1.0, 0.5, 0.25, 2.0];
+
+pub fn gravity_cycle_plugin(app: &mut App) {
+    app.add_observer(bind_gravity_cycle)
+        .add_observer(cycle_gravity);
+}
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+pub struct CycleGravity;
+
+fn bind_gravity_cycle(
+    trigger: Trigger<Binding<UpdateInputContext>>,
+    mut q_update_input_manager: Query<&mut Actions<UpdateInputContext>>,
+) {
+    if let Ok(mut actions) = q_update_input_manager.get_mut(trigger.target()) {
+        actions.bind::<CycleGravity>().to(KeyCode::F10);
+    }
+}
+
+fn cycle_gravity(
+    _trigger: Trigger<Completed<CycleGravity>>,
+    mut gravity_settings: ResMut<GravitySettings>,
+) {
+    // whatever it's closest to now, so this still works after something else changed it
+    let current_scale = gravity_settings.scale();
+    let current_step = GRAVITY_STEPS
+        .iter()
+        .enumerate()
+        .min_by(|(_, a), (_, b)| {
+            (*a - current_scale)
+                .abs()
+                .total_cmp(&(*b - current_scale).abs())
+        })
+        .map_or(0, |(index, _)| index);
+
+    let next_scale = GRAVITY_STEPS[(current_step + 1) % GRAVITY_STEPS.len()];
+    gravity_settings.magnitude = DEFAULT_GRAVITY * next_scale;
+    info!("gravity set to {}x", next_scale);
+}
@@ -0,0 +1,36 @@
+use bevy::prelude::*;
+use bevy_enhanced_input::{
+    events::Completed,
+    prelude::{Actions, Binding, InputAction},
+};
+
+use crate::{
+    asset_management::level_manager::{LevelManager, LoadLevel},
+    game::input::UpdateInputContext,
+};
+
+pub fn level_skip_plugin(app: &mut App) {
+    app.add_observer(bind_next_level)
+        .add_observer(skip_to_next_level);
+}
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+pub struct NextLevel;
+
+fn bind_next_level(
+    trigger: Trigger<Binding<UpdateInputContext>>,
+    mut q_update_input_manager: Query<&mut Actions<UpdateInputContext>>,
+) {
+    if let Ok(mut actions) = q_update_input_manager.get_mut(trigger.target()) {
+        actions.bind::<NextLevel>().to(KeyCode::F4);
+    }
+}
+
+fn skip_to_next_level(
+    _trigger: Trigger<Completed<NextLevel>>,
+    mut commands: Commands,
+    level_manager: Res<LevelManager>,
+) {
+    commands.trigger(LoadLevel(level_manager.next_level()));
+}
@@ -0,0 +1,39 @@
+use bevity_errors::bevity_errors_plugin;
+use bevy::prelude::*;
+use collider_overlay::collider_overlay_plugin;
+use gravity_cycle::gravity_cycle_plugin;
+use level_skip::level_skip_plugin;
+use noclip::noclip_plugin;
+use overhead_camera::overhead_camera_plugin;
+use power_inspector::power_inspector_plugin;
+#[cfg(not(target_arch = "wasm32"))]
+use screenshot::screenshot_plugin;
+use time_scale::time_scale_plugin;
+
+pub mod bevity_errors;
+pub mod collider_overlay;
+pub mod gravity_cycle;
+pub mod level_skip;
+pub mod noclip;
+pub mod overhead_camera;
+pub mod power_inspector;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod remote_methods;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod screenshot;
+pub mod time_scale;
+
+pub fn dev_plugins(app: &mut App) {
+    app.add_plugins((
+        power_inspector_plugin,
+        collider_overlay_plugin,
+        level_skip_plugin,
+        overhead_camera_plugin,
+        noclip_plugin,
+        time_scale_plugin,
+        gravity_cycle_plugin,
+        bevity_errors_plugin,
+        #[cfg(not(target_arch = "wasm32"))]
+        screenshot_plugin,
+    ));
+}
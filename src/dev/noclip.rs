@@ -0,0 +1,124 @@
+use avian3d::prelude::{LinearVelocity, RigidBodyDisabled, TransformInterpolation};
+use bevy::prelude::*;
+use bevy_enhanced_input::{
+    events::Completed,
+    prelude::{ActionValue, Actions, Binding, InputAction},
+};
+
+use crate::{
+    game::{
+        input::{FixedInputContext, Jump, Movement, Sprint, UpdateInputContext},
+        player::{NoclipState, Player, PlayerMovementConfig},
+    },
+    GameState, MainCamera,
+};
+
+const NOCLIP_SPEED: f32 = 60.;
+
+// Flies the player wherever the camera is looking, straight through walls
+pub fn noclip_plugin(app: &mut App) {
+    app.add_systems(
+        FixedUpdate,
+        fly_player
+            .run_if(in_state(GameState::Playing).and(|noclip: Res<NoclipState>| noclip.active)),
+    )
+    .add_systems(OnExit(GameState::Playing), stop_noclip)
+    .add_observer(bind_toggle_noclip)
+    .add_observer(bind_noclip_down)
+    .add_observer(toggle_noclip);
+}
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+pub struct ToggleNoclip;
+
+// jump goes up, this goes down
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+pub struct NoclipDown;
+
+fn bind_toggle_noclip(
+    trigger: Trigger<Binding<UpdateInputContext>>,
+    mut q_update_input_manager: Query<&mut Actions<UpdateInputContext>>,
+) {
+    if let Ok(mut actions) = q_update_input_manager.get_mut(trigger.target()) {
+        actions.bind::<ToggleNoclip>().to(KeyCode::F7);
+    }
+}
+
+fn bind_noclip_down(
+    trigger: Trigger<Binding<FixedInputContext>>,
+    mut q_fixed_input_manager: Query<&mut Actions<FixedInputContext>>,
+) {
+    if let Ok(mut actions) = q_fixed_input_manager.get_mut(trigger.target()) {
+        actions.bind::<NoclipDown>().to(KeyCode::ControlLeft);
+    }
+}
+
+fn toggle_noclip(
+    _trigger: Trigger<Completed<ToggleNoclip>>,
+    mut commands: Commands,
+    mut noclip_state: ResMut<NoclipState>,
+    state: Res<State<GameState>>,
+    q_player: Query<Entity, With<Player>>,
+) {
+    if *state.get() != GameState::Playing {
+        return;
+    }
+
+    let Ok(player) = q_player.single() else {
+        return;
+    };
+
+    noclip_state.active = !noclip_state.active;
+
+    if noclip_state.active {
+        // interpolation would keep pulling the transform back to the last physics position
+        commands
+            .entity(player)
+            .insert(RigidBodyDisabled)
+            .remove::<TransformInterpolation>();
+    } else {
+        // pick physics back up from wherever we flew to
+        commands
+            .entity(player)
+            .remove::<RigidBodyDisabled>()
+            .insert((LinearVelocity::ZERO, TransformInterpolation));
+    }
+}
+
+// the player gets despawned with the level, so just forget about it
+fn stop_noclip(mut noclip_state: ResMut<NoclipState>) {
+    noclip_state.active = false;
+}
+
+fn fly_player(
+    mut player_transform: Single<&mut Transform, (With<Player>, Without<MainCamera>)>,
+    camera: Single<&Transform, With<MainCamera>>,
+    input: Single<&Actions<FixedInputContext>>,
+    movement_config: Res<PlayerMovementConfig>,
+    time: Res<Time>,
+) {
+    let mut direction = Vec3::ZERO;
+
+    if let Ok(ActionValue::Axis2D(movement)) = input.value::<Movement>() {
+        direction += camera.forward() * movement.y + camera.right() * movement.x;
+    }
+
+    if matches!(input.value::<Jump>(), Ok(ActionValue::Bool(true))) {
+        direction += Vec3::Y;
+    }
+
+    if matches!(input.value::<NoclipDown>(), Ok(ActionValue::Bool(true))) {
+        direction -= Vec3::Y;
+    }
+
+    let speed_multiplier = if matches!(input.value::<Sprint>(), Ok(ActionValue::Bool(true))) {
+        movement_config.sprint_multiplier
+    } else {
+        1.0
+    };
+
+    player_transform.translation +=
+        direction.normalize_or_zero() * NOCLIP_SPEED * speed_multiplier * time.delta_secs();
+}
@@ -0,0 +1,111 @@
+use bevy::{
+    prelude::*,
+    render::camera::{ScalingMode, Viewport},
+    window::PrimaryWindow,
+};
+use bevy_enhanced_input::{
+    events::Completed,
+    prelude::{Actions, Binding, InputAction},
+};
+
+use crate::game::{input::UpdateInputContext, player::Player};
+
+// how much of the room above the player is still drawn, anything higher (ceilings) is clipped
+const OVERHEAD_CLIP_HEIGHT: f32 = 30.;
+const OVERHEAD_VIEW_HEIGHT: f32 = 400.;
+// fraction of the window the overhead view takes up, in the top right corner
+const OVERHEAD_VIEWPORT_FRACTION: f32 = 0.3;
+const OVERHEAD_VIEWPORT_MARGIN_PX: u32 = 16;
+
+// Top down view of the room around the player for level design. Renders on top of the main
+// camera in a corner, without the section outlines or postprocessing since those are only set
+// up on the main camera.
+pub fn overhead_camera_plugin(app: &mut App) {
+    app.add_systems(Update, follow_player_overhead)
+        .add_observer(bind_overhead_camera)
+        .add_observer(toggle_overhead_camera);
+}
+
+#[derive(Component)]
+pub struct OverheadCamera;
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+pub struct ToggleOverheadCamera;
+
+fn bind_overhead_camera(
+    trigger: Trigger<Binding<UpdateInputContext>>,
+    mut q_update_input_manager: Query<&mut Actions<UpdateInputContext>>,
+) {
+    if let Ok(mut actions) = q_update_input_manager.get_mut(trigger.target()) {
+        actions.bind::<ToggleOverheadCamera>().to(KeyCode::F5);
+    }
+}
+
+fn toggle_overhead_camera(
+    _trigger: Trigger<Completed<ToggleOverheadCamera>>,
+    mut commands: Commands,
+    q_overhead_camera: Query<Entity, With<OverheadCamera>>,
+) {
+    if let Ok(overhead_camera) = q_overhead_camera.single() {
+        commands.entity(overhead_camera).despawn();
+        return;
+    }
+
+    commands.spawn((
+        OverheadCamera,
+        Camera3d::default(),
+        Camera {
+            // after the main camera, so it draws over its corner
+            order: 1,
+            hdr: true,
+            // the viewport gets sized to the window in follow_player_overhead
+            is_active: false,
+            ..default()
+        },
+        Projection::Orthographic(OrthographicProjection {
+            scaling_mode: ScalingMode::FixedVertical {
+                viewport_height: OVERHEAD_VIEW_HEIGHT,
+            },
+            ..OrthographicProjection::default_3d()
+        }),
+        Msaa::Off,
+    ));
+}
+
+fn follow_player_overhead(
+    mut q_overhead_camera: Query<(&mut Camera, &mut Transform), With<OverheadCamera>>,
+    q_player: Query<&GlobalTransform, With<Player>>,
+    window: Single<&Window, With<PrimaryWindow>>,
+) {
+    let Ok((mut camera, mut camera_transform)) = q_overhead_camera.single_mut() else {
+        return;
+    };
+
+    let window_size = window.physical_size();
+    let viewport_size = (window_size.as_vec2() * OVERHEAD_VIEWPORT_FRACTION).as_uvec2();
+    if viewport_size.min_element() == 0 {
+        camera.is_active = false;
+        return;
+    }
+
+    camera.viewport = Some(Viewport {
+        physical_position: UVec2::new(
+            window_size
+                .x
+                .saturating_sub(viewport_size.x + OVERHEAD_VIEWPORT_MARGIN_PX),
+            OVERHEAD_VIEWPORT_MARGIN_PX,
+        ),
+        physical_size: viewport_size,
+        ..default()
+    });
+    camera.is_active = true;
+
+    if let Ok(player_transform) = q_player.single() {
+        // -Z up on screen, so the map stays put no matter where the player looks
+        *camera_transform = Transform::from_translation(
+            player_transform.translation() + Vec3::Y * OVERHEAD_CLIP_HEIGHT,
+        )
+        .looking_to(Vec3::NEG_Y, Vec3::NEG_Z);
+    }
+}
@@ -0,0 +1,100 @@
+use bevy::prelude::*;
+use bevy_inspector_egui::bevy_egui::{egui, EguiContextPass, EguiContexts};
+
+use crate::{
+    asset_management::asset_tag_components::{
+        ChargePad, CubeSpitter, DoorPole, SignalSpitter, StandingCubeSpitter, WeightedCube,
+    },
+    game::{power_graph::PowerGraph, signals::Powered},
+};
+
+pub fn power_inspector_plugin(app: &mut App) {
+    app.add_systems(EguiContextPass, power_inspector_window);
+}
+
+fn power_inspector_window(
+    mut contexts: EguiContexts,
+    q_powered: Query<
+        (
+            Entity,
+            Option<&Name>,
+            Has<WeightedCube>,
+            Has<CubeSpitter>,
+            Has<StandingCubeSpitter>,
+            Has<SignalSpitter>,
+            Has<DoorPole>,
+            Has<ChargePad>,
+        ),
+        With<Powered>,
+    >,
+    q_names: Query<&Name>,
+    power_graph: Res<PowerGraph>,
+) {
+    let Some(ctx) = contexts.try_ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new("Powered Entities")
+        .default_open(false)
+        .show(ctx, |ui| {
+            egui::Grid::new("powered_entities_grid")
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.strong("Entity");
+                    ui.strong("Kind");
+                    ui.strong("Powered By");
+                    ui.strong("Powers");
+                    ui.end_row();
+
+                    let describe = |entities: &[Entity]| {
+                        if entities.is_empty() {
+                            return String::from("-");
+                        }
+
+                        entities
+                            .iter()
+                            .map(|entity| match q_names.get(*entity) {
+                                Ok(name) => format!("{} ({})", entity, name),
+                                Err(_) => format!("{}", entity),
+                            })
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    };
+
+                    for (
+                        entity,
+                        maybe_name,
+                        is_cube,
+                        is_cube_spitter,
+                        is_standing_cube_spitter,
+                        is_signal_spitter,
+                        is_door_pole,
+                        is_charge_pad,
+                    ) in &q_powered
+                    {
+                        let kind = if is_cube {
+                            "Cube"
+                        } else if is_cube_spitter || is_standing_cube_spitter {
+                            "Cube Spitter"
+                        } else if is_signal_spitter {
+                            "Signal Spitter"
+                        } else if is_door_pole {
+                            "Door Pole"
+                        } else if is_charge_pad {
+                            "Charge Pad"
+                        } else {
+                            "Other"
+                        };
+
+                        match maybe_name {
+                            Some(name) => ui.label(format!("{} ({})", entity, name)),
+                            None => ui.label(format!("{}", entity)),
+                        };
+                        ui.label(kind);
+                        ui.label(describe(power_graph.sources_of(entity)));
+                        ui.label(describe(power_graph.targets_of(entity)));
+                        ui.end_row();
+                    }
+                });
+        });
+}
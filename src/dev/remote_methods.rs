@@ -0,0 +1,193 @@
+//! Extra BRP methods for poking at a running game, served on the same endpoint Bevity uses
+//! (http://127.0.0.1:5309). All of them are plain JSON-RPC 2.0 calls:
+//!
+//! - `dev/spawn_cube` - no params. Drops a weighted cube just in front of the player and
+//!   returns `{ "entity": <bits> }`.
+//! - `dev/toggle_powered` - `{ "entity": <bits> }`. Adds or removes Powered on the entity and
+//!   returns `{ "powered": bool }`.
+//! - `dev/set_game_state` - `{ "state": "Loading" | "MainMenu" | "Playing" | "Win" }`.
+//! - `dev/teleport_player` - `{ "position": [x, y, z] }`, or no params to go back to the
+//!   level's PlayerSpawnPoint.
+//! - `dev/screenshot` - no params. Saves the current frame to the ScreenshotConfig directory.
+//!
+//! e.g. `curl -X POST 127.0.0.1:5309 -d '{"jsonrpc":"2.0","id":1,"method":"dev/spawn_cube"}'`
+
+use avian3d::prelude::{LinearVelocity, RigidBody, RotationInterpolation, TransformInterpolation};
+use bevy::{
+    prelude::*,
+    remote::{error_codes, BrpError, BrpResult, RemotePlugin},
+};
+use serde_json::{json, Map, Value};
+
+use crate::{
+    asset_management::{
+        asset_loading::GameAssets,
+        asset_tag_components::{WeightedCube, WeightedCubeColors},
+    },
+    dev::screenshot::CaptureScreenshot,
+    game::{
+        player::{Player, PlayerSpawnPoint},
+        signals::Powered,
+    },
+    GameState, MainCamera,
+};
+
+const SPAWNED_CUBE_DISTANCE: f32 = 15.;
+
+pub fn with_dev_methods(remote_plugin: RemotePlugin) -> RemotePlugin {
+    remote_plugin
+        .with_method("dev/spawn_cube", spawn_cube)
+        .with_method("dev/toggle_powered", toggle_powered)
+        .with_method("dev/set_game_state", set_game_state)
+        .with_method("dev/teleport_player", teleport_player)
+        .with_method("dev/screenshot", screenshot)
+}
+
+fn parse_params(params: Option<Value>) -> Result<Map<String, Value>, BrpError> {
+    match params {
+        Some(Value::Object(params)) => Ok(params),
+        Some(_) => Err(invalid_params("params should be an object".to_string())),
+        None => Err(invalid_params("missing params".to_string())),
+    }
+}
+
+fn param<'a>(params: &'a Map<String, Value>, key: &str) -> Result<&'a Value, BrpError> {
+    params
+        .get(key)
+        .ok_or_else(|| invalid_params(format!("missing param {key}")))
+}
+
+fn invalid_params(message: String) -> BrpError {
+    BrpError {
+        code: error_codes::INVALID_PARAMS,
+        message,
+        data: None,
+    }
+}
+
+fn spawn_cube(
+    In(_params): In<Option<Value>>,
+    mut commands: Commands,
+    game_assets: Res<GameAssets>,
+    q_player: Query<&GlobalTransform, With<Player>>,
+    q_camera: Query<&GlobalTransform, With<MainCamera>>,
+) -> BrpResult {
+    let Ok(player_transform) = q_player.single() else {
+        return Err(BrpError::internal("no player in the world"));
+    };
+
+    // the player capsule doesn't rotate, so face wherever the camera is looking
+    let forward = q_camera
+        .single()
+        .map(|camera_transform| camera_transform.forward().with_y(0.).normalize_or_zero())
+        .unwrap_or_default();
+
+    let cube = commands
+        .spawn((
+            SceneRoot(game_assets.weighted_cube_cyan.clone()),
+            Transform::from_translation(
+                player_transform.translation() + forward * SPAWNED_CUBE_DISTANCE + Vec3::Y * 5.,
+            ),
+            RigidBody::Dynamic,
+            TransformInterpolation,
+            RotationInterpolation,
+            WeightedCube {
+                color: WeightedCubeColors::Cyan,
+            },
+        ))
+        .id();
+
+    Ok(json!({ "entity": cube.to_bits() }))
+}
+
+fn toggle_powered(
+    In(params): In<Option<Value>>,
+    mut commands: Commands,
+    q_entities: Query<Has<Powered>>,
+) -> BrpResult {
+    let params = parse_params(params)?;
+    let entity = param(&params, "entity")?
+        .as_u64()
+        .ok_or_else(|| invalid_params("entity should be the entity's bits".to_string()))?;
+    let entity = Entity::try_from_bits(entity).map_err(|err| invalid_params(err.to_string()))?;
+
+    let Ok(is_powered) = q_entities.get(entity) else {
+        return Err(BrpError::entity_not_found(entity));
+    };
+
+    if is_powered {
+        commands.entity(entity).remove::<Powered>();
+    } else {
+        commands.entity(entity).insert(Powered);
+    }
+
+    Ok(json!({ "powered": !is_powered }))
+}
+
+fn set_game_state(
+    In(params): In<Option<Value>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) -> BrpResult {
+    let params = parse_params(params)?;
+    let state = param(&params, "state")?
+        .as_str()
+        .ok_or_else(|| invalid_params("expected string for state".to_string()))?;
+
+    let state = match state {
+        "Loading" => GameState::Loading,
+        "MainMenu" => GameState::MainMenu,
+        "Playing" => GameState::Playing,
+        "Win" => GameState::Win,
+        other => return Err(invalid_params(format!("unknown game state {other}"))),
+    };
+
+    next_state.set(state);
+
+    Ok(Value::Null)
+}
+
+fn teleport_player(
+    In(params): In<Option<Value>>,
+    mut q_player: Query<(&mut Transform, &mut LinearVelocity), With<Player>>,
+    q_spawn_point: Query<&Transform, (With<PlayerSpawnPoint>, Without<Player>)>,
+) -> BrpResult {
+    let destination = match params {
+        Some(params) => {
+            let params = parse_params(Some(params))?;
+            // every axis has to be a number, not just three of them
+            let position: Option<Vec<f32>> =
+                param(&params, "position")?.as_array().and_then(|position| {
+                    position
+                        .iter()
+                        .map(|axis| axis.as_f64().map(|axis| axis as f32))
+                        .collect()
+                });
+
+            let Some([x, y, z]) = position.as_deref() else {
+                return Err(invalid_params("position should be [x, y, z]".to_string()));
+            };
+            Vec3::new(*x, *y, *z)
+        }
+        None => {
+            let Ok(spawn_point) = q_spawn_point.single() else {
+                return Err(BrpError::internal("no PlayerSpawnPoint in the level"));
+            };
+            spawn_point.translation
+        }
+    };
+
+    let Ok((mut player_transform, mut velocity)) = q_player.single_mut() else {
+        return Err(BrpError::internal("no player in the world"));
+    };
+
+    player_transform.translation = destination;
+    *velocity = LinearVelocity::ZERO;
+
+    Ok(Value::Null)
+}
+
+fn screenshot(In(_params): In<Option<Value>>, mut commands: Commands) -> BrpResult {
+    commands.trigger(CaptureScreenshot);
+
+    Ok(Value::Null)
+}
@@ -0,0 +1,88 @@
+use std::{
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use bevy::{
+    prelude::*,
+    render::view::screenshot::{save_to_disk, Screenshot},
+};
+use bevy_enhanced_input::{
+    events::Completed,
+    prelude::{Actions, Binding, InputAction},
+};
+
+use crate::{asset_management::level_manager::LevelManager, game::input::UpdateInputContext};
+
+// Level thumbnails for an eventual level select. Grabs the window itself, so it's whatever
+// the player sees, outlines and all.
+pub fn screenshot_plugin(app: &mut App) {
+    app.init_resource::<ScreenshotConfig>()
+        .add_observer(bind_take_screenshot)
+        .add_observer(take_screenshot_on_keypress)
+        .add_observer(capture_screenshot);
+}
+
+#[derive(Resource)]
+pub struct ScreenshotConfig {
+    pub directory: PathBuf,
+}
+
+impl Default for ScreenshotConfig {
+    fn default() -> Self {
+        Self {
+            directory: PathBuf::from("screenshots"),
+        }
+    }
+}
+
+// Also fired by the dev/screenshot remote method
+#[derive(Event)]
+pub struct CaptureScreenshot;
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+pub struct TakeScreenshot;
+
+fn bind_take_screenshot(
+    trigger: Trigger<Binding<UpdateInputContext>>,
+    mut q_update_input_manager: Query<&mut Actions<UpdateInputContext>>,
+) {
+    if let Ok(mut actions) = q_update_input_manager.get_mut(trigger.target()) {
+        actions.bind::<TakeScreenshot>().to(KeyCode::F6);
+    }
+}
+
+fn take_screenshot_on_keypress(
+    _trigger: Trigger<Completed<TakeScreenshot>>,
+    mut commands: Commands,
+) {
+    commands.trigger(CaptureScreenshot);
+}
+
+fn capture_screenshot(
+    _trigger: Trigger<CaptureScreenshot>,
+    mut commands: Commands,
+    config: Res<ScreenshotConfig>,
+    level_manager: Res<LevelManager>,
+) {
+    if let Err(err) = std::fs::create_dir_all(&config.directory) {
+        error!(?err, directory = ?config.directory, "couldn't create screenshot directory");
+        return;
+    }
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default();
+
+    let path = config
+        .directory
+        .join(format!("level{}_{timestamp}.png", level_manager.current));
+
+    info!(?path, "saving screenshot");
+
+    commands
+        .spawn(Screenshot::primary_window())
+        .observe(save_to_disk(path));
+}
@@ -0,0 +1,78 @@
+use bevy::prelude::*;
+use bevy_enhanced_input::{
+    events::Completed,
+    prelude::{Actions, Binding, InputAction},
+};
+
+use crate::game::input::UpdateInputContext;
+
+const TIME_SCALE_STEPS: [f32; 5] = [0.25, 0.5, 1.0, 1.5, 2.0];
+const MIN_TIME_SCALE: f32 = 0.05;
+const MAX_TIME_SCALE: f32 = 4.0;
+
+// Slow motion for watching physics and signals up close. Everything runs off virtual time:
+// FixedUpdate (so movement, signals and avian) accumulates from it, and the tweens tick in
+// Update with the default Time, so scaling it slows all of them down together.
+pub fn time_scale_plugin(app: &mut App) {
+    app.init_resource::<TimeScale>()
+        .register_type::<TimeScale>()
+        .add_systems(Update, apply_time_scale)
+        .add_observer(bind_time_scale)
+        .add_observer(slow_down)
+        .add_observer(speed_up);
+}
+
+// Editable from the inspector too, anything in between the steps works
+#[derive(Resource, Reflect)]
+#[reflect(Resource)]
+pub struct TimeScale(pub f32);
+
+impl Default for TimeScale {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+pub struct SlowDownTime;
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+pub struct SpeedUpTime;
+
+fn bind_time_scale(
+    trigger: Trigger<Binding<UpdateInputContext>>,
+    mut q_update_input_manager: Query<&mut Actions<UpdateInputContext>>,
+) {
+    if let Ok(mut actions) = q_update_input_manager.get_mut(trigger.target()) {
+        actions.bind::<SlowDownTime>().to(KeyCode::F8);
+        actions.bind::<SpeedUpTime>().to(KeyCode::F9);
+    }
+}
+
+fn slow_down(_trigger: Trigger<Completed<SlowDownTime>>, mut time_scale: ResMut<TimeScale>) {
+    if let Some(step) = TIME_SCALE_STEPS
+        .iter()
+        .rev()
+        .find(|&&step| step < time_scale.0)
+    {
+        time_scale.0 = *step;
+    }
+}
+
+fn speed_up(_trigger: Trigger<Completed<SpeedUpTime>>, mut time_scale: ResMut<TimeScale>) {
+    if let Some(step) = TIME_SCALE_STEPS.iter().find(|&&step| step > time_scale.0) {
+        time_scale.0 = *step;
+    }
+}
+
+fn apply_time_scale(time_scale: Res<TimeScale>, mut virtual_time: ResMut<Time<Virtual>>) {
+    if !time_scale.is_changed() {
+        return;
+    }
+
+    let scale = time_scale.0.clamp(MIN_TIME_SCALE, MAX_TIME_SCALE);
+    virtual_time.set_relative_speed(scale);
+    info!("time scale set to {}x", scale);
+}
@@ -0,0 +1,83 @@
+use bevy::prelude::*;
+
+use crate::rendering::test_material::TestMaterial;
+
+pub fn accessibility_plugin(app: &mut App) {
+    app.init_resource::<AccessibilitySettings>()
+        .add_systems(Update, apply_reduced_motion_to_gates);
+}
+
+/// Player-facing comfort settings.
+///
+/// `reduced_motion` suppresses:
+/// - the scrolling stripes on dissolve and discharge gates
+/// - camera headbob, regardless of `headbob_enabled`
+/// - the fov kick while sprinting
+#[derive(Resource)]
+pub struct AccessibilitySettings {
+    pub headbob_enabled: bool,
+    pub reduced_motion: bool,
+}
+
+impl Default for AccessibilitySettings {
+    fn default() -> Self {
+        Self {
+            headbob_enabled: true,
+            reduced_motion: false,
+        }
+    }
+}
+
+impl AccessibilitySettings {
+    pub fn headbob_active(&self) -> bool {
+        self.headbob_enabled && !self.reduced_motion
+    }
+}
+
+// The authored scroll speed of a gate's stripe material, so it can be restored when reduced motion is turned off
+#[derive(Component)]
+pub struct GateScrollSpeed(pub f32);
+
+fn apply_reduced_motion_to_gates(
+    accessibility_settings: Res<AccessibilitySettings>,
+    q_gates: Query<(&MeshMaterial3d<TestMaterial>, &GateScrollSpeed)>,
+    q_new_gates: Query<(), Added<GateScrollSpeed>>,
+    mut test_materials: ResMut<Assets<TestMaterial>>,
+) {
+    if !accessibility_settings.is_changed() && q_new_gates.is_empty() {
+        return;
+    }
+
+    for (material_handle, scroll_speed) in &q_gates {
+        if let Some(material) = test_materials.get_mut(material_handle) {
+            material.extension.params.scroll_speed = if accessibility_settings.reduced_motion {
+                0.0
+            } else {
+                scroll_speed.0
+            };
+        }
+    }
+}
+
+pub fn toggle_reduced_motion(
+    trigger: Trigger<Pointer<Click>>,
+    mut accessibility_settings: ResMut<AccessibilitySettings>,
+    mut text_query: Query<&mut Text>,
+) {
+    accessibility_settings.reduced_motion = !accessibility_settings.reduced_motion;
+
+    if let Ok(mut text) = text_query.get_mut(trigger.target()) {
+        **text = format!(
+            "{} ◀",
+            reduced_motion_label(accessibility_settings.reduced_motion)
+        );
+    }
+}
+
+pub fn reduced_motion_label(reduced_motion: bool) -> String {
+    if reduced_motion {
+        String::from("Reduced Motion: On")
+    } else {
+        String::from("Reduced Motion: Off")
+    }
+}
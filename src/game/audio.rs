@@ -1,14 +1,18 @@
 use crate::{
     asset_management::{
         asset_loading::GameSounds,
-        asset_tag_components::{Door, PowerButton, PressurePlate},
+        asset_tag_components::{Door, PowerButton, PressurePlate, WeightedCube},
     },
     game::{
         button::ButtonPressed,
         door::DoorOpened,
+        player::Held,
         pressure_plate::{PressurePlatePressed, PressurePlateReleased},
+        signals::Signal,
     },
+    MainCamera,
 };
+use avian3d::prelude::{ColliderOf, LinearVelocity, OnCollisionStart};
 use bevy::{
     audio::{DefaultSpatialScale, SpatialScale, Volume},
     prelude::*,
@@ -17,7 +21,9 @@ use std::time::Duration;
 
 #[derive(Resource)]
 pub struct AudioSettings {
-    pub volume: f32,
+    pub master_volume: f32,
+    pub music_volume: f32,
+    pub sfx_volume: f32,
     pub volume_step: f32,
     pub spatial_enabled: bool,
 }
@@ -25,13 +31,40 @@ pub struct AudioSettings {
 impl Default for AudioSettings {
     fn default() -> Self {
         Self {
-            volume: 0.5,
+            master_volume: 0.5,
+            music_volume: 1.0,
+            sfx_volume: 1.0,
             volume_step: 0.1,
             spatial_enabled: true,
         }
     }
 }
 
+impl AudioSettings {
+    pub fn effective_music_volume(&self) -> f32 {
+        self.master_volume * self.music_volume
+    }
+
+    pub fn effective_sfx_volume(&self) -> f32 {
+        self.master_volume * self.sfx_volume
+    }
+
+    fn channel_volume_mut(&mut self, channel: VolumeChannel) -> &mut f32 {
+        match channel {
+            VolumeChannel::Master => &mut self.master_volume,
+            VolumeChannel::Music => &mut self.music_volume,
+            VolumeChannel::Sfx => &mut self.sfx_volume,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum VolumeChannel {
+    Master,
+    Music,
+    Sfx,
+}
+
 #[derive(Resource)]
 pub struct PressurePlateSoundCooldown {
     pub last_down_time: Option<Duration>,
@@ -49,6 +82,20 @@ impl Default for PressurePlateSoundCooldown {
     }
 }
 
+// Slower hits than this don't make a sound, so cubes settling or being nudged stay quiet
+const CUBE_IMPACT_MIN_SPEED: f32 = 15.0;
+// and anything this fast or faster plays at full volume
+const CUBE_IMPACT_FULL_SPEED: f32 = 60.0;
+const CUBE_IMPACT_COOLDOWN: Duration = Duration::from_millis(250);
+
+// By the time a collision is reported the solver has already stopped the cube, so keep hold of
+// how fast it was going going into the physics step
+#[derive(Component, Default)]
+pub struct CubeImpactAudio {
+    velocity_before_step: Vec3,
+    last_played: Option<Duration>,
+}
+
 #[derive(Component)]
 pub struct BackgroundMusic;
 
@@ -66,6 +113,7 @@ pub fn audio_plugin(app: &mut App) {
         .init_resource::<PressurePlateSoundCooldown>()
         .insert_resource::<DefaultSpatialScale>(DefaultSpatialScale(SpatialScale::new(0.1)))
         .add_systems(Startup, setup_spatial_listener)
+        .add_systems(FixedUpdate, record_cube_velocities)
         .add_systems(
             Update,
             (
@@ -73,9 +121,7 @@ pub fn audio_plugin(app: &mut App) {
                 update_music_volume,
                 update_spatial_listener,
             ),
-        )
-        .add_observer(handle_volume_up)
-        .add_observer(handle_volume_down);
+        );
 }
 
 fn setup_spatial_listener(mut commands: Commands) {
@@ -89,8 +135,14 @@ fn setup_spatial_listener(mut commands: Commands) {
 
 fn update_spatial_listener(
     mut listener_query: Query<&mut Transform, (With<SpatialListener>, With<SpatialAudioListener>)>,
-    camera_query: Query<&Transform, (With<Camera3d>, Without<SpatialListener>)>,
+    camera_query: Query<&Transform, (With<MainCamera>, Without<SpatialListener>)>,
+    audio_settings: Res<AudioSettings>,
 ) {
+    // every sound is spawned non-spatial while disabled, so the listener has nothing to do
+    if !audio_settings.spatial_enabled {
+        return;
+    }
+
     // Update listener position to match camera/player position
     if let (Ok(mut listener_transform), Ok(camera_transform)) =
         (listener_query.single_mut(), camera_query.single())
@@ -112,7 +164,7 @@ fn start_background_music(
             AudioPlayer::new(game_sounds.song.clone()),
             PlaybackSettings {
                 mode: bevy::audio::PlaybackMode::Loop,
-                volume: Volume::Linear(audio_settings.volume),
+                volume: Volume::Linear(audio_settings.effective_music_volume()),
                 spatial: false, // Keep background music non-spatial
                 ..default()
             },
@@ -122,17 +174,43 @@ fn start_background_music(
 }
 
 pub fn handle_volume_up(
-    _trigger: Trigger<Pointer<Click>>,
-    mut audio_settings: ResMut<AudioSettings>,
-) {
-    audio_settings.volume = (audio_settings.volume + audio_settings.volume_step).min(1.0);
+    channel: VolumeChannel,
+) -> impl FnMut(Trigger<Pointer<Click>>, ResMut<AudioSettings>) {
+    move |_trigger, mut audio_settings| {
+        let volume_step = audio_settings.volume_step;
+        let volume = audio_settings.channel_volume_mut(channel);
+        *volume = (*volume + volume_step).min(1.0);
+    }
 }
 
 pub fn handle_volume_down(
-    _trigger: Trigger<Pointer<Click>>,
+    channel: VolumeChannel,
+) -> impl FnMut(Trigger<Pointer<Click>>, ResMut<AudioSettings>) {
+    move |_trigger, mut audio_settings| {
+        let volume_step = audio_settings.volume_step;
+        let volume = audio_settings.channel_volume_mut(channel);
+        *volume = (*volume - volume_step).max(0.0);
+    }
+}
+
+pub fn toggle_spatial_audio(
+    trigger: Trigger<Pointer<Click>>,
     mut audio_settings: ResMut<AudioSettings>,
+    mut text_query: Query<&mut Text>,
 ) {
-    audio_settings.volume = (audio_settings.volume - audio_settings.volume_step).max(0.0);
+    audio_settings.spatial_enabled = !audio_settings.spatial_enabled;
+
+    if let Ok(mut text) = text_query.get_mut(trigger.target()) {
+        **text = format!("{} ◀", spatial_audio_label(audio_settings.spatial_enabled));
+    }
+}
+
+pub fn spatial_audio_label(spatial_enabled: bool) -> String {
+    if spatial_enabled {
+        String::from("Spatial Audio: On")
+    } else {
+        String::from("Spatial Audio: Off")
+    }
 }
 
 fn update_music_volume(
@@ -141,7 +219,7 @@ fn update_music_volume(
 ) {
     if audio_settings.is_changed() {
         for mut sink in music_query.iter_mut() {
-            sink.set_volume(Volume::Linear(audio_settings.volume));
+            sink.set_volume(Volume::Linear(audio_settings.effective_music_volume()));
         }
     }
 }
@@ -159,7 +237,7 @@ pub fn button_pressed_audio(
             &mut commands,
             game_sounds.button2.clone(),
             button_transform.translation(),
-            audio_settings.volume,
+            audio_settings.effective_sfx_volume(),
             audio_settings.spatial_enabled,
         );
     }
@@ -177,7 +255,7 @@ pub fn door_opened_audio(
             &mut commands,
             game_sounds.door_open.clone(),
             door_transform.translation(),
-            audio_settings.volume * 2.0,
+            audio_settings.effective_sfx_volume() * 2.0,
             audio_settings.spatial_enabled,
         );
     }
@@ -205,7 +283,7 @@ pub fn pressure_plate_pressed_audio(
                 &mut commands,
                 game_sounds.pressure_plate_down.clone(),
                 plate_transform.translation(),
-                audio_settings.volume,
+                audio_settings.effective_sfx_volume(),
                 audio_settings.spatial_enabled,
             );
 
@@ -236,7 +314,7 @@ pub fn pressure_plate_released_audio(
                 &mut commands,
                 game_sounds.pressure_plate_up.clone(),
                 plate_transform.translation(),
-                audio_settings.volume,
+                audio_settings.effective_sfx_volume(),
                 audio_settings.spatial_enabled,
             );
 
@@ -245,6 +323,82 @@ pub fn pressure_plate_released_audio(
     }
 }
 
+fn record_cube_velocities(mut q_cubes: Query<(&LinearVelocity, &mut CubeImpactAudio)>) {
+    for (linear_velocity, mut impact_audio) in &mut q_cubes {
+        impact_audio.velocity_before_step = linear_velocity.0;
+    }
+}
+
+// Observed on each cube's collider
+pub fn cube_impact_audio(
+    trigger: Trigger<OnCollisionStart>,
+    mut commands: Commands,
+    game_sounds: Res<GameSounds>,
+    audio_settings: Res<AudioSettings>,
+    time: Res<Time>,
+    q_collider_of: Query<&ColliderOf>,
+    mut q_cubes: Query<
+        (&GlobalTransform, &mut CubeImpactAudio),
+        (With<WeightedCube>, Without<Held>),
+    >,
+    q_velocities: Query<&LinearVelocity>,
+    q_signals: Query<(), With<Signal>>,
+) {
+    if q_signals.contains(trigger.collider) {
+        return;
+    }
+
+    let Ok(cube_collider_of) = q_collider_of.get(trigger.target()) else {
+        return;
+    };
+    let Ok((cube_transform, cube_impact_audio)) = q_cubes.get(cube_collider_of.body) else {
+        return;
+    };
+
+    // relative to whatever it hit, which is standing still unless it's a body of its own
+    let other_velocity = trigger.body.map_or(Vec3::ZERO, |other_body| {
+        q_cubes
+            .get(other_body)
+            .map(|(_, other_impact_audio)| other_impact_audio.velocity_before_step)
+            .or_else(|_| {
+                q_velocities
+                    .get(other_body)
+                    .map(|linear_velocity| linear_velocity.0)
+            })
+            .unwrap_or(Vec3::ZERO)
+    });
+    let impact_speed = (cube_impact_audio.velocity_before_step - other_velocity).length();
+
+    if impact_speed < CUBE_IMPACT_MIN_SPEED {
+        return;
+    }
+
+    let now = time.elapsed();
+    if cube_impact_audio
+        .last_played
+        .is_some_and(|last_played| now.saturating_sub(last_played) < CUBE_IMPACT_COOLDOWN)
+    {
+        return;
+    }
+
+    let position = cube_transform.translation();
+    if let Ok((_, mut cube_impact_audio)) = q_cubes.get_mut(cube_collider_of.body) {
+        cube_impact_audio.last_played = Some(now);
+    }
+
+    let loudness = ((impact_speed - CUBE_IMPACT_MIN_SPEED)
+        / (CUBE_IMPACT_FULL_SPEED - CUBE_IMPACT_MIN_SPEED))
+        .clamp(0.2, 1.0);
+
+    spawn_spatial_sound(
+        &mut commands,
+        game_sounds.cube_impact.clone(),
+        position,
+        audio_settings.effective_sfx_volume() * loudness,
+        audio_settings.spatial_enabled,
+    );
+}
+
 // Helper functions for spawning spatial vs non-spatial sounds
 fn spawn_spatial_sound(
     commands: &mut Commands,
@@ -6,13 +6,14 @@ use bevy_tween::{
     bevy_time_runner::TimeSpan,
     combinator::{sequence, tween},
     prelude::{AnimationBuilderExt, EaseKind},
-    tween::{AnimationTarget, TargetAsset},
+    tween::TargetAsset,
 };
 
 use crate::{
     asset_management::asset_tag_components::{Door, PowerButton},
-    game::{audio::button_pressed_audio, signals::DirectSignal},
+    game::{audio::button_pressed_audio, signals::DirectSignal, standing_cube_spitter::Tombstone},
     rendering::unlit_material::UnlitMaterial,
+    util::make_child_animatable,
 };
 
 use super::{
@@ -22,17 +23,25 @@ use super::{
 };
 
 pub fn button_plugin(app: &mut App) {
-    app.add_systems(FixedPreUpdate, register_buttons)
+    app.init_resource::<PendingSignals>()
+        .add_systems(FixedPreUpdate, register_buttons)
         .add_systems(Update, update_delayed_signals);
 }
 
 #[derive(Component)]
 pub struct ButtonTargets(pub Vec<Entity>);
 
-#[derive(Component)]
-struct DelayedSignalTimer {
-    timer: Timer,
-    target: Entity,
+pub const BUTTON_SIGNAL_DELAY_SEC: f32 = 0.5;
+
+// DirectSignals waiting to go out, so one press can fan out to many targets at different delays
+#[derive(Resource, Default)]
+pub struct PendingSignals(pub Vec<(Timer, Entity)>);
+
+impl PendingSignals {
+    pub fn push(&mut self, delay_sec: f32, target: Entity) {
+        self.0
+            .push((Timer::from_seconds(delay_sec, TimerMode::Once), target));
+    }
 }
 
 fn register_buttons(
@@ -63,12 +72,12 @@ fn register_buttons(
 
         for button_child in button_children.iter() {
             if let Ok(material_handle) = q_unlit_objects.get(button_child) {
-                let old_material = unlit_materials.get(material_handle).unwrap().clone();
-
-                commands.entity(button_child).insert((
-                    AnimationTarget,
-                    MeshMaterial3d(unlit_materials.add(old_material)),
-                ));
+                make_child_animatable(
+                    &mut commands,
+                    button_child,
+                    material_handle,
+                    &mut unlit_materials,
+                );
             }
         }
     }
@@ -80,11 +89,12 @@ pub struct ButtonPressed;
 pub fn button_pressed(
     trigger: Trigger<Interacted>,
     mut commands: Commands,
-    q_button: Query<(&ButtonTargets, &Children)>,
+    q_button: Query<(&ButtonTargets, &Children), Without<Tombstone>>,
     q_collider_of: Query<&ColliderOf>,
     q_unlit_objects: Query<&MeshMaterial3d<UnlitMaterial>>,
     q_tween: Query<(), With<TimeSpan>>,
     q_children: Query<&Children>,
+    mut pending_signals: ResMut<PendingSignals>,
 ) {
     if let Ok(collider_of) = q_collider_of.get(trigger.target()) {
         if let Ok((button_targets, button_children)) = q_button.get(collider_of.body) {
@@ -130,10 +140,7 @@ pub fn button_pressed(
 
             // Send signals to targets with delay
             for target in &button_targets.0 {
-                commands.spawn(DelayedSignalTimer {
-                    timer: Timer::from_seconds(0.5, TimerMode::Once),
-                    target: *target,
-                });
+                pending_signals.push(BUTTON_SIGNAL_DELAY_SEC, *target);
             }
 
             commands.entity(collider_of.body).trigger(ButtonPressed);
@@ -143,18 +150,50 @@ pub fn button_pressed(
 
 fn update_delayed_signals(
     mut commands: Commands,
-    mut q_delayed_signals: Query<(Entity, &mut DelayedSignalTimer)>,
+    mut pending_signals: ResMut<PendingSignals>,
     time: Res<Time>,
 ) {
-    for (timer_entity, mut delayed_signal) in &mut q_delayed_signals {
-        delayed_signal.timer.tick(time.delta());
+    pending_signals.0.retain_mut(|(timer, target)| {
+        timer.tick(time.delta());
 
-        if delayed_signal.timer.finished() {
-            // Send the DirectSignal
-            commands.entity(delayed_signal.target).trigger(DirectSignal);
-
-            // Remove the timer entity
-            commands.entity(timer_entity).try_despawn();
+        if timer.finished() {
+            // target may have been despawned while we waited
+            if let Ok(mut target_commands) = commands.get_entity(*target) {
+                target_commands.trigger(DirectSignal);
+            }
+            false
+        } else {
+            true
         }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::test_app::{advance_fixed, headless_app, spawn_device, spawn_device_group};
+
+    #[derive(Resource, Default)]
+    struct ButtonPresses(u32);
+
+    #[test]
+    fn interacting_presses_the_button_once() {
+        let mut app = headless_app();
+        app.init_resource::<ButtonPresses>().add_observer(
+            |_trigger: Trigger<ButtonPressed>, mut presses: ResMut<ButtonPresses>| {
+                presses.0 += 1;
+            },
+        );
+
+        let button = spawn_device(&mut app, PowerButton { unused: false }, Vec3::ZERO);
+        spawn_device_group(&mut app, &[button]);
+        advance_fixed(&mut app, 2);
+        let button_collider = app.world().get::<Children>(button).unwrap()[0];
+
+        app.world_mut().trigger_targets(Interacted, button_collider);
+        app.world_mut().flush();
+        advance_fixed(&mut app, 1);
+
+        assert_eq!(app.world().resource::<ButtonPresses>().0, 1);
     }
 }
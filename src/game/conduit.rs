@@ -0,0 +1,69 @@
+use bevy::prelude::*;
+
+use crate::{
+    asset_management::{
+        asset_tag_components::Conduit,
+        named_target::{resolve_named_targets, NamedTarget, ResolveNamedTargets},
+    },
+    GameState,
+};
+
+use super::{pressure_plate::PoweredBy, signals::Powered};
+
+pub fn conduit_plugin(app: &mut App) {
+    app.add_systems(FixedPreUpdate, resolve_named_targets::<Conduit>)
+        .add_systems(
+            FixedUpdate,
+            forward_conduit_power.run_if(in_state(GameState::Playing)),
+        );
+}
+
+// Forwards Powered from one device to another regardless of where they sit in the scene
+#[derive(Component)]
+pub struct SignalConduit {
+    pub from: Entity,
+    pub to: Entity,
+}
+
+impl ResolveNamedTargets for Conduit {
+    type Resolved = SignalConduit;
+
+    fn targets(&self) -> Vec<&NamedTarget> {
+        vec![&self.from, &self.to]
+    }
+
+    fn resolve(&self, entities: &[Entity]) -> SignalConduit {
+        SignalConduit {
+            from: entities[0],
+            to: entities[1],
+        }
+    }
+}
+
+fn forward_conduit_power(
+    mut commands: Commands,
+    q_conduits: Query<&SignalConduit>,
+    q_powered: Query<(), With<Powered>>,
+    q_powered_by: Query<&PoweredBy>,
+) {
+    for conduit in &q_conduits {
+        let source_powered = q_powered.contains(conduit.from);
+        let target_powered = q_powered.contains(conduit.to);
+
+        if source_powered && !target_powered {
+            commands
+                .entity(conduit.to)
+                .try_insert((Powered, PoweredBy(conduit.from)));
+        } else if !source_powered && target_powered {
+            // only take back power we gave, something else might be powering the target
+            if q_powered_by
+                .get(conduit.to)
+                .is_ok_and(|powered_by| powered_by.0 == conduit.from)
+            {
+                commands
+                    .entity(conduit.to)
+                    .try_remove::<(Powered, PoweredBy)>();
+            }
+        }
+    }
+}
@@ -15,7 +15,10 @@ use bevy_tween::{
 use crate::{
     asset_management::{
         asset_loading::GameAssets,
-        asset_tag_components::{CubeSpitter, PermanentlyPowered, WeightedCube, WeightedCubeColors},
+        asset_tag_components::{
+            CubeSpitter, MaxOwned, PermanentlyPowered, StartsPowered, WeightedCube,
+            WeightedCubeColors,
+        },
     },
     game::standing_cube_spitter::Tombstone,
     rendering::unlit_material::UnlitMaterial,
@@ -26,7 +29,7 @@ use super::{
     pressure_plate::{POWER_ANIMATION_DURATION_SEC, POWER_MATERIAL_INTENSITY},
     signals::{
         default_signal_collisions, DirectSignal, MaterialIntensityInterpolator, OwnedObjects,
-        Powered,
+        Powered, SpitterCooldown,
     },
     GameLayer,
 };
@@ -43,20 +46,30 @@ pub fn cube_spitter_plugin(app: &mut App) {
 fn check_and_replace_wall_cubes(
     mut commands: Commands,
     mut q_powered_spitters: Query<
-        (&CubeSpitter, &Transform, &mut OwnedObjects),
+        (
+            &CubeSpitter,
+            &Transform,
+            &mut OwnedObjects,
+            &MaxOwned,
+            &mut SpitterCooldown,
+        ),
         (With<CubeSpitter>, With<Powered>),
     >,
     q_existing_entities: Query<Entity>, // To check if owned entities still exist
     game_assets: Res<GameAssets>,
 ) {
-    for (spitter, spitter_transform, mut spitter_owned_objects) in &mut q_powered_spitters {
+    for (spitter, spitter_transform, mut spitter_owned_objects, max_owned, mut cooldown) in
+        &mut q_powered_spitters
+    {
         // Remove any owned objects that no longer exist
         spitter_owned_objects
             .0
             .retain(|&entity| q_existing_entities.contains(entity));
 
-        // If no cubes exist, spawn a new one immediately
-        if spitter_owned_objects.0.is_empty() {
+        // If we're short on cubes, spawn a new one as soon as the cooldown allows
+        if spitter_owned_objects.0.len() < max_owned.count.max(1) && cooldown.ready() {
+            cooldown.restart();
+
             let cube_id = commands
                 .spawn((
                     SceneRoot(match spitter.color {
@@ -85,18 +98,36 @@ fn check_and_replace_wall_cubes(
 pub fn cube_spitter_direct_signal(
     trigger: Trigger<DirectSignal>,
     mut commands: Commands,
-    mut q_cube_spitters: Query<(
-        &RigidBodyColliders,
-        &CubeSpitter,
-        &Transform,
-        &mut OwnedObjects,
-    )>,
+    mut q_cube_spitters: Query<
+        (
+            &RigidBodyColliders,
+            &CubeSpitter,
+            &Transform,
+            &mut OwnedObjects,
+            &MaxOwned,
+            &mut SpitterCooldown,
+        ),
+        Without<Tombstone>,
+    >,
+    q_existing_entities: Query<Entity>,
     q_unlit_objects: Query<&MeshMaterial3d<UnlitMaterial>>,
     game_assets: Res<GameAssets>,
 ) {
-    if let Ok((spitter_colliders, spitter, spitter_transform, mut spitter_owned_objects)) =
-        q_cube_spitters.get_mut(trigger.target())
+    if let Ok((
+        spitter_colliders,
+        spitter,
+        spitter_transform,
+        mut spitter_owned_objects,
+        max_owned,
+        mut cooldown,
+    )) = q_cube_spitters.get_mut(trigger.target())
     {
+        // ignore the signal entirely, otherwise we'd eat the current cube without replacing it
+        if !cooldown.ready() {
+            return;
+        }
+        cooldown.restart();
+
         if let Some(collider_entity) = spitter_colliders.iter().next() {
             if let Ok(spitter_material_handle) = q_unlit_objects.get(collider_entity) {
                 commands
@@ -125,13 +156,15 @@ pub fn cube_spitter_direct_signal(
                         ),
                     )));
 
-                // despawn the old owned objects and clear the list
-                for object in spitter_owned_objects.iter() {
-                    if let Ok(mut ec) = commands.get_entity(*object) {
+                // despawn the oldest owned objects, dissolved ones don't count
+                spitter_owned_objects
+                    .0
+                    .retain(|&entity| q_existing_entities.contains(entity));
+                for object in spitter_owned_objects.make_room(max_owned.count) {
+                    if let Ok(mut ec) = commands.get_entity(object) {
                         ec.insert(Tombstone).try_despawn()
                     }
                 }
-                spitter_owned_objects.clear();
 
                 let cube_id = commands
                     .spawn((
@@ -164,8 +197,14 @@ fn cube_spitter_receive_power(
     trigger: Trigger<OnAdd, Powered>,
     mut commands: Commands,
     mut q_spitter: Query<
-        (&Children, &CubeSpitter, &Transform, &mut OwnedObjects),
-        With<CubeSpitter>,
+        (
+            &Children,
+            &CubeSpitter,
+            &Transform,
+            &mut OwnedObjects,
+            &mut SpitterCooldown,
+        ),
+        (With<CubeSpitter>, Without<Tombstone>),
     >,
     q_unlit_objects: Query<&MeshMaterial3d<UnlitMaterial>>,
     unlit_materials: Res<Assets<UnlitMaterial>>,
@@ -173,8 +212,13 @@ fn cube_spitter_receive_power(
     q_children: Query<&Children>,
     game_assets: Res<GameAssets>,
 ) {
-    if let Ok((spitter_children, spitter, spitter_transform, mut spitter_owned_objects)) =
-        q_spitter.get_mut(trigger.target())
+    if let Ok((
+        spitter_children,
+        spitter,
+        spitter_transform,
+        mut spitter_owned_objects,
+        mut cooldown,
+    )) = q_spitter.get_mut(trigger.target())
     {
         // Animate material to powered state for each child
         for spitter_child in spitter_children.iter() {
@@ -208,7 +252,10 @@ fn cube_spitter_receive_power(
         }
 
         // If no cubes exist when powered, spawn one immediately
-        if spitter_owned_objects.0.is_empty() {
+        // (or let check_and_replace_wall_cubes do it once the cooldown is up)
+        if spitter_owned_objects.0.is_empty() && cooldown.ready() {
+            cooldown.restart();
+
             let cube_id = commands
                 .spawn((
                     SceneRoot(match spitter.color {
@@ -237,7 +284,7 @@ fn cube_spitter_receive_power(
 fn cube_spitter_lose_power(
     trigger: Trigger<OnRemove, Powered>,
     mut commands: Commands,
-    q_spitter: Query<&Children, With<CubeSpitter>>,
+    q_spitter: Query<&Children, (With<CubeSpitter>, Without<Tombstone>)>,
     q_unlit_objects: Query<&MeshMaterial3d<UnlitMaterial>>,
     unlit_materials: Res<Assets<UnlitMaterial>>,
     q_tween: Query<(), With<TimeSpan>>,
@@ -279,15 +326,29 @@ fn cube_spitter_lose_power(
 
 fn register_cube_spitter_signals(
     mut commands: Commands,
-    q_new_spitter: Query<(Entity, &Children, Has<PermanentlyPowered>), Added<CubeSpitter>>,
+    q_new_spitter: Query<
+        (
+            Entity,
+            &Children,
+            Has<PermanentlyPowered>,
+            Has<StartsPowered>,
+        ),
+        Added<CubeSpitter>,
+    >,
 ) {
     // for static geo like spitters, the tag is on the parent, but the rigid body is on the child
-    for (spitter_entity, spitter_children, is_permanently_powered) in &q_new_spitter {
+    for (spitter_entity, spitter_children, is_permanently_powered, starts_powered) in &q_new_spitter
+    {
         // warning: we actually expect there to only be ever one spitter child
         // this explodes if not
         commands
             .entity(spitter_entity)
-            .insert((OwnedObjects::default(), RigidBody::Static))
+            .insert((
+                OwnedObjects::default(),
+                SpitterCooldown::default(),
+                RigidBody::Static,
+            ))
+            .insert_if_new(MaxOwned::default())
             .observe(cube_spitter_direct_signal)
             .observe(cube_spitter_receive_power)
             .observe(cube_spitter_lose_power);
@@ -299,6 +360,13 @@ fn register_cube_spitter_signals(
                 .remove::<PermanentlyPowered>();
         }
 
+        if starts_powered {
+            commands
+                .entity(spitter_entity)
+                .insert(Powered)
+                .remove::<StartsPowered>();
+        }
+
         for spitter_child in spitter_children.iter() {
             commands
                 .entity(spitter_child)
@@ -319,3 +387,43 @@ fn register_cube_spitter_signals(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{
+        signals::SPITTER_RESPAWN_INTERVAL_SEC,
+        test_app::{advance_fixed, advance_secs, headless_app, spawn_device},
+    };
+
+    #[test]
+    fn spitter_keeps_max_owned_cubes() {
+        let mut app = headless_app();
+        let spitter = spawn_device(
+            &mut app,
+            (
+                CubeSpitter {
+                    color: WeightedCubeColors::Cyan,
+                },
+                MaxOwned { count: 2 },
+            ),
+            Vec3::ZERO,
+        );
+        advance_fixed(&mut app, 2);
+
+        for _ in 0..3 {
+            app.world_mut().trigger_targets(DirectSignal, spitter);
+            app.world_mut().flush();
+            // past the spitter's cooldown
+            advance_secs(&mut app, SPITTER_RESPAWN_INTERVAL_SEC + 0.1);
+        }
+
+        let live_cubes = app
+            .world_mut()
+            .query_filtered::<(), With<WeightedCube>>()
+            .iter(app.world())
+            .count();
+        assert_eq!(live_cubes, 2);
+        assert_eq!(app.world().get::<OwnedObjects>(spitter).unwrap().len(), 2);
+    }
+}
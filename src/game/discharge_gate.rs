@@ -6,19 +6,27 @@ use avian3d::prelude::{
 use bevy::{color::palettes::tailwind::ORANGE_300, prelude::*, render::view::NoFrustumCulling};
 
 use crate::{
-    asset_management::asset_tag_components::DischargeGate,
-    game::signals::Powered,
+    asset_management::asset_tag_components::{DischargeGate, GateImmune, WeightedCube},
+    game::{
+        pressure_plate::PoweredBy,
+        signals::Powered,
+        weighted_cube::{fully_discharge_cube, CubeDischarge},
+    },
     rendering::{
         test_material::{TestMaterial, TestMaterialExtension, TestMaterialParams},
         unlit_material::UnlitMaterial,
     },
+    util::clone_unlit_material,
 };
 
 use super::{
+    accessibility::GateScrollSpeed,
     player::{Player, RightHand},
     GameLayer,
 };
 
+const DISCHARGE_GATE_SCROLL_SPEED: f32 = -0.03;
+
 pub fn discharge_gate_plugin(app: &mut App) {
     app.add_systems(FixedPreUpdate, (register_discharge_gates,));
 }
@@ -33,7 +41,7 @@ fn register_discharge_gates(
     for gate_children in &q_new_gate {
         for gate_child in gate_children.iter() {
             if let Ok(material_handle) = q_unlit_objects.get(gate_child) {
-                let mut old_material = unlit_materials.get(material_handle).unwrap().clone();
+                let mut old_material = clone_unlit_material(&unlit_materials, material_handle);
                 old_material.base.alpha_mode = AlphaMode::Blend;
 
                 let test_material = test_materials.add(TestMaterial {
@@ -44,7 +52,7 @@ fn register_discharge_gates(
                             stripe_frequency: 15.0,
                             stripe_angle: -FRAC_PI_4, // Opposite angle to distinguish from dissolve gate
                             stripe_thickness: 0.9,
-                            scroll_speed: -0.03, // Opposite direction scroll
+                            scroll_speed: DISCHARGE_GATE_SCROLL_SPEED, // Opposite direction scroll
                         },
                     },
                 });
@@ -59,6 +67,7 @@ fn register_discharge_gates(
                             [GameLayer::Device, GameLayer::Player],
                         ),
                         Sensor,
+                        GateScrollSpeed(DISCHARGE_GATE_SCROLL_SPEED),
                         NoFrustumCulling,
                     ))
                     .observe(handle_discharge_collisions);
@@ -70,7 +79,15 @@ fn register_discharge_gates(
 pub fn handle_discharge_collisions(
     trigger: Trigger<OnCollisionStart>,
     mut commands: Commands,
-    q_powered: Query<&Powered>,
+    q_powered: Query<&Powered, Without<GateImmune>>,
+    q_charged_cubes: Query<
+        (),
+        (
+            With<WeightedCube>,
+            Or<(With<Powered>, With<PoweredBy>, With<CubeDischarge>)>,
+            Without<GateImmune>,
+        ),
+    >,
     q_player: Query<&RightHand, With<Player>>,
     q_collider_of: Query<&ColliderOf>,
     q_discharge_gates: Query<(Entity, &DischargeGate)>,
@@ -89,6 +106,13 @@ pub fn handle_discharge_collisions(
     }
 
     if let Ok(targeted_body) = q_collider_of.get(device_or_player_collider_entity) {
+        // Cubes carry extra power state that all needs to go
+        if q_charged_cubes.contains(targeted_body.body) {
+            fully_discharge_cube(&mut commands, targeted_body.body);
+            info!("Discharged cube {:?}", targeted_body.body);
+            return;
+        }
+
         // Check if the colliding entity itself is powered
         if q_powered.contains(targeted_body.body) {
             commands.entity(targeted_body.body).remove::<Powered>();
@@ -101,8 +125,11 @@ pub fn handle_discharge_collisions(
 
         // Check if the colliding entity is a player with a held powered object
         if let Ok(right_hand) = q_player.get(targeted_body.body) {
-            if let Some(held_entity) = right_hand.held_object {
-                if q_powered.contains(held_entity) {
+            if let Some(held_entity) = right_hand.held_object() {
+                if q_charged_cubes.contains(held_entity) {
+                    fully_discharge_cube(&mut commands, held_entity);
+                    info!("Discharged held cube {:?}", held_entity);
+                } else if q_powered.contains(held_entity) {
                     commands.entity(held_entity).remove::<Powered>();
                     info!(
                         "Discharged held entity {:?} - removed Powered component",
@@ -113,3 +140,38 @@ pub fn handle_discharge_collisions(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{
+        test_app::{
+            advance_fixed, advance_secs, child_intensity, headless_app, spawn_device,
+            spawn_test_cube,
+        },
+        weighted_cube::PoweringUp,
+    };
+
+    #[test]
+    fn discharge_gate_cancels_a_power_up() {
+        let mut app = headless_app();
+        spawn_device(&mut app, DischargeGate { unused: false }, Vec3::ZERO);
+        let cube = spawn_test_cube(&mut app, Vec3::X * 50.0);
+        advance_fixed(&mut app, 2);
+
+        app.world_mut().entity_mut(cube).insert(Powered);
+        advance_fixed(&mut app, 1);
+        assert!(app.world().entity(cube).contains::<PoweringUp>());
+
+        app.world_mut()
+            .entity_mut(cube)
+            .insert(Transform::from_translation(Vec3::ZERO));
+        advance_fixed(&mut app, 4);
+        advance_secs(&mut app, 2.0);
+
+        let cube = app.world().entity(cube);
+        assert!(!cube.contains::<Powered>());
+        assert!(!cube.contains::<PoweringUp>());
+        assert!((child_intensity(&app, cube.id()) - 1.0).abs() < 0.01);
+    }
+}
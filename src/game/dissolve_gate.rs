@@ -1,24 +1,29 @@
 use std::f32::consts::FRAC_PI_4;
 
 use avian3d::prelude::{
-    ColliderOf, CollisionEventsEnabled, CollisionLayers, OnCollisionStart, Sensor,
+    AngularVelocity, ColliderOf, CollisionEventsEnabled, CollisionLayers, LinearVelocity,
+    OnCollisionStart, Sensor,
 };
 use bevy::{color::palettes::tailwind::PURPLE_300, prelude::*};
 
 use crate::{
-    asset_management::asset_tag_components::DissolveGate,
+    asset_management::asset_tag_components::{DissolveGate, GateImmune},
     game::{player::Held, standing_cube_spitter::Tombstone},
     rendering::{
         test_material::{TestMaterial, TestMaterialExtension, TestMaterialParams},
         unlit_material::UnlitMaterial,
     },
+    util::clone_unlit_material,
 };
 
 use super::{
+    accessibility::GateScrollSpeed,
     player::{Player, RightHand},
     GameLayer,
 };
 
+const DISSOLVE_GATE_SCROLL_SPEED: f32 = 0.05;
+
 pub fn dissolve_gate_plugin(app: &mut App) {
     app.add_systems(FixedPreUpdate, (register_dissolve_gates,));
 }
@@ -31,6 +36,29 @@ pub struct Dissolveable {
     pub respawn_transform: Option<Transform>,
 }
 
+// Sends a dissolveable back to where it came from, dropping it if it was being held, or gets
+// rid of it if it has nowhere to go
+pub fn dissolve_or_respawn(commands: &mut Commands, entity: Entity, dissolveable: &Dissolveable) {
+    match &dissolveable.respawn_transform {
+        Some(respawn_transform) => {
+            commands
+                .entity(entity)
+                // whatever it was doing when it hit the gate shouldn't carry over
+                .try_insert((
+                    *respawn_transform,
+                    LinearVelocity::ZERO,
+                    AngularVelocity::ZERO,
+                ))
+                .try_remove::<Held>();
+        }
+        None => {
+            if let Ok(mut ec) = commands.get_entity(entity) {
+                ec.try_insert(Tombstone).despawn();
+            }
+        }
+    }
+}
+
 fn register_dissolve_gates(
     mut commands: Commands,
     q_new_gate: Query<&Children, Added<DissolveGate>>,
@@ -41,7 +69,7 @@ fn register_dissolve_gates(
     for gate_children in &q_new_gate {
         for gate_child in gate_children.iter() {
             if let Ok(material_handle) = q_unlit_objects.get(gate_child) {
-                let mut old_material = unlit_materials.get(material_handle).unwrap().clone();
+                let mut old_material = clone_unlit_material(&unlit_materials, material_handle);
                 old_material.base.alpha_mode = AlphaMode::Blend;
                 // old_material.extension.params.alpha = 0.5;
                 // old_material.extension.params.blend_color = RED.into();
@@ -55,7 +83,7 @@ fn register_dissolve_gates(
                             stripe_frequency: 20.0,
                             stripe_angle: FRAC_PI_4,
                             stripe_thickness: 0.95,
-                            scroll_speed: 0.05,
+                            scroll_speed: DISSOLVE_GATE_SCROLL_SPEED,
                         },
                     },
                 });
@@ -70,6 +98,7 @@ fn register_dissolve_gates(
                             [GameLayer::Device, GameLayer::Player],
                         ),
                         Sensor,
+                        GateScrollSpeed(DISSOLVE_GATE_SCROLL_SPEED),
                     ))
                     .observe(handle_dissolve_collisions);
             }
@@ -77,11 +106,16 @@ fn register_dissolve_gates(
     }
 }
 
+/// Respawns or despawns Dissolveable bodies that touch a gate, including whatever the player is
+/// holding when they walk through. Held objects that aren't Dissolveable, and anything GateImmune,
+/// come through untouched.
+/// Removing Held (or despawning, which also removes it) runs released_item, so the player's
+/// RightHand never ends up pointing at a dissolved object.
 pub fn handle_dissolve_collisions(
     trigger: Trigger<OnCollisionStart>,
     mut commands: Commands,
-    q_dissolveable: Query<&Dissolveable>,
-    q_player: Query<&RightHand, With<Player>>,
+    q_dissolveable: Query<&Dissolveable, Without<GateImmune>>,
+    mut q_player: Query<&mut RightHand, With<Player>>,
     q_collider_of: Query<&ColliderOf>,
     q_dissolve_gates: Query<(Entity, &DissolveGate)>,
     q_child_of: Query<&ChildOf>,
@@ -99,49 +133,121 @@ pub fn handle_dissolve_collisions(
 
     if let Ok(targeted_body) = q_collider_of.get(device_or_player_collider_entity) {
         if let Ok(dissolveable) = q_dissolveable.get(targeted_body.body) {
-            match &dissolveable.respawn_transform {
-                Some(respawn_transform) => {
-                    // Respawn the entity at the specified transform
-                    commands
-                        .entity(targeted_body.body)
-                        .try_insert(*respawn_transform);
-                }
-                None => {
-                    // Despawn the entity
-                    if let Ok(mut ec) = commands.get_entity(targeted_body.body) {
-                        ec.try_insert(Tombstone).despawn();
-                    }
+            // a dissolved object isn't where the player left it anymore
+            for mut right_hand in &mut q_player {
+                if right_hand.last_released == Some(targeted_body.body) {
+                    right_hand.last_released = None;
                 }
             }
+
+            dissolve_or_respawn(&mut commands, targeted_body.body, dissolveable);
             return;
         }
 
         // Check if the colliding entity is a player with a held object
         if let Ok(right_hand) = q_player.get(targeted_body.body) {
-            if let Some(held_entity) = right_hand.held_object {
+            if let Some(held_entity) = right_hand.held_object() {
                 if let Ok(dissolveable) = q_dissolveable.get(held_entity) {
-                    match &dissolveable.respawn_transform {
-                        Some(respawn_transform) => {
-                            // Respawn the held entity at the specified transform and remove Held component
-                            commands
-                                .entity(held_entity)
-                                .try_insert(*respawn_transform)
-                                .remove::<Held>();
-                            info!(
-                                "Dissolved held entity {:?} respawned at {:?}",
-                                held_entity, respawn_transform
-                            );
-                        }
-                        None => {
-                            if let Ok(mut ec) = commands.get_entity(held_entity) {
-                                ec.try_insert(Tombstone).despawn();
-
-                                info!("Dissolved entity {:?} despawned", held_entity);
-                            }
-                        }
-                    }
+                    info!("Dissolved held entity {:?}", held_entity);
+                    dissolve_or_respawn(&mut commands, held_entity, dissolveable);
                 }
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::test_app::{
+        advance_fixed, headless_app, spawn_device, spawn_test_cube, spawn_test_player,
+    };
+    use avian3d::prelude::{GravityScale, RigidBody};
+
+    // Walks a player carrying a cube through a dissolve gate, returns whether the cube made it
+    fn carried_cube_survives_gate(immune: bool) -> bool {
+        let mut app = headless_app();
+        spawn_device(&mut app, DissolveGate { unused: false }, Vec3::ZERO);
+        let player = spawn_test_player(&mut app, Vec3::X * 30.0);
+        // collision events need something dynamic
+        app.world_mut()
+            .entity_mut(player)
+            .insert((RigidBody::Dynamic, GravityScale(0.0)));
+
+        let cube = spawn_test_cube(&mut app, Vec3::X * -100.0);
+        app.world_mut().entity_mut(cube).insert(Dissolveable {
+            respawn_transform: None,
+        });
+        if immune {
+            app.world_mut()
+                .entity_mut(cube)
+                .insert(GateImmune { unused: false });
+        }
+        app.world_mut()
+            .get_mut::<RightHand>(player)
+            .unwrap()
+            .set_active(cube);
+        advance_fixed(&mut app, 2);
+
+        app.world_mut()
+            .entity_mut(player)
+            .insert(Transform::from_translation(Vec3::ZERO));
+        advance_fixed(&mut app, 4);
+
+        app.world().get_entity(cube).is_ok()
+    }
+
+    #[test]
+    fn carried_cube_dissolves_in_gate() {
+        assert!(!carried_cube_survives_gate(false));
+    }
+
+    #[test]
+    fn carried_gate_immune_cube_survives_gate() {
+        assert!(carried_cube_survives_gate(true));
+    }
+
+    #[test]
+    fn carried_cube_respawns_out_of_the_players_hands() {
+        let mut app = headless_app();
+        spawn_device(&mut app, DissolveGate { unused: false }, Vec3::ZERO);
+        let player = spawn_test_player(&mut app, Vec3::X * 30.0);
+        app.world_mut()
+            .entity_mut(player)
+            .insert((RigidBody::Dynamic, GravityScale(0.0)));
+
+        let respawn_transform = Transform::from_xyz(0.0, 0.0, 40.0);
+        let cube = spawn_test_cube(&mut app, Vec3::X * -100.0);
+        app.world_mut().entity_mut(cube).insert((
+            Dissolveable {
+                respawn_transform: Some(respawn_transform),
+            },
+            Held::default(),
+            GravityScale(0.0),
+            // still spinning from however it got picked up
+            AngularVelocity(Vec3::Y * 5.0),
+        ));
+        app.world_mut()
+            .get_mut::<RightHand>(player)
+            .unwrap()
+            .set_active(cube);
+        advance_fixed(&mut app, 2);
+
+        app.world_mut()
+            .entity_mut(player)
+            .insert(Transform::from_translation(Vec3::ZERO));
+        advance_fixed(&mut app, 4);
+
+        let cube_ref = app.world().entity(cube);
+        assert!(!cube_ref.contains::<Held>());
+        assert_eq!(
+            *cube_ref.get::<AngularVelocity>().unwrap(),
+            AngularVelocity::ZERO
+        );
+        assert!(cube_ref
+            .get::<Transform>()
+            .unwrap()
+            .translation
+            .abs_diff_eq(respawn_transform.translation, 0.01));
+    }
+}
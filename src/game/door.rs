@@ -1,4 +1,4 @@
-use std::time::Duration;
+use std::{collections::HashSet, time::Duration};
 
 use avian3d::prelude::{
     Collider, CollisionEventsEnabled, CollisionLayers, RigidBody, RigidBodyColliders,
@@ -13,9 +13,12 @@ use bevy_tween::{
 };
 
 use crate::{
-    asset_management::asset_tag_components::{ChargePad, Door, DoorPole, ExtraDoorPowerRequired},
-    game::{audio::door_opened_audio, pressure_plate::PoweredBy},
+    asset_management::asset_tag_components::{
+        AutoCloseAfter, ChargePad, CumulativePower, Door, DoorPole, ExtraDoorPowerRequired,
+    },
+    game::{audio::door_opened_audio, pressure_plate::PoweredBy, standing_cube_spitter::Tombstone},
     rendering::{section_color_prepass::DrawSection, unlit_material::UnlitMaterial},
+    util::{clone_unlit_material, make_child_animatable},
 };
 
 use super::{
@@ -36,18 +39,59 @@ pub struct DoorOriginalPosition(pub Vec3);
 #[derive(Component)]
 pub struct PowersDoor(pub Entity);
 
+// How many poles are powering a door that needs more than one, kept up to date for the readout
+#[derive(Component, Default)]
+pub struct DoorPowerProgress {
+    pub powered: u32,
+    pub required: u32,
+}
+
+// Every pole that has powered a CumulativePower door so far
+#[derive(Component, Default)]
+pub struct CumulativePowerState {
+    pub fired_poles: HashSet<Entity>,
+}
+
+// Runtime state for doors with AutoCloseAfter
+#[derive(Component, Default)]
+pub struct DoorAutoCloseState {
+    pub timer: Option<Timer>,
+    // set once the timer runs out, cleared when the door loses power
+    pub locked_closed: bool,
+}
+
 fn register_doors(
     mut commands: Commands,
-    q_new_door: Query<(Entity, &Children, &ChildOf, &Transform), Added<Door>>,
+    q_new_door: Query<
+        (
+            Entity,
+            &Children,
+            &ChildOf,
+            &Transform,
+            Has<AutoCloseAfter>,
+            Has<ExtraDoorPowerRequired>,
+            Has<CumulativePower>,
+        ),
+        Added<Door>,
+    >,
     mut unlit_materials: ResMut<Assets<UnlitMaterial>>,
     q_unlit_objects: Query<&MeshMaterial3d<UnlitMaterial>>,
     q_children: Query<&Children>,
     q_pole: Query<Entity, With<DoorPole>>,
 ) {
-    for (door_entity, door_children, door_parent, door_transform) in &q_new_door {
+    for (
+        door_entity,
+        door_children,
+        door_parent,
+        door_transform,
+        auto_closes,
+        needs_extra_power,
+        cumulative,
+    ) in &q_new_door
+    {
         for door_child in door_children.iter() {
             if let Ok(material_handle) = q_unlit_objects.get(door_child) {
-                let mut new_material = unlit_materials.get(material_handle).unwrap().clone();
+                let mut new_material = clone_unlit_material(&unlit_materials, material_handle);
 
                 new_material.base.depth_bias = 100.;
 
@@ -77,14 +121,16 @@ fn register_doors(
                     if let Ok(pole_children) = q_children.get(pole) {
                         for pole_child in pole_children.iter() {
                             if let Ok(material_handle) = q_unlit_objects.get(pole_child) {
-                                let new_material =
-                                    unlit_materials.get(material_handle).unwrap().clone();
+                                make_child_animatable(
+                                    &mut commands,
+                                    pole_child,
+                                    material_handle,
+                                    &mut unlit_materials,
+                                );
 
                                 commands
                                     .entity(pole_child)
                                     .insert((
-                                        AnimationTarget,
-                                        MeshMaterial3d(unlit_materials.add(new_material)),
                                         CollisionLayers::new(
                                             GameLayer::Device,
                                             [
@@ -111,11 +157,29 @@ fn register_doors(
                 DoorOriginalPosition(door_transform.translation),
             ))
             .observe(door_opened_audio);
+
+        if auto_closes {
+            commands
+                .entity(door_entity)
+                .insert(DoorAutoCloseState::default());
+        }
+
+        if needs_extra_power {
+            commands
+                .entity(door_entity)
+                .insert(DoorPowerProgress::default());
+        }
+
+        if cumulative {
+            commands
+                .entity(door_entity)
+                .insert(CumulativePowerState::default());
+        }
     }
 }
 
 #[derive(Component)]
-pub struct PoweredTimer(Timer);
+pub struct PoweredTimer(pub Timer);
 
 const DOOR_POLE_POWER_DURATION_SEC: u64 = 2;
 fn door_pole_direct_signal(
@@ -134,48 +198,117 @@ fn door_pole_direct_signal(
     }
 }
 
-const DOOR_LIFT_HEIGHT: f32 = 20.;
-
 fn count_powered_poles_for_door(
     door_entity: Entity,
-    q_poles: &Query<&PowersDoor, (With<DoorPole>, With<Powered>)>,
+    q_poles: &Query<(Entity, &PowersDoor), (With<DoorPole>, With<Powered>)>,
 ) -> u32 {
     q_poles
         .iter()
-        .filter(|powers_door| powers_door.0 == door_entity)
+        .filter(|(_, powers_door)| powers_door.0 == door_entity)
         .count() as u32
 }
 
+// Remembers whichever of the door's poles are powered right now, and counts everything so far
+fn count_fired_poles_for_door(
+    door_entity: Entity,
+    cumulative_state: &mut CumulativePowerState,
+    q_poles: &Query<(Entity, &PowersDoor), (With<DoorPole>, With<Powered>)>,
+) -> u32 {
+    for (pole, powers_door) in q_poles {
+        if powers_door.0 == door_entity {
+            cumulative_state.fired_poles.insert(pole);
+        }
+    }
+
+    cumulative_state.fired_poles.len() as u32
+}
+
+const MIN_DOOR_LIFT_HEIGHT: f32 = 0.01;
+
 fn check_door_power_requirements(
     mut commands: Commands,
-    q_doors: Query<
+    time: Res<Time>,
+    mut q_doors: Query<
         (
             Entity,
             &Transform,
             &Children,
+            &Door,
             &DoorOriginalPosition,
             Option<&ExtraDoorPowerRequired>,
+            Option<(&AutoCloseAfter, &mut DoorAutoCloseState)>,
+            Option<&mut DoorPowerProgress>,
+            Option<&mut CumulativePowerState>,
         ),
-        With<Door>,
+        (With<Door>, Without<Tombstone>),
     >,
-    q_powered_poles: Query<&PowersDoor, (With<DoorPole>, With<Powered>)>,
+    q_powered_poles: Query<(Entity, &PowersDoor), (With<DoorPole>, With<Powered>)>,
     q_tween: Query<(), With<TimeSpan>>,
 ) {
-    for (door_entity, door_transform, door_children, original_pos, extra_power_required) in &q_doors
+    for (
+        door_entity,
+        door_transform,
+        door_children,
+        door,
+        original_pos,
+        extra_power_required,
+        auto_close,
+        power_progress,
+        cumulative_state,
+    ) in &mut q_doors
     {
-        let powered_count = count_powered_poles_for_door(door_entity, &q_powered_poles);
+        let powered_count = match cumulative_state {
+            Some(mut cumulative_state) => {
+                count_fired_poles_for_door(door_entity, &mut cumulative_state, &q_powered_poles)
+            }
+            None => count_powered_poles_for_door(door_entity, &q_powered_poles),
+        };
         let required_count = extra_power_required.map(|e| e.amount + 1).unwrap_or(1);
 
-        let should_be_open = powered_count >= required_count;
+        if let Some(mut power_progress) = power_progress {
+            power_progress.powered = powered_count;
+            power_progress.required = required_count;
+        }
+
+        let is_powered = powered_count >= required_count;
+        let mut should_be_open = is_powered;
+        // a zero lift height would make the open tween duration NaN
+        let lift_height = door.lift_height.max(MIN_DOOR_LIFT_HEIGHT);
         let current_y = door_transform.translation.y;
-        let target_y = original_pos.0.y + DOOR_LIFT_HEIGHT;
+        let target_y = original_pos.0.y + lift_height;
         let original_y = original_pos.0.y;
 
-        let is_currently_open = current_y > original_y + 1.0;
+        let is_currently_open = current_y > original_y + lift_height * 0.5;
 
         // Check if door is already animating by looking at its children
         let is_animating = door_children.iter().any(|child| q_tween.contains(child));
 
+        if let Some((auto_close_after, mut auto_close_state)) = auto_close {
+            if auto_close_state.locked_closed {
+                // needs to lose power before it can be opened again
+                if is_powered {
+                    should_be_open = false;
+                } else {
+                    auto_close_state.locked_closed = false;
+                }
+            } else if let Some(timer) = auto_close_state.timer.as_mut() {
+                timer.tick(time.delta());
+                should_be_open = !timer.finished();
+
+                if timer.finished() {
+                    auto_close_state.timer = None;
+                    auto_close_state.locked_closed = is_powered;
+                }
+            } else if is_currently_open && !is_animating {
+                // door just finished opening, start counting
+                auto_close_state.timer = Some(Timer::from_seconds(
+                    auto_close_after.seconds,
+                    TimerMode::Once,
+                ));
+                should_be_open = true;
+            }
+        }
+
         if should_be_open && !is_currently_open && !is_animating {
             // Door should open and isn't already animating
             for child in door_children.iter() {
@@ -185,8 +318,8 @@ fn check_door_power_requirements(
             }
 
             let remaining_distance = target_y - current_y;
-            let total_distance = DOOR_LIFT_HEIGHT;
-            let progress = remaining_distance / total_distance;
+            let total_distance = lift_height;
+            let progress = (remaining_distance / total_distance).clamp(0.0, 1.0);
             let duration = Duration::from_secs_f32(1.0 * progress);
             commands.entity(door_entity).trigger(DoorOpened);
 
@@ -206,6 +339,8 @@ fn check_door_power_requirements(
                 }
             }
 
+            commands.entity(door_entity).trigger(DoorClosed);
+
             commands.entity(door_entity).animation().insert(tween(
                 Duration::from_secs(1),
                 EaseKind::Linear,
@@ -219,10 +354,13 @@ fn check_door_power_requirements(
 #[derive(Event)]
 pub struct DoorOpened;
 
+#[derive(Event)]
+pub struct DoorClosed;
+
 fn on_power_added(
     trigger: Trigger<OnAdd, Powered>,
     mut commands: Commands,
-    q_pole: Query<&RigidBodyColliders, With<DoorPole>>,
+    q_pole: Query<&RigidBodyColliders, (With<DoorPole>, Without<Tombstone>)>,
     q_unlit_objects: Query<&MeshMaterial3d<UnlitMaterial>>,
     q_tween: Query<(), With<TimeSpan>>,
     q_children: Query<&Children, With<Collider>>,
@@ -259,7 +397,7 @@ fn on_power_added(
 fn on_power_removed(
     trigger: Trigger<OnRemove, Powered>,
     mut commands: Commands,
-    q_pole: Query<&RigidBodyColliders, With<DoorPole>>,
+    q_pole: Query<&RigidBodyColliders, (With<DoorPole>, Without<Tombstone>)>,
     q_unlit_objects: Query<&MeshMaterial3d<UnlitMaterial>>,
     q_tween: Query<(), With<TimeSpan>>,
     q_children: Query<&Children, With<Collider>>,
@@ -317,3 +455,68 @@ fn update_powered_timers(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::test_app::{advance_secs, headless_app, spawn_device, spawn_device_group};
+
+    fn spawn_door(app: &mut App, lift_height: f32) -> (Entity, Entity) {
+        let door = spawn_device(
+            app,
+            Door {
+                unused: false,
+                lift_height,
+            },
+            Vec3::ZERO,
+        );
+        let pole = spawn_device(app, DoorPole { unused: false }, Vec3::X * 30.0);
+        spawn_device_group(app, &[door, pole]);
+        advance_secs(app, 0.1);
+
+        (door, pole)
+    }
+
+    #[test]
+    fn door_lifts_by_its_lift_height() {
+        let mut app = headless_app();
+        let (door, pole) = spawn_door(&mut app, 10.0);
+
+        app.world_mut().entity_mut(pole).insert(Powered);
+        advance_secs(&mut app, 1.5);
+
+        let door_y = app.world().get::<Transform>(door).unwrap().translation.y;
+        assert!((door_y - 10.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn door_with_no_lift_height_does_not_panic() {
+        let mut app = headless_app();
+        let (_, pole) = spawn_door(&mut app, 0.0);
+
+        app.world_mut().entity_mut(pole).insert(Powered);
+        advance_secs(&mut app, 1.5);
+    }
+
+    #[derive(Resource, Default)]
+    struct DoorOpenedCount(u32);
+
+    #[test]
+    fn door_opened_fires_once_per_opening() {
+        let mut app = headless_app();
+        app.init_resource::<DoorOpenedCount>().add_observer(
+            |_trigger: Trigger<DoorOpened>, mut count: ResMut<DoorOpenedCount>| count.0 += 1,
+        );
+        let (_, pole) = spawn_door(&mut app, 20.0);
+
+        app.world_mut().entity_mut(pole).insert(Powered);
+        advance_secs(&mut app, 3.0);
+        assert_eq!(app.world().resource::<DoorOpenedCount>().0, 1);
+
+        app.world_mut().entity_mut(pole).remove::<Powered>();
+        advance_secs(&mut app, 2.0);
+        app.world_mut().entity_mut(pole).insert(Powered);
+        advance_secs(&mut app, 2.0);
+        assert_eq!(app.world().resource::<DoorOpenedCount>().0, 2);
+    }
+}
@@ -0,0 +1,188 @@
+use std::time::Duration;
+
+use avian3d::prelude::{
+    CollisionEventsEnabled, CollisionLayers, RigidBody, RigidBodyColliders, Sensor,
+};
+use bevy::prelude::*;
+use bevy_tween::{
+    bevy_time_runner::TimeSpan,
+    combinator::tween,
+    prelude::{AnimationBuilderExt, EaseKind},
+    tween::{AnimationTarget, TargetAsset},
+};
+
+use crate::{
+    asset_management::asset_tag_components::ForceField,
+    rendering::unlit_material::{MaterialAlphaInterpolator, UnlitMaterial},
+    util::clone_unlit_material,
+};
+
+use super::{
+    door::PoweredTimer,
+    pressure_plate::POWER_ANIMATION_DURATION_SEC,
+    signals::{default_signal_collisions, DirectSignal, Powered},
+    GameLayer,
+};
+
+const FORCE_FIELD_POWER_DURATION_SEC: f32 = 5.0;
+const FORCE_FIELD_SOLID_ALPHA: f32 = 0.9;
+const FORCE_FIELD_PASSABLE_ALPHA: f32 = 0.2;
+
+pub fn force_field_plugin(app: &mut App) {
+    app.add_systems(FixedPreUpdate, register_force_fields);
+}
+
+// Signals always need to reach the field, otherwise there'd be no way to turn it on
+fn solid_layers() -> CollisionLayers {
+    CollisionLayers::new(
+        GameLayer::Device,
+        [
+            GameLayer::Default,
+            GameLayer::Player,
+            GameLayer::Device,
+            GameLayer::Signal,
+        ],
+    )
+}
+
+fn passable_layers() -> CollisionLayers {
+    CollisionLayers::new(GameLayer::Device, [GameLayer::Signal])
+}
+
+fn register_force_fields(
+    mut commands: Commands,
+    q_new_force_field: Query<(Entity, &Children), Added<ForceField>>,
+    mut unlit_materials: ResMut<Assets<UnlitMaterial>>,
+    q_unlit_objects: Query<&MeshMaterial3d<UnlitMaterial>>,
+) {
+    for (force_field_entity, force_field_children) in &q_new_force_field {
+        commands
+            .entity(force_field_entity)
+            .insert(RigidBody::Static)
+            .observe(force_field_direct_signal)
+            .observe(on_power_added)
+            .observe(on_power_removed);
+
+        for force_field_child in force_field_children.iter() {
+            if let Ok(material_handle) = q_unlit_objects.get(force_field_child) {
+                let mut new_material = clone_unlit_material(&unlit_materials, material_handle);
+                new_material.base.alpha_mode = AlphaMode::Blend;
+                new_material.extension.params.alpha = FORCE_FIELD_PASSABLE_ALPHA;
+
+                commands
+                    .entity(force_field_child)
+                    .insert((
+                        AnimationTarget,
+                        MeshMaterial3d(unlit_materials.add(new_material)),
+                        passable_layers(),
+                        Sensor,
+                        CollisionEventsEnabled,
+                    ))
+                    .observe(default_signal_collisions);
+            }
+        }
+    }
+}
+
+fn force_field_direct_signal(
+    trigger: Trigger<DirectSignal>,
+    mut commands: Commands,
+    q_force_field: Query<Entity, With<ForceField>>,
+) {
+    if let Ok(force_field_entity) = q_force_field.get(trigger.target()) {
+        commands.entity(force_field_entity).insert((
+            Powered,
+            PoweredTimer(Timer::from_seconds(
+                FORCE_FIELD_POWER_DURATION_SEC,
+                TimerMode::Once,
+            )),
+        ));
+    }
+}
+
+fn on_power_added(
+    trigger: Trigger<OnAdd, Powered>,
+    mut commands: Commands,
+    q_force_field: Query<&RigidBodyColliders, With<ForceField>>,
+    q_unlit_objects: Query<&MeshMaterial3d<UnlitMaterial>>,
+    q_tween: Query<(), With<TimeSpan>>,
+    q_children: Query<&Children>,
+) {
+    let Ok(force_field_colliders) = q_force_field.get(trigger.target()) else {
+        return;
+    };
+
+    for collider_entity in force_field_colliders.iter() {
+        commands
+            .entity(collider_entity)
+            .remove::<Sensor>()
+            .insert(solid_layers());
+
+        animate_force_field_alpha(
+            &mut commands,
+            collider_entity,
+            &q_unlit_objects,
+            &q_tween,
+            &q_children,
+            FORCE_FIELD_PASSABLE_ALPHA,
+            FORCE_FIELD_SOLID_ALPHA,
+        );
+    }
+}
+
+fn on_power_removed(
+    trigger: Trigger<OnRemove, Powered>,
+    mut commands: Commands,
+    q_force_field: Query<&RigidBodyColliders, With<ForceField>>,
+    q_unlit_objects: Query<&MeshMaterial3d<UnlitMaterial>>,
+    q_tween: Query<(), With<TimeSpan>>,
+    q_children: Query<&Children>,
+) {
+    let Ok(force_field_colliders) = q_force_field.get(trigger.target()) else {
+        return;
+    };
+
+    for collider_entity in force_field_colliders.iter() {
+        commands
+            .entity(collider_entity)
+            .insert((Sensor, passable_layers()));
+
+        animate_force_field_alpha(
+            &mut commands,
+            collider_entity,
+            &q_unlit_objects,
+            &q_tween,
+            &q_children,
+            FORCE_FIELD_SOLID_ALPHA,
+            FORCE_FIELD_PASSABLE_ALPHA,
+        );
+    }
+}
+
+fn animate_force_field_alpha(
+    commands: &mut Commands,
+    collider_entity: Entity,
+    q_unlit_objects: &Query<&MeshMaterial3d<UnlitMaterial>>,
+    q_tween: &Query<(), With<TimeSpan>>,
+    q_children: &Query<&Children>,
+    start: f32,
+    end: f32,
+) {
+    // Kill any half finished fade so the two don't fight
+    if let Ok(collider_children) = q_children.get(collider_entity) {
+        for child in collider_children.iter() {
+            if q_tween.contains(child) {
+                commands.entity(child).despawn();
+            }
+        }
+    }
+
+    if let Ok(material_handle) = q_unlit_objects.get(collider_entity) {
+        commands.entity(collider_entity).animation().insert(tween(
+            Duration::from_millis((POWER_ANIMATION_DURATION_SEC * 1000.) as u64),
+            EaseKind::CubicOut,
+            TargetAsset::Asset(material_handle.clone_weak())
+                .with(MaterialAlphaInterpolator { start, end }),
+        ));
+    }
+}
@@ -0,0 +1,40 @@
+use avian3d::prelude::Gravity;
+use bevy::prelude::*;
+
+pub const DEFAULT_GRAVITY: f32 = 19.6;
+
+pub fn gravity_plugin(app: &mut App) {
+    app.init_resource::<GravitySettings>()
+        .insert_resource(Gravity(Vec3::NEG_Y * DEFAULT_GRAVITY))
+        .add_systems(Update, apply_gravity_settings);
+}
+
+// How hard things fall, in units/s². Change this rather than avian's Gravity directly so the
+// player's jump tuning can follow along.
+#[derive(Resource)]
+pub struct GravitySettings {
+    pub magnitude: f32,
+}
+
+impl Default for GravitySettings {
+    fn default() -> Self {
+        Self {
+            magnitude: DEFAULT_GRAVITY,
+        }
+    }
+}
+
+impl GravitySettings {
+    // compared to normal gravity, for scaling anything tuned against it
+    pub fn scale(&self) -> f32 {
+        self.magnitude / DEFAULT_GRAVITY
+    }
+}
+
+fn apply_gravity_settings(gravity_settings: Res<GravitySettings>, mut gravity: ResMut<Gravity>) {
+    if !gravity_settings.is_changed() {
+        return;
+    }
+
+    gravity.0 = Vec3::NEG_Y * gravity_settings.magnitude.max(0.0);
+}
@@ -0,0 +1,184 @@
+use std::time::Duration;
+
+use avian3d::prelude::{ColliderOf, RigidBody, SpatialQuery, SpatialQueryFilter};
+use bevy::prelude::*;
+use bevy_enhanced_input::prelude::{ActionValue, Actions};
+use bevy_tween::{
+    bevy_time_runner::TimeSpan,
+    combinator::tween,
+    prelude::{AnimationBuilderExt, EaseKind},
+    tween::TargetAsset,
+};
+
+use crate::{
+    asset_management::asset_tag_components::{Door, HoldButton},
+    rendering::unlit_material::UnlitMaterial,
+    util::make_child_animatable,
+    GameState, MainCamera,
+};
+
+use super::{
+    button::ButtonTargets,
+    door::PoweredTimer,
+    input::{FixedInputContext, UseInteract},
+    interaction::INTERACTION_DISTANCE,
+    pressure_plate::{POWER_ANIMATION_DURATION_SEC, POWER_MATERIAL_INTENSITY},
+    signals::{MaterialIntensityInterpolator, Powered},
+    standing_cube_spitter::Tombstone,
+    GameLayer,
+};
+
+pub fn hold_button_plugin(app: &mut App) {
+    app.add_systems(FixedPreUpdate, register_hold_buttons)
+        .add_systems(
+            FixedUpdate,
+            update_hold_buttons.run_if(in_state(GameState::Playing)),
+        );
+}
+
+// Whether the player is holding Use on this button right now
+#[derive(Component, Default)]
+pub struct HoldButtonState {
+    pub held: bool,
+    // targets the button is keeping on itself, the only ones it turns off when let go
+    pub powered_targets: Vec<Entity>,
+}
+
+fn register_hold_buttons(
+    mut commands: Commands,
+    q_new_button: Query<(Entity, &Children, &ChildOf), Added<HoldButton>>,
+    mut unlit_materials: ResMut<Assets<UnlitMaterial>>,
+    q_unlit_objects: Query<&MeshMaterial3d<UnlitMaterial>>,
+    q_children: Query<&Children>,
+    q_doors: Query<&Door>,
+) {
+    for (button_entity, button_children, button_child_of) in &q_new_button {
+        if let Ok(parent_children) = q_children.get(button_child_of.parent()) {
+            // same targets a PowerButton would get, they just stay powered instead of getting a signal
+            let button_targets: Vec<Entity> = parent_children
+                .iter()
+                .filter(|&sibling| sibling != button_entity && !q_doors.contains(sibling))
+                .collect();
+
+            commands.entity(button_entity).insert((
+                ButtonTargets(button_targets),
+                HoldButtonState::default(),
+                RigidBody::Static,
+            ));
+        }
+
+        for button_child in button_children.iter() {
+            if let Ok(material_handle) = q_unlit_objects.get(button_child) {
+                make_child_animatable(
+                    &mut commands,
+                    button_child,
+                    material_handle,
+                    &mut unlit_materials,
+                );
+            }
+        }
+    }
+}
+
+fn update_hold_buttons(
+    mut commands: Commands,
+    input: Single<&Actions<FixedInputContext>>,
+    spatial_query: SpatialQuery,
+    camera_query: Query<&GlobalTransform, With<MainCamera>>,
+    q_collider_of: Query<&ColliderOf>,
+    mut q_hold_buttons: Query<
+        (Entity, &mut HoldButtonState, &ButtonTargets, &Children),
+        Without<Tombstone>,
+    >,
+    q_powered: Query<Has<PoweredTimer>, With<Powered>>,
+    q_unlit_objects: Query<&MeshMaterial3d<UnlitMaterial>>,
+    unlit_materials: Res<Assets<UnlitMaterial>>,
+    q_tween: Query<(), With<TimeSpan>>,
+    q_children: Query<&Children>,
+) {
+    let use_held = matches!(input.value::<UseInteract>(), Ok(ActionValue::Bool(true)));
+
+    // only the button under the crosshair can be held, letting go or looking away releases it
+    let aimed_button = camera_query
+        .single()
+        .ok()
+        .filter(|_| use_held)
+        .and_then(|camera_transform| {
+            spatial_query.cast_ray(
+                camera_transform.translation(),
+                camera_transform.forward(),
+                INTERACTION_DISTANCE,
+                true,
+                &SpatialQueryFilter::default().with_mask([GameLayer::Default, GameLayer::Device]),
+            )
+        })
+        .and_then(|hit| q_collider_of.get(hit.entity).ok())
+        .map(|collider_of| collider_of.body);
+
+    for (button_entity, mut state, button_targets, button_children) in &mut q_hold_buttons {
+        let held = aimed_button == Some(button_entity);
+
+        if held {
+            for target in &button_targets.0 {
+                // a signal may have powered it on a timer first, the button keeps it on past that.
+                // Anything else powering it keeps it, and it stays on when the button is let go.
+                let powered_elsewhere = q_powered.get(*target).is_ok_and(|on_timer| !on_timer);
+                if powered_elsewhere && !state.powered_targets.contains(target) {
+                    continue;
+                }
+
+                if let Ok(mut target_commands) = commands.get_entity(*target) {
+                    target_commands
+                        .try_insert(Powered)
+                        .try_remove::<PoweredTimer>();
+                    if !state.powered_targets.contains(target) {
+                        state.powered_targets.push(*target);
+                    }
+                }
+            }
+        }
+
+        if held == state.held {
+            continue;
+        }
+        state.held = held;
+
+        if !held {
+            for target in state.powered_targets.drain(..) {
+                if let Ok(mut target_commands) = commands.get_entity(target) {
+                    target_commands.try_remove::<Powered>();
+                }
+            }
+        }
+
+        let (start, end) = if held {
+            (1.0, POWER_MATERIAL_INTENSITY)
+        } else {
+            (POWER_MATERIAL_INTENSITY, 1.0)
+        };
+
+        for button_child in button_children.iter() {
+            if let Ok(child_children) = q_children.get(button_child) {
+                for child in child_children.iter() {
+                    if q_tween.contains(child) {
+                        commands.entity(child).try_despawn();
+                    }
+                }
+            }
+
+            if let Ok(material_handle) = q_unlit_objects.get(button_child) {
+                // pick up from wherever a cut-short animation left off
+                let start = unlit_materials
+                    .get(material_handle)
+                    .map_or(start, |material| material.extension.params.intensity);
+
+                commands.entity(button_child).animation().insert(tween(
+                    Duration::from_secs_f32(POWER_ANIMATION_DURATION_SEC * 0.5),
+                    EaseKind::CubicOut,
+                    TargetAsset::Asset(material_handle.clone_weak())
+                        .with(MaterialIntensityInterpolator { start, end }),
+                ));
+            }
+        }
+    }
+}
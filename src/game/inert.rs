@@ -5,17 +5,21 @@ use bevy::prelude::*;
 use bevy_tween::{
     combinator::{parallel, tween},
     prelude::{AnimationBuilderExt, EaseKind},
-    tween::{AnimationTarget, TargetAsset},
+    tween::TargetAsset,
 };
 
 use crate::{
-    asset_management::asset_tag_components::Inert,
+    asset_management::asset_tag_components::{Inert, StartsPowered},
     rendering::unlit_material::{MaterialColorOverrideInterpolator, UnlitMaterial},
+    util::make_child_animatable,
 };
 
 use super::{
     pressure_plate::{POWER_ANIMATION_DURATION_SEC, POWER_MATERIAL_INTENSITY},
-    signals::{default_signal_collisions, DirectSignal, MaterialIntensityInterpolator},
+    signals::{
+        default_signal_collisions, DirectSignal, MaterialIntensityInterpolator, Powered,
+        SignalDebounce,
+    },
     GameLayer,
 };
 
@@ -25,25 +29,35 @@ pub fn inert_plugin(app: &mut App) {
 
 fn register_inert(
     mut commands: Commands,
-    q_new_inert: Query<(Entity, &Children), Added<Inert>>,
+    q_new_inert: Query<(Entity, &Children, Has<StartsPowered>), Added<Inert>>,
     mut unlit_materials: ResMut<Assets<UnlitMaterial>>,
     q_unlit_objects: Query<&MeshMaterial3d<UnlitMaterial>>,
 ) {
-    for (inert_entity, inert_children) in &q_new_inert {
+    for (inert_entity, inert_children, starts_powered) in &q_new_inert {
         commands
             .entity(inert_entity)
-            .insert(RigidBody::Static)
+            .insert((RigidBody::Static, SignalDebounce::default()))
             .observe(inert_direct_signal);
 
+        if starts_powered {
+            commands
+                .entity(inert_entity)
+                .insert(Powered)
+                .remove::<StartsPowered>();
+        }
+
         for inert_child in inert_children.iter() {
             if let Ok(material_handle) = q_unlit_objects.get(inert_child) {
-                if let Some(old_material) = unlit_materials.get(material_handle) {
-                    let new_material = old_material.clone();
+                if unlit_materials.get(material_handle).is_some() {
+                    make_child_animatable(
+                        &mut commands,
+                        inert_child,
+                        material_handle,
+                        &mut unlit_materials,
+                    );
                     commands
                         .entity(inert_child)
                         .insert((
-                            AnimationTarget,
-                            MeshMaterial3d(unlit_materials.add(new_material)),
                             CollisionLayers::new(
                                 GameLayer::Device,
                                 [GameLayer::Player, GameLayer::Signal, GameLayer::Device],
@@ -67,41 +81,51 @@ fn inert_direct_signal(
     let inert_entity = trigger.target();
 
     if let Ok(inert_children) = q_inert.get(inert_entity) {
-        for child in inert_children.iter() {
-            if let Ok(material_handle) = q_unlit_objects.get(child) {
-                // Instantly snap to max brightness
-                if let Some(material) = unlit_materials.get_mut(material_handle) {
-                    material.extension.params.intensity = POWER_MATERIAL_INTENSITY;
-                }
+        flash_device(
+            &mut commands,
+            inert_children,
+            &q_unlit_objects,
+            &mut unlit_materials,
+        );
+    }
+}
 
-                // Then tween down to dim
-                commands.entity(child).animation().insert(parallel((
-                    tween(
-                        Duration::from_millis((POWER_ANIMATION_DURATION_SEC * 1000.) as u64),
-                        EaseKind::CubicOut,
-                        TargetAsset::Asset(material_handle.clone_weak()).with(
-                            MaterialIntensityInterpolator {
-                                start: POWER_MATERIAL_INTENSITY,
-                                end: 1.0, // Normal intensity
-                            },
-                        ),
+// Snap a device's materials bright, then fade them back down, showing the signal landed
+pub fn flash_device(
+    commands: &mut Commands,
+    device_children: &Children,
+    q_unlit_objects: &Query<&MeshMaterial3d<UnlitMaterial>>,
+    unlit_materials: &mut Assets<UnlitMaterial>,
+) {
+    for child in device_children.iter() {
+        if let Ok(material_handle) = q_unlit_objects.get(child) {
+            // Instantly snap to max brightness
+            if let Some(material) = unlit_materials.get_mut(material_handle) {
+                material.extension.params.intensity = POWER_MATERIAL_INTENSITY;
+            }
+
+            // Then tween down to dim
+            commands.entity(child).animation().insert(parallel((
+                tween(
+                    Duration::from_millis((POWER_ANIMATION_DURATION_SEC * 1000.) as u64),
+                    EaseKind::CubicOut,
+                    TargetAsset::Asset(material_handle.clone_weak()).with(
+                        MaterialIntensityInterpolator {
+                            start: POWER_MATERIAL_INTENSITY,
+                            end: 1.0, // Normal intensity
+                        },
                     ),
-                    tween(
-                        Duration::from_millis((POWER_ANIMATION_DURATION_SEC * 1000.) as u64),
-                        EaseKind::CubicOut,
-                        TargetAsset::Asset(material_handle.clone_weak()).with(
-                            MaterialColorOverrideInterpolator {
-                                target_color: LinearRgba::new(
-                                    2. / 255.,
-                                    76. / 255.,
-                                    128. / 255.,
-                                    1.0,
-                                ),
-                            },
-                        ),
+                ),
+                tween(
+                    Duration::from_millis((POWER_ANIMATION_DURATION_SEC * 1000.) as u64),
+                    EaseKind::CubicOut,
+                    TargetAsset::Asset(material_handle.clone_weak()).with(
+                        MaterialColorOverrideInterpolator {
+                            target_color: LinearRgba::new(2. / 255., 76. / 255., 128. / 255., 1.0),
+                        },
                     ),
-                )));
-            }
+                ),
+            )));
         }
     }
 }
@@ -1,8 +1,8 @@
 use bevy::prelude::*;
 use bevy_enhanced_input::{
     input::Input,
-    prelude::{Actions, Binding, InputAction, InputContext, InputContextAppExt},
-    preset::Cardinal,
+    prelude::{Actions, Binding, InputAction, InputContext, InputContextAppExt, RebuildBindings},
+    preset::{Axial, Cardinal},
     EnhancedInputPlugin,
 };
 
@@ -27,7 +27,144 @@ pub fn input_plugin(app: &mut App) {
         .add_input_context::<FixedInputContext>()
         .add_observer(update_input_binding)
         .add_observer(fixed_update_input_binding)
-        .add_systems(Startup, spawn_input_manager);
+        .init_resource::<KeyBindings>()
+        .add_systems(Startup, spawn_input_manager)
+        .add_systems(Update, rebuild_bindings_on_change);
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RebindableAction {
+    Forward,
+    Back,
+    Left,
+    Right,
+    Jump,
+    Sprint,
+    UseInteract,
+    SystemMenu,
+}
+
+impl RebindableAction {
+    pub const ALL: [RebindableAction; 8] = [
+        RebindableAction::Forward,
+        RebindableAction::Back,
+        RebindableAction::Left,
+        RebindableAction::Right,
+        RebindableAction::Jump,
+        RebindableAction::Sprint,
+        RebindableAction::UseInteract,
+        RebindableAction::SystemMenu,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            RebindableAction::Forward => "Forward",
+            RebindableAction::Back => "Back",
+            RebindableAction::Left => "Left",
+            RebindableAction::Right => "Right",
+            RebindableAction::Jump => "Jump",
+            RebindableAction::Sprint => "Sprint",
+            RebindableAction::UseInteract => "Use",
+            RebindableAction::SystemMenu => "Menu",
+        }
+    }
+}
+
+// Keyboard/mouse bindings for each rebindable action. Gamepad bindings and Escape (so the menu can
+// always be reached) are fixed.
+#[derive(Resource)]
+pub struct KeyBindings {
+    pub forward: Input,
+    pub back: Input,
+    pub left: Input,
+    pub right: Input,
+    pub jump: Input,
+    pub sprint: Input,
+    pub use_interact: Input,
+    pub system_menu: Input,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            forward: KeyCode::KeyW.into(),
+            back: KeyCode::KeyS.into(),
+            left: KeyCode::KeyA.into(),
+            right: KeyCode::KeyD.into(),
+            jump: KeyCode::Space.into(),
+            sprint: KeyCode::ShiftLeft.into(),
+            use_interact: MouseButton::Left.into(),
+            system_menu: KeyCode::Tab.into(),
+        }
+    }
+}
+
+impl KeyBindings {
+    pub fn get(&self, action: RebindableAction) -> Input {
+        match action {
+            RebindableAction::Forward => self.forward,
+            RebindableAction::Back => self.back,
+            RebindableAction::Left => self.left,
+            RebindableAction::Right => self.right,
+            RebindableAction::Jump => self.jump,
+            RebindableAction::Sprint => self.sprint,
+            RebindableAction::UseInteract => self.use_interact,
+            RebindableAction::SystemMenu => self.system_menu,
+        }
+    }
+
+    pub fn set(&mut self, action: RebindableAction, input: Input) {
+        match action {
+            RebindableAction::Forward => self.forward = input,
+            RebindableAction::Back => self.back = input,
+            RebindableAction::Left => self.left = input,
+            RebindableAction::Right => self.right = input,
+            RebindableAction::Jump => self.jump = input,
+            RebindableAction::Sprint => self.sprint = input,
+            RebindableAction::UseInteract => self.use_interact = input,
+            RebindableAction::SystemMenu => self.system_menu = input,
+        }
+    }
+}
+
+pub fn input_label(input: Input) -> String {
+    match input {
+        Input::Keyboard { key, .. } => format!("{:?}", key),
+        Input::MouseButton { button, .. } => format!("Mouse {:?}", button),
+        _ => String::from("?"),
+    }
+}
+
+// Compact version for prompts, e.g. "LMB" or "E"
+pub fn short_input_label(input: Input) -> String {
+    match input {
+        Input::Keyboard { key, .. } => {
+            let name = format!("{:?}", key);
+            name.strip_prefix("Key")
+                .or_else(|| name.strip_prefix("Digit"))
+                .unwrap_or(&name)
+                .to_string()
+        }
+        Input::MouseButton {
+            button: MouseButton::Left,
+            ..
+        } => String::from("LMB"),
+        Input::MouseButton {
+            button: MouseButton::Right,
+            ..
+        } => String::from("RMB"),
+        Input::MouseButton {
+            button: MouseButton::Middle,
+            ..
+        } => String::from("MMB"),
+        _ => input_label(input),
+    }
+}
+
+fn rebuild_bindings_on_change(mut commands: Commands, key_bindings: Res<KeyBindings>) {
+    if key_bindings.is_changed() && !key_bindings.is_added() {
+        commands.trigger(RebuildBindings);
+    }
 }
 
 #[derive(Debug, InputAction)]
@@ -38,14 +175,35 @@ pub struct SystemMenuOrCancel;
 #[input_action(output = bool)]
 pub struct UseInteract;
 
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+pub struct RetrieveLastDrop;
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+pub struct CycleHeld;
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+pub struct DropHeld;
+
 #[derive(Debug, InputAction)]
 #[input_action(output = bool)]
 pub struct Jump;
 
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+pub struct Sprint;
+
 #[derive(Debug, InputAction)]
 #[input_action(output = Vec2)]
 pub struct Look;
 
+// Analog look from a gamepad stick; unlike mouse Look this is a rate and gets scaled by frame time
+#[derive(Debug, InputAction)]
+#[input_action(output = Vec2)]
+pub struct LookStick;
+
 #[derive(Debug, InputAction)]
 #[input_action(output = Vec2)]
 pub struct Movement;
@@ -64,24 +222,60 @@ fn spawn_input_manager(mut commands: Commands) {
 fn update_input_binding(
     trigger: Trigger<Binding<UpdateInputContext>>,
     mut q_update_input_manager: Query<&mut Actions<UpdateInputContext>>,
+    key_bindings: Res<KeyBindings>,
 ) {
     if let Ok(mut actions) = q_update_input_manager.get_mut(trigger.target()) {
         actions.bind::<SystemMenuOrCancel>().to(KeyCode::Escape);
-        actions.bind::<SystemMenuOrCancel>().to(KeyCode::Tab);
+        actions
+            .bind::<SystemMenuOrCancel>()
+            .to(key_bindings.system_menu);
+        actions
+            .bind::<SystemMenuOrCancel>()
+            .to(GamepadButton::Start);
 
         actions.bind::<Look>().to(Input::mouse_motion());
+        actions.bind::<LookStick>().to(Axial::right_stick());
     }
 }
 
 fn fixed_update_input_binding(
     trigger: Trigger<Binding<FixedInputContext>>,
     mut q_fixed_input_manager: Query<&mut Actions<FixedInputContext>>,
+    key_bindings: Res<KeyBindings>,
 ) {
     if let Ok(mut actions) = q_fixed_input_manager.get_mut(trigger.target()) {
-        actions.bind::<Movement>().to(Cardinal::wasd_keys());
+        actions.bind::<Movement>().to((
+            Cardinal {
+                north: key_bindings.forward,
+                east: key_bindings.right,
+                south: key_bindings.back,
+                west: key_bindings.left,
+            },
+            Axial::left_stick(),
+        ));
+
+        actions
+            .bind::<Jump>()
+            .to((key_bindings.jump, GamepadButton::South));
+
+        actions
+            .bind::<Sprint>()
+            .to((key_bindings.sprint, GamepadButton::LeftThumb));
+
+        actions
+            .bind::<UseInteract>()
+            .to((key_bindings.use_interact, GamepadButton::West));
+
+        actions
+            .bind::<RetrieveLastDrop>()
+            .to((KeyCode::KeyR, GamepadButton::North));
 
-        actions.bind::<Jump>().to(KeyCode::Space);
+        actions
+            .bind::<CycleHeld>()
+            .to((KeyCode::KeyQ, GamepadButton::East));
 
-        actions.bind::<UseInteract>().to(MouseButton::Left);
+        actions
+            .bind::<DropHeld>()
+            .to((KeyCode::KeyG, GamepadButton::DPadDown));
     }
 }
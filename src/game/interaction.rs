@@ -1,9 +1,8 @@
 use std::time::Duration;
 
 use avian3d::prelude::{
-    ColliderConstructor, ColliderOf, CollisionEventsEnabled, CollisionLayers, LockedAxes,
-    RigidBody, RigidBodyColliders, RotationInterpolation, Sensor, SpatialQuery, SpatialQueryFilter,
-    TransformInterpolation,
+    ColliderConstructor, ColliderOf, CollisionLayers, LockedAxes, RigidBody, RigidBodyColliders,
+    RotationInterpolation, SpatialQuery, SpatialQueryFilter, TransformInterpolation,
 };
 use bevy::prelude::*;
 use bevy_enhanced_input::events::Completed;
@@ -14,49 +13,98 @@ use bevy_tween::{
     tween::{AnimationTarget, TargetComponent},
 };
 
-use crate::asset_management::{
-    asset_loading::GameAssets,
-    asset_tag_components::{
-        BigRedButton, CubeSpitter, ExitDoorShutter, Immobile, PowerButton, SignalSpitter,
-        StandingCubeSpitter, WeightedCube,
+use crate::{
+    asset_management::{
+        asset_loading::GameAssets,
+        asset_tag_components::{
+            BigRedButton, CubeSpitter, ExitDoorShutter, FreezeOnPower, HandToggleable, HoldButton,
+            Immobile, PowerButton, SignalSpitter, StandingCubeSpitter, WeightedCube,
+            WeightedCubeColors,
+        },
     },
+    MainCamera,
 };
 
 use super::{
     button::button_pressed,
     dissolve_gate::Dissolveable,
-    input::UseInteract,
-    player::{Held, RightHand},
-    signals::{Signal, MAX_SIGNAL_TRAVEL_DIST},
+    input::{CycleHeld, RetrieveLastDrop, UseInteract},
+    player::{
+        stash_held_object, unstash_held_object, CarryConfig, CarryWeight, Held, HeldGhost,
+        RightHand, Stashed, HEAVY_CARRY_WEIGHT, LIGHT_CARRY_WEIGHT,
+    },
+    signal_spitter::toggle_continuous_emission,
+    signals::{
+        signal_lifetime, spawn_signal, Powered, Signal, SignalBudget, SignalMeshCache,
+        DEFAULT_SIGNAL_SPEED, MAX_SIGNAL_TRAVEL_DIST,
+    },
     GameLayer,
 };
 
 pub fn interaction_plugin(app: &mut App) {
-    app.add_observer(interact).add_systems(
-        FixedPreUpdate,
-        (
-            register_big_red_button_interaction,
-            register_power_button_interaction,
-            register_weighted_cube_interaction,
-            register_signal_spitter_interaction,
-            register_standing_cube_spitter_interaction,
-        ),
-    );
+    app.init_resource::<InteractionCooldown>()
+        .add_observer(interact)
+        .add_observer(retrieve_last_drop)
+        .add_observer(cycle_held_object)
+        .add_systems(
+            FixedPreUpdate,
+            (
+                register_big_red_button_interaction,
+                register_power_button_interaction,
+                register_hold_button_interaction,
+                register_weighted_cube_interaction,
+                register_signal_spitter_interaction,
+                register_standing_cube_spitter_interaction,
+            ),
+        );
 }
 
 pub const INTERACTION_DISTANCE: f32 = 30.;
 
+// Mashing Use shouldn't be able to press a button twice or pick something up and drop it again
+// straight away, so anything within `cooldown_secs` of the last interaction is ignored
+#[derive(Resource)]
+pub struct InteractionCooldown {
+    pub cooldown_secs: f32,
+    last_interaction: Option<Duration>,
+}
+
+impl Default for InteractionCooldown {
+    fn default() -> Self {
+        Self {
+            cooldown_secs: 0.15,
+            last_interaction: None,
+        }
+    }
+}
+
+impl InteractionCooldown {
+    fn ready(&self, now: Duration) -> bool {
+        self.last_interaction.is_none_or(|last_interaction| {
+            now.saturating_sub(last_interaction).as_secs_f32() >= self.cooldown_secs
+        })
+    }
+}
+
 fn interact(
     _trigger: Trigger<Completed<UseInteract>>,
     mut commands: Commands,
+    mut cooldown: ResMut<InteractionCooldown>,
+    time: Res<Time>,
     spatial_query: SpatialQuery,
-    camera_query: Query<&GlobalTransform, With<Camera>>,
+    camera_query: Query<&GlobalTransform, With<MainCamera>>,
     interactables: Query<&Interactable, Without<InteractionsDisabled>>,
-    right_hand: Single<&mut RightHand>,
+    mut right_hand: Single<&mut RightHand>,
     q_held: Query<&Held>,
+    q_ghosts: Query<(&HeldGhost, &Transform)>,
+    carry_config: Res<CarryConfig>,
 ) {
     let mut found_hit: bool = false;
 
+    if !cooldown.ready(time.elapsed()) {
+        return;
+    }
+
     // Get camera transform and window for raycast
     let Ok(camera_transform) = camera_query.single() else {
         return;
@@ -78,12 +126,13 @@ fn interact(
 
         // Check if the hit entity is interactable
         if let Ok(interactable) = interactables.get(hit_entity) {
-            // Check if we can interact (don't pick up if already holding something)
-            let can_interact = !(right_hand.held_object.is_some()
+            // Check if we can interact (don't pick up if there's no room for it)
+            let can_interact = !(right_hand.is_full(carry_config.capacity)
                 && matches!(interactable.primary_action, Interactions::PickUp));
 
             if can_interact {
                 commands.entity(hit_entity).trigger(Interacted);
+                cooldown.last_interaction = Some(time.elapsed());
                 found_hit = true;
             }
         }
@@ -91,19 +140,129 @@ fn interact(
 
     // If no interaction found, try to release held object
     if !found_hit {
-        if let Some(held_entity) = right_hand.held_object {
+        if let Some(held_entity) = right_hand.held_object() {
             if let Ok(held) = q_held.get(held_entity) {
                 if held.can_release {
+                    // with a ghost preview the object was left parked, so it goes where the
+                    // ghost was before it's let go
+                    if let Some((_, ghost_transform)) =
+                        q_ghosts.iter().find(|(ghost, _)| ghost.held == held_entity)
+                    {
+                        commands.entity(held_entity).insert(*ghost_transform);
+                    }
+
                     commands
                         .entity(held_entity)
                         .remove::<Held>()
                         .insert((RotationInterpolation, TransformInterpolation));
+                    right_hand.last_released = Some(held_entity);
+                    cooldown.last_interaction = Some(time.elapsed());
                 }
             }
         }
     }
 }
 
+// further than a normal pickup, otherwise you could just click on it
+const RETRIEVE_DISTANCE: f32 = INTERACTION_DISTANCE * 2.;
+
+// Grabs the last thing the player put down, as long as it's still pickable and they can see it.
+// Goes through Interacted on the object's collider so it's the same as clicking on it.
+fn retrieve_last_drop(
+    _trigger: Trigger<Completed<RetrieveLastDrop>>,
+    mut commands: Commands,
+    spatial_query: SpatialQuery,
+    camera_query: Query<&GlobalTransform, With<MainCamera>>,
+    q_body_transforms: Query<&GlobalTransform>,
+    q_collider_of: Query<&ColliderOf>,
+    interactables: Query<&Interactable, Without<InteractionsDisabled>>,
+    mut right_hand: Single<&mut RightHand>,
+    carry_config: Res<CarryConfig>,
+) {
+    if right_hand.is_full(carry_config.capacity) {
+        return;
+    }
+
+    let Some(last_released) = right_hand.last_released else {
+        return;
+    };
+
+    let Ok(body_transform) = q_body_transforms.get(last_released) else {
+        // despawned since it was dropped
+        right_hand.last_released = None;
+        return;
+    };
+
+    let Ok(camera_transform) = camera_query.single() else {
+        return;
+    };
+
+    let ray_origin = camera_transform.translation();
+    let Ok(ray_direction) = Dir3::new(body_transform.translation() - ray_origin) else {
+        return;
+    };
+
+    let Some(hit) = spatial_query.cast_ray(
+        ray_origin,
+        ray_direction,
+        RETRIEVE_DISTANCE,
+        true,
+        &SpatialQueryFilter::default().with_mask([GameLayer::Default, GameLayer::Device]),
+    ) else {
+        return;
+    };
+
+    // anything else in the way means it's out of sight
+    let hit_last_released = q_collider_of
+        .get(hit.entity)
+        .is_ok_and(|collider_of| collider_of.body == last_released);
+    let pickable = interactables
+        .get(hit.entity)
+        .is_ok_and(|interactable| matches!(interactable.primary_action, Interactions::PickUp));
+
+    if hit_last_released && pickable {
+        commands.entity(hit.entity).trigger(Interacted);
+        right_hand.last_released = None;
+    }
+}
+
+// Puts the held object away and brings out the next thing being carried
+fn cycle_held_object(
+    _trigger: Trigger<Completed<CycleHeld>>,
+    mut commands: Commands,
+    mut right_hand: Single<&mut RightHand>,
+    q_carried: Query<&RigidBodyColliders, Or<(With<Held>, With<Stashed>)>>,
+) {
+    let Some(active) = right_hand.held_object() else {
+        return;
+    };
+
+    // anything dissolved while it was stashed is gone
+    right_hand
+        .held_objects
+        .retain(|held_object| q_carried.contains(*held_object));
+    let Some(active_index) = right_hand
+        .held_objects
+        .iter()
+        .position(|held_object| *held_object == active)
+    else {
+        return;
+    };
+
+    if right_hand.held_objects.len() < 2 {
+        right_hand.active = active_index;
+        return;
+    }
+
+    right_hand.active = (active_index + 1) % right_hand.held_objects.len();
+    let next_held = right_hand.held_objects[right_hand.active];
+
+    stash_held_object(&mut commands, active);
+    if let Ok(next_colliders) = q_carried.get(next_held) {
+        unstash_held_object(&mut commands, next_held, next_colliders);
+    }
+}
+
 #[derive(Event)]
 pub struct Interacted;
 
@@ -124,6 +283,9 @@ impl Interactable {
 pub enum Interactions {
     Press,
     PickUp,
+    Toggle,
+    // nothing happens on the click itself, hold_button watches for Use being held down
+    Hold,
 }
 
 // Rest of your existing functions remain the same...
@@ -135,38 +297,42 @@ fn big_red_button_interaction(
     q_body_transforms: Query<&GlobalTransform, (With<RigidBody>, Without<CubeSpitter>)>,
     exit_door_shutter: Single<Entity, With<ExitDoorShutter>>,
     mut meshes: ResMut<Assets<Mesh>>,
+    mut signal_mesh_cache: ResMut<SignalMeshCache>,
+    q_signals: Query<(), With<Signal>>,
+    signal_budget: Res<SignalBudget>,
 ) {
     let button_collider_of = q_collider_of.get(trigger.target()).unwrap();
     let target_location = q_body_transforms.get(button_collider_of.body).unwrap();
 
     let start_loc = target_location.translation() + Vec3::Y * 10.;
-    let signal_indicator = commands
-        .spawn((
+    let mut live_signals = q_signals.iter().count();
+
+    // the shutter still opens if there's no room for the signal
+    if let Some(signal_indicator) = spawn_signal(
+        &mut commands,
+        &mut live_signals,
+        &signal_budget,
+        (
             ColliderConstructor::Cuboid {
                 x_length: 100.,
                 y_length: 100.,
                 z_length: 0.1,
             },
             CollisionLayers::new(GameLayer::Signal, [GameLayer::Device]),
-            Mesh3d(meshes.add(Plane3d::new(-Vec3::Z, Vec2::splat(100.)))),
-            MeshMaterial3d(game_assets.cyan_signal_material.clone()),
+            Mesh3d(signal_mesh_cache.plane(&mut meshes, 100.)),
+            MeshMaterial3d(game_assets.signal_material(WeightedCubeColors::Cyan)),
             Transform::from_translation(start_loc),
-            AnimationTarget,
-            CollisionEventsEnabled,
-            RigidBody::Kinematic,
-            Sensor,
-            Signal,
-        ))
-        .id();
-
-    commands.entity(signal_indicator).animation().insert(tween(
-        Duration::from_secs(10),
-        EaseKind::Linear,
-        TargetComponent::marker().with(translation(
-            start_loc,
-            start_loc + Vec3::Z * MAX_SIGNAL_TRAVEL_DIST,
-        )),
-    ));
+        ),
+    ) {
+        commands.entity(signal_indicator).animation().insert(tween(
+            signal_lifetime(DEFAULT_SIGNAL_SPEED),
+            EaseKind::Linear,
+            TargetComponent::marker().with(translation(
+                start_loc,
+                start_loc + Vec3::Z * MAX_SIGNAL_TRAVEL_DIST,
+            )),
+        ));
+    }
 
     let target = TargetComponent::marker();
     commands.entity(*exit_door_shutter).insert(AnimationTarget);
@@ -223,21 +389,59 @@ fn register_power_button_interaction(
     }
 }
 
+fn register_hold_button_interaction(
+    mut commands: Commands,
+    q_new_buttons: Query<&Children, Added<HoldButton>>,
+    q_mesh: Query<Entity, With<Mesh3d>>,
+) {
+    for children in &q_new_buttons {
+        if let Some(found_child) = children.iter().find(|&child| q_mesh.contains(child)) {
+            commands
+                .entity(found_child)
+                .insert(Interactable::new(Interactions::Hold));
+        }
+    }
+}
+
 fn pick_up(
     trigger: Trigger<Interacted>,
     mut commands: Commands,
     mut right_hand: Single<&mut RightHand>,
     q_collider_of: Query<&ColliderOf>,
+    q_carry_weight: Query<&CarryWeight>,
+    q_frozen: Query<(), (With<FreezeOnPower>, With<Powered>)>,
+    carry_config: Res<CarryConfig>,
 ) {
     if let Ok(collider_of) = q_collider_of.get(trigger.target()) {
-        if right_hand.held_object.is_none() {
-            right_hand.held_object = Some(collider_of.body);
-            commands
-                .entity(collider_of.body)
-                .insert(Held::default())
-                .remove::<TransformInterpolation>()
-                .remove::<RotationInterpolation>();
+        if right_hand.is_full(carry_config.capacity) {
+            return;
+        }
+
+        // frozen in place while powered, it's a platform until it loses power
+        if q_frozen.contains(collider_of.body) {
+            return;
+        }
+
+        // only light things can be carried together, what's already stashed got checked going in
+        if let Some(held_entity) = right_hand.held_object() {
+            let is_light = |entity| {
+                q_carry_weight
+                    .get(entity)
+                    .is_ok_and(|carry_weight| carry_weight.factor <= LIGHT_CARRY_WEIGHT)
+            };
+            if !is_light(held_entity) || !is_light(collider_of.body) {
+                return;
+            }
+
+            stash_held_object(&mut commands, held_entity);
         }
+
+        right_hand.set_active(collider_of.body);
+        commands
+            .entity(collider_of.body)
+            .insert(Held::default())
+            .remove::<TransformInterpolation>()
+            .remove::<RotationInterpolation>();
     }
 }
 
@@ -256,26 +460,53 @@ fn register_weighted_cube_interaction(
                 .observe(pick_up)
                 .insert(Interactable::new(Interactions::PickUp));
         }
-        commands.entity(cube_entity).insert(Dissolveable {
-            respawn_transform: None,
-        });
+        commands.entity(cube_entity).insert((
+            Dissolveable {
+                respawn_transform: None,
+            },
+            CarryWeight {
+                factor: LIGHT_CARRY_WEIGHT,
+            },
+        ));
     }
 }
 
 fn register_signal_spitter_interaction(
     mut commands: Commands,
-    q_new_spitters: Query<(Entity, &Children, &Transform, Has<Immobile>), Added<SignalSpitter>>,
+    q_new_spitters: Query<
+        (
+            Entity,
+            &Children,
+            &Transform,
+            Has<Immobile>,
+            Has<HandToggleable>,
+        ),
+        Added<SignalSpitter>,
+    >,
     q_mesh: Query<Entity, With<Mesh3d>>,
 ) {
-    for (new_spitter, children, transform, is_immobile) in &q_new_spitters {
+    for (new_spitter, children, transform, is_immobile, is_hand_toggleable) in &q_new_spitters {
         if children.len() > 1 {
             warn!("spitter cannot have more than one child");
             continue;
         }
 
         if let Some(found_child) = children.iter().find(|&child| q_mesh.contains(child)) {
+            // toggling takes the place of picking it up
+            if is_hand_toggleable {
+                commands
+                    .entity(found_child)
+                    .observe(toggle_continuous_emission)
+                    .insert(Interactable::new(Interactions::Toggle));
+            }
+
             if is_immobile {
                 commands.entity(new_spitter).insert(RigidBody::Static);
+            } else if is_hand_toggleable {
+                commands.entity(new_spitter).insert((
+                    RigidBody::Dynamic,
+                    LockedAxes::ALL_LOCKED.unlock_translation_y(),
+                ));
             } else {
                 commands
                     .entity(found_child)
@@ -288,6 +519,9 @@ fn register_signal_spitter_interaction(
                     Dissolveable {
                         respawn_transform: Some(*transform),
                     },
+                    CarryWeight {
+                        factor: HEAVY_CARRY_WEIGHT,
+                    },
                 ));
             }
         }
@@ -323,8 +557,40 @@ fn register_standing_cube_spitter_interaction(
                     Dissolveable {
                         respawn_transform: Some(*transform),
                     },
+                    CarryWeight {
+                        factor: HEAVY_CARRY_WEIGHT,
+                    },
                 ));
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::test_app::{advance_fixed, headless_app, spawn_test_cube, spawn_test_player};
+
+    #[test]
+    fn frozen_cube_cannot_be_picked_up() {
+        let mut app = headless_app();
+        app.init_resource::<CarryConfig>().add_observer(pick_up);
+        spawn_test_player(&mut app, Vec3::X * 20.0);
+        let cube = spawn_test_cube(&mut app, Vec3::ZERO);
+        app.world_mut()
+            .entity_mut(cube)
+            .insert(FreezeOnPower { unused: false });
+        advance_fixed(&mut app, 2);
+        let cube_collider = app.world().get::<Children>(cube).unwrap()[0];
+
+        app.world_mut().entity_mut(cube).insert(Powered);
+        app.world_mut().trigger_targets(Interacted, cube_collider);
+        app.world_mut().flush();
+        assert!(!app.world().entity(cube).contains::<Held>());
+
+        app.world_mut().entity_mut(cube).remove::<Powered>();
+        app.world_mut().trigger_targets(Interacted, cube_collider);
+        app.world_mut().flush();
+        assert!(app.world().entity(cube).contains::<Held>());
+    }
+}
@@ -0,0 +1,73 @@
+use std::time::Duration;
+
+use bevy::{prelude::*, time::Stopwatch};
+
+use crate::{
+    asset_management::level_manager::{
+        save_progress, unlock_next_level, LevelManager, LevelProgress,
+    },
+    GameState,
+};
+
+pub fn level_timer_plugin(app: &mut App) {
+    app.init_resource::<LevelTimer>()
+        .add_systems(OnEnter(GameState::Playing), start_level_timer)
+        .add_systems(
+            OnEnter(GameState::Win),
+            stop_level_timer.after(unlock_next_level),
+        )
+        .add_systems(Update, tick_level_timer);
+}
+
+// Runs from leaving the main menu until the win screen
+#[derive(Resource, Default)]
+pub struct LevelTimer {
+    pub stopwatch: Stopwatch,
+    pub running: bool,
+    // set when the run that just finished beat the saved best
+    pub new_best: bool,
+}
+
+fn start_level_timer(mut level_timer: ResMut<LevelTimer>) {
+    level_timer.stopwatch.reset();
+    level_timer.running = true;
+    level_timer.new_best = false;
+}
+
+fn tick_level_timer(mut level_timer: ResMut<LevelTimer>, time: Res<Time>) {
+    if level_timer.running {
+        level_timer.stopwatch.tick(time.delta());
+    }
+}
+
+pub fn stop_level_timer(
+    mut level_timer: ResMut<LevelTimer>,
+    level_manager: Res<LevelManager>,
+    mut progress: ResMut<LevelProgress>,
+) {
+    level_timer.running = false;
+
+    let elapsed = level_timer.stopwatch.elapsed();
+    level_timer.new_best = progress
+        .best_times
+        .get(&level_manager.current)
+        .is_none_or(|best| elapsed < *best);
+
+    if level_timer.new_best {
+        progress.best_times.insert(level_manager.current, elapsed);
+    }
+
+    // also picks up the level unlock from this win
+    save_progress(&level_manager, &progress);
+}
+
+// mm:ss.mmm
+pub fn format_level_time(time: Duration) -> String {
+    let millis = time.as_millis();
+    format!(
+        "{:02}:{:02}.{:03}",
+        millis / 60_000,
+        (millis / 1000) % 60,
+        millis % 1000
+    )
+}
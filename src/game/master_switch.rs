@@ -0,0 +1,97 @@
+use avian3d::prelude::{CollisionEventsEnabled, CollisionLayers, RigidBody};
+use bevy::prelude::*;
+
+use crate::{
+    asset_management::asset_tag_components::{MasterSwitch, WeightedCube},
+    rendering::unlit_material::UnlitMaterial,
+    util::make_child_animatable,
+};
+
+use super::{
+    inert::flash_device,
+    signals::{default_signal_collisions, DirectSignal, Powered, SignalDebounce},
+    GameLayer,
+};
+
+pub fn master_switch_plugin(app: &mut App) {
+    app.add_systems(FixedPreUpdate, register_master_switches);
+}
+
+fn register_master_switches(
+    mut commands: Commands,
+    q_new_switch: Query<(Entity, &Children), Added<MasterSwitch>>,
+    mut unlit_materials: ResMut<Assets<UnlitMaterial>>,
+    q_unlit_objects: Query<&MeshMaterial3d<UnlitMaterial>>,
+) {
+    for (switch_entity, switch_children) in &q_new_switch {
+        commands
+            .entity(switch_entity)
+            .insert((RigidBody::Static, SignalDebounce::default()))
+            .observe(master_switch_direct_signal);
+
+        for switch_child in switch_children.iter() {
+            if let Ok(material_handle) = q_unlit_objects.get(switch_child) {
+                make_child_animatable(
+                    &mut commands,
+                    switch_child,
+                    material_handle,
+                    &mut unlit_materials,
+                );
+                commands
+                    .entity(switch_child)
+                    .insert((
+                        CollisionLayers::new(
+                            GameLayer::Device,
+                            [GameLayer::Player, GameLayer::Signal, GameLayer::Device],
+                        ),
+                        CollisionEventsEnabled,
+                    ))
+                    .observe(default_signal_collisions);
+            }
+        }
+    }
+}
+
+// Takes Powered off everything at once. Whatever reacts to losing power (OnRemove observers,
+// door poles, spitters) animates down the same as if it had run out on its own. Cubes normally
+// keep their charge, so they're only drained when asked to.
+pub fn depower_all(
+    commands: &mut Commands,
+    q_powered: &Query<Entity, With<Powered>>,
+    q_cubes: &Query<(), With<WeightedCube>>,
+    include_cubes: bool,
+) {
+    for powered_entity in q_powered {
+        if !include_cubes && q_cubes.contains(powered_entity) {
+            continue;
+        }
+
+        commands.entity(powered_entity).try_remove::<Powered>();
+    }
+}
+
+fn master_switch_direct_signal(
+    trigger: Trigger<DirectSignal>,
+    mut commands: Commands,
+    q_switch: Query<(&MasterSwitch, &Children)>,
+    q_powered: Query<Entity, With<Powered>>,
+    q_cubes: Query<(), With<WeightedCube>>,
+    q_unlit_objects: Query<&MeshMaterial3d<UnlitMaterial>>,
+    mut unlit_materials: ResMut<Assets<UnlitMaterial>>,
+) {
+    if let Ok((master_switch, switch_children)) = q_switch.get(trigger.target()) {
+        flash_device(
+            &mut commands,
+            switch_children,
+            &q_unlit_objects,
+            &mut unlit_materials,
+        );
+
+        depower_all(
+            &mut commands,
+            &q_powered,
+            &q_cubes,
+            master_switch.depower_cubes,
+        );
+    }
+}
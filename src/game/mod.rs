@@ -1,52 +1,85 @@
 use avian3d::{
-    prelude::{Collider, Gravity, PhysicsLayer},
+    prelude::{Collider, PhysicsLayer},
     PhysicsPlugins,
 };
 use bevy::prelude::*;
 use bevy_tween::{bevy_time_runner::TimeRunnerEnded, TweenSystemSet};
 use button::button_plugin;
+use conduit::conduit_plugin;
 use cube_spitter::cube_spitter_plugin;
 use dissolve_gate::dissolve_gate_plugin;
 use door::door_plugin;
+use force_field::force_field_plugin;
+use gravity::gravity_plugin;
+use hold_button::hold_button_plugin;
 use inert::inert_plugin;
 use input::input_plugin;
 use interaction::interaction_plugin;
+use level_timer::level_timer_plugin;
+use master_switch::master_switch_plugin;
 use player::player_plugin;
+use power_graph::power_graph_plugin;
 use pressure_plate::pressure_plate_plugin;
+use signal_relay::signal_relay_plugin;
 use signal_spitter::signal_spitter_plugin;
 use signals::signals_plugin;
 use standing_cube_spitter::standing_cube_spitter_plugin;
+use teleport_gate::teleport_gate_plugin;
 use weighted_cube::cube_plugin;
 
 use crate::game::{
-    audio::audio_plugin, discharge_gate::discharge_gate_plugin,
-    signal_preview::signal_preview_plugin,
+    accessibility::accessibility_plugin, audio::audio_plugin,
+    discharge_gate::discharge_gate_plugin, signal_preview::signal_preview_plugin,
 };
 
+pub mod accessibility;
 pub mod audio;
 pub mod button;
+pub mod conduit;
 pub mod cube_spitter;
 pub mod discharge_gate;
 pub mod dissolve_gate;
 pub mod door;
+pub mod force_field;
+pub mod gravity;
+pub mod hold_button;
 pub mod inert;
 pub mod input;
 pub mod interaction;
+pub mod level_timer;
+pub mod master_switch;
 pub mod player;
+pub mod power_graph;
 pub mod pressure_plate;
 pub mod signal_preview;
+pub mod signal_relay;
 pub mod signal_spitter;
 pub mod signals;
 pub mod standing_cube_spitter;
+pub mod teleport_gate;
+#[cfg(test)]
+pub mod test_app;
 pub mod weighted_cube;
 
 pub fn gameplay_plugins(app: &mut App) {
     app.add_plugins((
-        PhysicsPlugins::default(),
-        //PhysicsDebugPlugin::default(),
+        gameplay_logic_plugins,
         player_plugin,
         input_plugin,
         interaction_plugin,
+        signal_preview_plugin,
+        accessibility_plugin,
+        level_timer_plugin,
+    ));
+}
+
+// Devices, power and signals, without the player or anything that needs a window or GPU, so it
+// can run headless on MinimalPlugins. Still expects GameAssets, GameSounds and the material
+// asset types to be around, they just don't have to have anything in them.
+pub fn gameplay_logic_plugins(app: &mut App) {
+    app.add_plugins((
+        PhysicsPlugins::default(),
+        //PhysicsDebugPlugin::default(),
         signals_plugin,
         pressure_plate_plugin,
         dissolve_gate_plugin,
@@ -56,14 +89,20 @@ pub fn gameplay_plugins(app: &mut App) {
         cube_spitter_plugin,
         cube_plugin,
         standing_cube_spitter_plugin,
-    ))
-    .add_plugins((
         button_plugin,
         discharge_gate_plugin,
-        signal_preview_plugin,
         audio_plugin,
     ))
-    .insert_resource(Gravity(Vec3::NEG_Y * 19.6));
+    .add_plugins((
+        conduit_plugin,
+        teleport_gate_plugin,
+        force_field_plugin,
+        signal_relay_plugin,
+        power_graph_plugin,
+        hold_button_plugin,
+        master_switch_plugin,
+        gravity_plugin,
+    ));
 
     app.add_systems(
         PostUpdate,
@@ -1,36 +1,57 @@
-use std::f32::consts::FRAC_PI_2;
+use std::{
+    f32::consts::{FRAC_PI_2, TAU},
+    time::Duration,
+};
 
 use avian3d::{
     math::PI,
     prelude::{
-        Collider, CollisionEventsEnabled, CollisionLayers, LinearVelocity, LockedAxes, RigidBody,
-        RigidBodyColliders, RigidBodyDisabled, ShapeCaster, ShapeHits, SpatialQueryFilter,
+        Collider, ColliderDisabled, CollisionEventsEnabled, CollisionLayers, LinearVelocity,
+        LockedAxes, RigidBody, RigidBodyColliders, RigidBodyDisabled, RotationInterpolation,
+        ShapeCastConfig, ShapeCaster, ShapeHits, SpatialQuery, SpatialQueryFilter,
         TransformInterpolation,
     },
 };
 use bevy::{
-    color::palettes::css::{RED, WHITE},
+    color::palettes::css::{LIME, RED, WHITE},
     ecs::entity_disabling::Disabled,
     prelude::*,
 };
 
 use bevy_enhanced_input::{
+    events::Completed,
     prelude::{ActionValue, Actions},
     EnhancedInputSystem,
 };
 use bevy_tnua::prelude::*;
 use bevy_tnua_avian3d::*;
+use bevy_tween::{
+    bevy_time_runner::TimeSpan,
+    combinator::tween,
+    prelude::{AnimationBuilderExt, EaseKind},
+    tween::TargetAsset,
+};
 
 use crate::{
-    rendering::{section_color_prepass::DrawSection, unlit_material::UnlitMaterial},
+    asset_management::asset_loading::NoAutoCollider,
+    rendering::{
+        section_color_prepass::DrawSection,
+        unlit_material::{MaterialAlphaInterpolator, UnlitMaterial},
+    },
     ui::crosshair::CrosshairState,
     GameState, MainCamera,
 };
 
 use super::{
+    accessibility::AccessibilitySettings,
     dissolve_gate::handle_dissolve_collisions,
-    input::{FixedInputContext, Jump, Look, Movement, UpdateInputContext},
+    gravity::GravitySettings,
+    input::{
+        DropHeld, FixedInputContext, Jump, Look, LookStick, Movement, Sprint, UpdateInputContext,
+    },
     interaction::InteractionsDisabled,
+    signals::{hostile_signal_collisions, PlayerHitBySignal},
+    standing_cube_spitter::Tombstone,
     GameLayer,
 };
 
@@ -47,25 +68,52 @@ pub fn player_plugin(app: &mut App) {
     )
     .add_systems(
         PostUpdate,
-        camera_follow_player
+        (camera_follow_player, apply_headbob, apply_sprint_fov_kick)
+            .chain()
             .after(RunFixedMainLoopSystem::AfterFixedMainLoop)
             .before(TransformSystem::TransformPropagate)
             .run_if(in_state(GameState::Playing)),
     )
     .add_systems(
         FixedUpdate,
-        (move_player, jump).run_if(in_state(GameState::Playing)),
+        (tick_player_push, move_player, jump)
+            .chain()
+            .run_if(in_state(GameState::Playing).and(not_noclipping)),
+    )
+    .add_systems(
+        FixedUpdate,
+        update_player_grounded.after(TnuaPipelineStages::Logic),
     )
     .add_systems(
         PreUpdate, // this is on its own because we are basically guessing where to put it atm
-        project_held_placable_item.run_if(in_state(GameState::Playing)),
+        (
+            project_held_placable_item,
+            auto_drop_obstructed_item,
+            carry_stashed_items,
+        )
+            .chain()
+            .run_if(in_state(GameState::Playing)),
     )
     .add_systems(
         Update,
         (picked_up_item).run_if(in_state(GameState::Playing)),
     )
-    .add_systems(OnEnter(GameState::Playing), spawn_player)
+    // spawned for the main menu so its camera already sits where the level starts
+    .add_systems(OnEnter(GameState::MainMenu), spawn_player)
+    .add_systems(
+        Update,
+        respawn_missing_player
+            .run_if(in_state(GameState::MainMenu).or(in_state(GameState::Playing))),
+    )
     .add_observer(released_item)
+    .add_observer(drop_held_object)
+    .add_observer(start_player_push)
+    .init_resource::<LookSettings>()
+    .init_resource::<CameraSettings>()
+    .init_resource::<PlayerMovementConfig>()
+    .init_resource::<CarryConfig>()
+    .init_resource::<HeldObjectTint>()
+    .init_resource::<NoclipState>()
     .register_type::<PlayerSpawnPoint>()
     .register_type::<RightHand>();
 }
@@ -76,7 +124,68 @@ pub struct Player;
 #[derive(Component, Default, Reflect)]
 #[reflect(Component)]
 pub struct RightHand {
-    pub held_object: Option<Entity>,
+    // everything being carried, up to CarryConfig::capacity. Only the active one is out in front
+    // of the player, the rest are Stashed.
+    pub held_objects: Vec<Entity>,
+    pub active: usize,
+    // whatever the player last put down on purpose, so it can be grabbed back
+    pub last_released: Option<Entity>,
+}
+
+impl RightHand {
+    // the one being placed, if anything is being carried
+    pub fn held_object(&self) -> Option<Entity> {
+        self.held_objects.get(self.active).copied()
+    }
+
+    pub fn is_full(&self, capacity: usize) -> bool {
+        self.held_objects.len() >= capacity.max(1)
+    }
+
+    pub fn set_active(&mut self, entity: Entity) {
+        if let Some(index) = self.held_objects.iter().position(|held| *held == entity) {
+            self.active = index;
+        } else {
+            self.held_objects.push(entity);
+            self.active = self.held_objects.len() - 1;
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.held_objects.clear();
+        self.active = 0;
+    }
+}
+
+// Carried but not the active held object. Hidden and without collisions, and kept on the player
+// so it comes back out from the right place.
+#[derive(Component)]
+pub struct Stashed;
+
+// Puts the active held object away. Taking Held off runs released_item, which sees Stashed and
+// hides the object instead of dropping it.
+pub fn stash_held_object(commands: &mut Commands, held_entity: Entity) {
+    commands
+        .entity(held_entity)
+        .insert(Stashed)
+        .remove::<Held>();
+}
+
+pub fn unstash_held_object(
+    commands: &mut Commands,
+    stashed_entity: Entity,
+    stashed_colliders: &RigidBodyColliders,
+) {
+    for collider_entity in stashed_colliders.iter() {
+        commands
+            .entity(collider_entity)
+            .try_remove::<ColliderDisabled>();
+    }
+
+    commands
+        .entity(stashed_entity)
+        .remove::<Stashed>()
+        .insert((Visibility::Inherited, Held::default()));
 }
 
 #[derive(Component, Reflect)]
@@ -90,37 +199,192 @@ fn spawn_player(
     spawn_point: Single<&Transform, With<PlayerSpawnPoint>>,
     mut camera: Single<&mut Transform, (With<MainCamera>, Without<PlayerSpawnPoint>)>,
 ) {
+    spawn_player_at(&mut commands, **spawn_point, &mut camera);
+}
+
+// Restarting a level despawns the player, and the menu scene's spawn point can show up after
+// we enter MainMenu, so put a fresh one in once a spawn point shows up
+fn respawn_missing_player(
+    mut commands: Commands,
+    q_new_spawn_points: Query<&Transform, Added<PlayerSpawnPoint>>,
+    q_player: Query<(), With<Player>>,
+    mut camera: Single<&mut Transform, (With<MainCamera>, Without<PlayerSpawnPoint>)>,
+) {
+    if !q_player.is_empty() {
+        return;
+    }
+
+    if let Some(spawn_point) = q_new_spawn_points.iter().next() {
+        spawn_player_at(&mut commands, *spawn_point, &mut camera);
+    }
+}
+
+fn spawn_player_at(commands: &mut Commands, spawn_point: Transform, camera: &mut Transform) {
     commands
         .spawn((
-            **spawn_point,
+            spawn_point,
             RigidBody::Dynamic,
-            Collider::capsule(1.5, 8.0),
+            Collider::capsule(PLAYER_RADIUS, 8.0),
             TnuaController::default(), // todo: what options
             TnuaAvian3dSensorShape(Collider::capsule(1.49, 7.99)),
             LockedAxes::ROTATION_LOCKED,
             Player,
+            PlayerGrounded::default(),
             RightHand::default(),
             StateScoped(GameState::Playing),
             TransformInterpolation,
             CollisionLayers::new(
                 GameLayer::Player,
-                [GameLayer::Default, GameLayer::Device, GameLayer::Win],
+                [
+                    GameLayer::Default,
+                    GameLayer::Device,
+                    GameLayer::Win,
+                    GameLayer::Signal,
+                ],
             ),
             CollisionEventsEnabled,
         ))
-        .observe(handle_dissolve_collisions);
+        .observe(handle_dissolve_collisions)
+        .observe(hostile_signal_collisions)
+        .observe(pushed_by_hostile_signal);
 
     // set camera rotation to away from origin.
-    **camera = camera.looking_at(Vec3::ZERO, Vec3::Y);
+    *camera = camera.looking_at(Vec3::ZERO, Vec3::Y);
     camera.rotate_y(PI);
 }
 
+// Only ever turned on by the dev noclip toggle. While it's on, the player's body is disabled and
+// something else moves the player around, so the normal movement stays out of the way.
+#[derive(Resource, Default)]
+pub struct NoclipState {
+    pub active: bool,
+}
+
+fn not_noclipping(noclip_state: Res<NoclipState>) -> bool {
+    !noclip_state.active
+}
+
+// Whether the player is standing on something, for anything that cares (footsteps, landing...).
+// Tnua is the source of truth, this is just its walk basis read back once it has run for the
+// tick. Jumping counts as airborne from the moment the jump starts, even before leaving the
+// ground, and noclip always counts as airborne.
+#[derive(Component, Default, PartialEq)]
+pub struct PlayerGrounded(pub bool);
+
+fn update_player_grounded(
+    mut q_player: Query<(&TnuaController, &mut PlayerGrounded), With<Player>>,
+    noclip_state: Res<NoclipState>,
+) {
+    for (controller, mut grounded) in &mut q_player {
+        // no basis yet means move_player hasn't run, so nothing is holding the player up either
+        let is_grounded =
+            !noclip_state.active && controller.is_airborne().is_ok_and(|airborne| !airborne);
+        grounded.set_if_neq(PlayerGrounded(is_grounded));
+    }
+}
+
 const PLAYER_VELOCITY: f32 = 30.0;
+const PLAYER_ACCELERATION: f32 = 120.;
+
+#[derive(Resource)]
+pub struct PlayerMovementConfig {
+    pub walk_speed: f32,
+    pub sprint_multiplier: f32,
+    // however heavy the held object is, the player keeps at least this fraction of their speed
+    pub min_carry_speed_factor: f32,
+}
+
+impl Default for PlayerMovementConfig {
+    fn default() -> Self {
+        Self {
+            walk_speed: PLAYER_VELOCITY,
+            sprint_multiplier: 1.6,
+            min_carry_speed_factor: 0.4,
+        }
+    }
+}
+
+// How much holding this slows the player down, as a fraction of their speed
+#[derive(Component)]
+pub struct CarryWeight {
+    pub factor: f32,
+}
+
+pub const LIGHT_CARRY_WEIGHT: f32 = 0.1;
+pub const HEAVY_CARRY_WEIGHT: f32 = 0.35;
+
+// Shoves the player for a moment. Tnua owns the player's velocity, so rather than an impulse
+// this gets added to the walk basis' desired velocity until it wears off.
+#[derive(Event)]
+pub struct PlayerPush(pub Vec3);
+
+const PLAYER_PUSH_DURATION_SEC: f32 = 0.3;
+// fast enough to be felt, slow enough that a single physics step can't carry the player
+// through a wall
+const MAX_PLAYER_PUSH_SPEED: f32 = 80.;
+const HOSTILE_SIGNAL_PUSH_SPEED: f32 = 60.;
+
+#[derive(Component)]
+struct ActivePush {
+    velocity: Vec3,
+    timer: Timer,
+}
+
+impl ActivePush {
+    // eases off linearly over the push window
+    fn current_velocity(&self) -> Vec3 {
+        self.velocity * (1.0 - self.timer.fraction())
+    }
+}
+
+fn start_player_push(
+    trigger: Trigger<PlayerPush>,
+    mut commands: Commands,
+    q_player: Query<Entity, With<Player>>,
+) {
+    // the walk basis only steers horizontally, jumping is its own thing
+    let velocity = trigger
+        .event()
+        .0
+        .with_y(0.0)
+        .clamp_length_max(MAX_PLAYER_PUSH_SPEED);
+
+    for player in &q_player {
+        commands.entity(player).try_insert(ActivePush {
+            velocity,
+            timer: Timer::from_seconds(PLAYER_PUSH_DURATION_SEC, TimerMode::Once),
+        });
+    }
+}
+
+fn pushed_by_hostile_signal(trigger: Trigger<PlayerHitBySignal>, mut commands: Commands) {
+    commands.trigger(PlayerPush(
+        trigger.event().direction * HOSTILE_SIGNAL_PUSH_SPEED,
+    ));
+}
+
+fn tick_player_push(
+    mut commands: Commands,
+    mut q_push: Query<(Entity, &mut ActivePush)>,
+    time: Res<Time>,
+) {
+    for (player, mut push) in &mut q_push {
+        push.timer.tick(time.delta());
+        if push.timer.finished() {
+            commands.entity(player).remove::<ActivePush>();
+        }
+    }
+}
 
 fn move_player(
     mut controller: Single<&mut TnuaController>,
     input: Single<&Actions<FixedInputContext>>,
     camera: Single<&Transform, With<MainCamera>>,
+    movement_config: Res<PlayerMovementConfig>,
+    q_push: Query<&ActivePush, With<Player>>,
+    right_hand: Single<&RightHand>,
+    q_carry_weight: Query<&CarryWeight>,
+    gravity_settings: Res<GravitySettings>,
 ) {
     if let Ok(ActionValue::Axis2D(movement)) = input.value::<Movement>() {
         let camera_forward = camera.forward();
@@ -131,25 +395,59 @@ fn move_player(
 
         let direction = forward_horizontal * movement.y + right_horizontal * movement.x;
 
+        let is_sprinting = matches!(input.value::<Sprint>(), Ok(ActionValue::Bool(true)));
+        let speed_multiplier = if is_sprinting {
+            movement_config.sprint_multiplier
+        } else {
+            1.0
+        };
+
+        // stashed things weigh the player down too
+        let carry_weight: f32 = right_hand
+            .held_objects
+            .iter()
+            .filter_map(|held_object| q_carry_weight.get(*held_object).ok())
+            .map(|carry_weight| carry_weight.factor)
+            .sum();
+        let carry_multiplier = (1.0 - carry_weight).max(movement_config.min_carry_speed_factor);
+
+        let push_velocity = q_push
+            .iter()
+            .map(ActivePush::current_velocity)
+            .sum::<Vec3>();
+
         controller.basis(TnuaBuiltinWalk {
-            desired_velocity: direction * PLAYER_VELOCITY,
+            desired_velocity: direction
+                * movement_config.walk_speed
+                * speed_multiplier
+                * carry_multiplier
+                + push_velocity,
             float_height: 4.0,
             max_slope: FRAC_PI_2,
-            acceleration: 120.,
-            air_acceleration: 120.,
-            free_fall_extra_gravity: 100.,
+            // scale acceleration too, so reaching sprint speed takes as long as reaching walk speed
+            acceleration: PLAYER_ACCELERATION * speed_multiplier,
+            air_acceleration: PLAYER_ACCELERATION * speed_multiplier,
+            free_fall_extra_gravity: 100. * gravity_settings.scale(),
             ..default()
         });
     }
 }
 
-fn jump(mut controller: Single<&mut TnuaController>, input: Single<&Actions<FixedInputContext>>) {
+// The extra gravities were tuned against normal gravity, so they get scaled along with it. Tnua
+// works out the takeoff speed from the height, so the jump reaches the same height at any gravity
+// and low gravity just makes it floatier.
+fn jump(
+    mut controller: Single<&mut TnuaController>,
+    input: Single<&Actions<FixedInputContext>>,
+    gravity_settings: Res<GravitySettings>,
+) {
     if let Ok(ActionValue::Bool(jump)) = input.value::<Jump>() {
         if jump {
+            let gravity_scale = gravity_settings.scale();
             controller.action(TnuaBuiltinJump {
                 height: 8.0,
-                takeoff_extra_gravity: 120.,
-                fall_extra_gravity: 60.,
+                takeoff_extra_gravity: 120. * gravity_scale,
+                fall_extra_gravity: 60. * gravity_scale,
                 shorten_extra_gravity: 0.0,
                 ..default()
             });
@@ -158,64 +456,359 @@ fn jump(mut controller: Single<&mut TnuaController>, input: Single<&Actions<Fixe
 }
 
 const MAX_PITCH: f32 = 89.0_f32.to_radians(); // Limit vertical look angle
-const SENSITIVITY: f32 = 0.1;
+
+#[derive(Resource)]
+pub struct LookSettings {
+    // Radians per pixel of mouse motion. Mouse motion is already a per-frame delta, so it is never
+    // scaled by frame time: the same physical movement turns the camera the same amount at any
+    // framerate. (the default matches the old `0.1 * delta_secs` feel at 60fps)
+    pub mouse_sensitivity: f32,
+    // Radians per second at full stick deflection
+    pub stick_sensitivity: f32,
+    pub invert_y: bool,
+    // Vertical field of view in radians when not sprinting
+    pub fov: f32,
+}
+
+impl Default for LookSettings {
+    fn default() -> Self {
+        Self {
+            mouse_sensitivity: 0.0017,
+            stick_sensitivity: 3.0,
+            invert_y: false,
+            fov: BASE_FOV,
+        }
+    }
+}
 
 fn rotate_camera(
     input: Single<&Actions<UpdateInputContext>>,
     mut camera: Single<&mut Transform, With<MainCamera>>,
+    look_settings: Res<LookSettings>,
     time: Res<Time>,
 ) {
-    if let Ok(ActionValue::Axis2D(look)) = input.value::<Look>() {
-        let scaled_sensitivity = SENSITIVITY * time.delta_secs();
+    let mut look_delta = Vec2::ZERO;
 
-        camera.rotate_y(-look.x * scaled_sensitivity);
+    if let Ok(ActionValue::Axis2D(look)) = input.value::<Look>() {
+        look_delta += look * look_settings.mouse_sensitivity;
+    }
 
-        let current_pitch = camera.rotation.to_euler(EulerRot::YXZ).1;
-        let new_pitch = (current_pitch - look.y * scaled_sensitivity).clamp(-MAX_PITCH, MAX_PITCH);
-        let pitch_delta = new_pitch - current_pitch;
+    // the stick is a rate, so unlike the mouse it does scale with frame time
+    // (stick up is +y, where mouse up is -y)
+    if let Ok(ActionValue::Axis2D(look_stick)) = input.value::<LookStick>() {
+        look_delta += Vec2::new(look_stick.x, -look_stick.y)
+            * look_settings.stick_sensitivity
+            * time.delta_secs();
+    }
 
-        camera.rotate_local_x(pitch_delta);
+    if look_settings.invert_y {
+        look_delta.y = -look_delta.y;
     }
+
+    apply_look_rotation(&mut camera, look_delta);
+}
+
+// Rotates by `delta` radians of (yaw, pitch), clamping pitch
+fn apply_look_rotation(camera: &mut Transform, delta: Vec2) {
+    camera.rotate_y(-delta.x);
+
+    let current_pitch = camera.rotation.to_euler(EulerRot::YXZ).1;
+    let new_pitch = (current_pitch - delta.y).clamp(-MAX_PITCH, MAX_PITCH);
+    let pitch_delta = new_pitch - current_pitch;
+
+    camera.rotate_local_x(pitch_delta);
 }
 
 const CAMERA_HEIGHT: f32 = 4.0;
+const PLAYER_RADIUS: f32 = 1.5;
+// from the middle of the player capsule (8 long, plus the caps) down to the bottom of it
+const PLAYER_HALF_HEIGHT: f32 = 4.0 + PLAYER_RADIUS;
+// extra gap between the player capsule and a held object's bounding sphere
+const HELD_OBJECT_CLEARANCE: f32 = 0.5;
+// past this the player was teleported or respawned, and gliding over there would look silly
+const CAMERA_SNAP_DISTANCE: f32 = 20.0;
+
+// How quickly the camera catches up to the player, per second. 0 snaps it right onto the player
+// every frame, which is the default. Smoothing hides stair steps and float height changes but
+// trails the player, so the view (and held object placement, which is aimed from the camera)
+// lags a little behind where the body actually is.
+#[derive(Resource, Default)]
+pub struct CameraSettings {
+    pub vertical_smoothing: f32,
+    pub horizontal_smoothing: f32,
+}
+
 fn camera_follow_player(
+    camera_settings: Res<CameraSettings>,
     maybe_player: Option<Single<(&Transform, Has<Disabled>), With<Player>>>,
     mut camera: Single<&mut Transform, (With<MainCamera>, Without<Player>)>,
+    // headbob gets added onto the camera afterwards, so keep our own copy to ease from
+    mut follow_position: Local<Option<Vec3>>,
+    time: Res<Time>,
 ) {
     if let Some(player_single) = maybe_player {
         let (player_transform, _is_disabled) = player_single.into_inner();
-        camera.translation = player_transform
+        let target = player_transform
             .translation
             .with_y(player_transform.translation.y + CAMERA_HEIGHT);
+
+        let position = match *follow_position {
+            Some(previous) if previous.distance(target) < CAMERA_SNAP_DISTANCE => {
+                let delta_secs = time.delta_secs();
+                let horizontal = previous.xz().lerp(
+                    target.xz(),
+                    follow_ease(camera_settings.horizontal_smoothing, delta_secs),
+                );
+                let vertical = previous.y.lerp(
+                    target.y,
+                    follow_ease(camera_settings.vertical_smoothing, delta_secs),
+                );
+                Vec3::new(horizontal.x, vertical, horizontal.y)
+            }
+            _ => target,
+        };
+
+        *follow_position = Some(position);
+        camera.translation = position;
+    }
+}
+
+fn follow_ease(smoothing: f32, delta_secs: f32) -> f32 {
+    if smoothing <= 0.0 {
+        return 1.0;
+    }
+
+    1.0 - (-smoothing * delta_secs).exp()
+}
+
+pub const BASE_FOV: f32 = 1.396;
+// extra radians of fov at full sprint, and how quickly it eases in and out
+const SPRINT_FOV_KICK: f32 = 0.1;
+const SPRINT_FOV_EASE_RATE: f32 = 8.0;
+// close enough to the target fov to stop easing and leave the projection alone
+const FOV_SETTLE_EPSILON: f32 = 0.0001;
+
+#[derive(Component, Default)]
+pub struct HeadbobState {
+    pub phase: f32,
+}
+
+const HEADBOB_FREQUENCY: f32 = 0.4; // radians of phase per unit of horizontal distance travelled
+const HEADBOB_VERTICAL_AMPLITUDE: f32 = 0.25;
+const HEADBOB_HORIZONTAL_AMPLITUDE: f32 = 0.12;
+
+// Runs after camera_follow_player has reset the camera translation, so the offset never accumulates.
+// Only translation is touched, leaving rotate_camera's pitch clamping alone.
+fn apply_headbob(
+    accessibility_settings: Res<AccessibilitySettings>,
+    maybe_player: Option<Single<&LinearVelocity, With<Player>>>,
+    camera: Single<(&mut Transform, &mut HeadbobState), (With<MainCamera>, Without<Player>)>,
+    movement_config: Res<PlayerMovementConfig>,
+    time: Res<Time>,
+) {
+    let (mut camera_transform, mut headbob) = camera.into_inner();
+
+    if !accessibility_settings.headbob_active() {
+        headbob.phase = 0.0;
+        return;
+    }
+
+    let Some(player_velocity) = maybe_player else {
+        return;
+    };
+
+    let horizontal_speed = player_velocity.0.with_y(0.0).length();
+    headbob.phase =
+        (headbob.phase + horizontal_speed * HEADBOB_FREQUENCY * time.delta_secs()) % TAU;
+
+    let speed_factor = (horizontal_speed / movement_config.walk_speed).min(1.5);
+    let vertical_offset = (headbob.phase * 2.0).sin() * HEADBOB_VERTICAL_AMPLITUDE * speed_factor;
+    let horizontal_offset = headbob.phase.sin() * HEADBOB_HORIZONTAL_AMPLITUDE * speed_factor;
+
+    let camera_right = camera_transform.right().with_y(0.0).normalize_or_zero();
+    camera_transform.translation += Vec3::Y * vertical_offset + camera_right * horizontal_offset;
+}
+
+// Widens the view a bit while sprinting. The outlines are drawn a fixed number of pixels wide in
+// screen space, so they don't need to know about the fov changing.
+fn apply_sprint_fov_kick(
+    accessibility_settings: Res<AccessibilitySettings>,
+    noclip_state: Res<NoclipState>,
+    input: Single<&Actions<FixedInputContext>>,
+    look_settings: Res<LookSettings>,
+    mut projection: Single<&mut Projection, With<MainCamera>>,
+    time: Res<Time>,
+) {
+    let Projection::Perspective(perspective) = projection.as_ref() else {
+        return;
+    };
+
+    let is_moving = matches!(
+        input.value::<Movement>(),
+        Ok(ActionValue::Axis2D(movement)) if movement != Vec2::ZERO
+    );
+    let is_sprinting = matches!(input.value::<Sprint>(), Ok(ActionValue::Bool(true)));
+
+    let target_fov = if is_moving
+        && is_sprinting
+        && !noclip_state.active
+        && !accessibility_settings.reduced_motion
+    {
+        look_settings.fov + SPRINT_FOV_KICK
+    } else {
+        look_settings.fov
+    };
+
+    // a Projection change has the camera rebuild its frustum, so don't touch it while settled
+    if (perspective.fov - target_fov).abs() < FOV_SETTLE_EPSILON {
+        return;
+    }
+
+    let ease = 1.0 - (-SPRINT_FOV_EASE_RATE * time.delta_secs()).exp();
+    let mut fov = perspective.fov.lerp(target_fov, ease);
+    if (fov - target_fov).abs() < FOV_SETTLE_EPSILON {
+        fov = target_fov;
+    }
+
+    if let Projection::Perspective(perspective) = projection.as_mut() {
+        perspective.fov = fov;
     }
 }
 
 #[derive(Component, Default)]
 pub struct Held {
     pub can_release: bool,
+    // bounding sphere of the held object, used to keep it out of the player
+    pub radius: f32,
+    // how far the collider reaches from the body's origin along each axis, with y measured
+    // down to the bottom face since that's the side that gets set on things
+    pub extents: Vec3,
+    // how long it's been since the object last had somewhere valid to go
+    pub obstructed_secs: f32,
+}
+
+#[derive(Resource)]
+pub struct CarryConfig {
+    // furthest surface a held object will snap to, checked every frame while it's carried
+    pub max_carry_distance: f32,
+    // where the object hovers when there's no surface in range
+    pub hover_distance: f32,
+    // opt-in: drop the object at the player's feet once it's been unplaceable for a while
+    pub auto_drop: bool,
+    pub auto_drop_after_secs: f32,
+    // opt-in: hide the real object while it's held and show a see-through copy where it'll go
+    pub ghost_preview: bool,
+    // how many things can be carried at once. Anything past the first has to be light, and only
+    // one is out at a time, the rest get cycled through.
+    pub capacity: usize,
+}
+
+const GHOST_ALPHA: f32 = 0.4;
+const HELD_ALPHA: f32 = 0.75;
+const HELD_FADE_DURATION_SEC: f32 = 0.2;
+
+// Stand-in for a held object while CarryConfig::ghost_preview is on. The real object is hidden
+// and stays parked where it was, only the ghost follows the projected placement. Putting it
+// down moves the object to the ghost, anything else that takes it away (dropping it, a dissolve
+// gate...) puts it wherever it needs to go itself.
+#[derive(Component)]
+pub struct HeldGhost {
+    pub held: Entity,
+    pub material: Handle<UnlitMaterial>,
+}
+
+impl Default for CarryConfig {
+    fn default() -> Self {
+        Self {
+            max_carry_distance: 50.0,
+            hover_distance: 20.0,
+            auto_drop: false,
+            auto_drop_after_secs: 3.0,
+            ghost_preview: false,
+            capacity: 1,
+        }
+    }
+}
+
+// Colors a held object gets to say whether it can be put down where it is
+#[derive(Resource)]
+pub struct HeldObjectTint {
+    // None leaves the object untinted on a valid spot (the ghost preview falls back to lime)
+    pub valid_color: Option<Color>,
+    pub invalid_color: Color,
+    pub blend_factor: f32,
+}
+
+impl Default for HeldObjectTint {
+    fn default() -> Self {
+        Self {
+            valid_color: None,
+            invalid_color: RED.into(),
+            blend_factor: 0.8,
+        }
+    }
+}
+
+impl HeldObjectTint {
+    fn apply(&self, material: &mut UnlitMaterial, valid: bool) {
+        match (valid, self.valid_color) {
+            (true, None) => {
+                material.extension.params.blend_color = WHITE.into();
+                material.extension.params.blend_factor = 0.0;
+            }
+            (true, Some(valid_color)) => {
+                material.extension.params.blend_color = valid_color.into();
+                material.extension.params.blend_factor = self.blend_factor;
+            }
+            (false, _) => {
+                material.extension.params.blend_color = self.invalid_color.into();
+                material.extension.params.blend_factor = self.blend_factor;
+            }
+        }
+    }
 }
 
 fn picked_up_item(
     mut commands: Commands,
-    mut q_picked_up: Query<(Entity, &RigidBodyColliders, &mut LinearVelocity), Added<Held>>,
+    mut q_picked_up: Query<
+        (Entity, &RigidBodyColliders, &mut LinearVelocity, &mut Held),
+        Added<Held>,
+    >,
     mut q_collider_materials: Query<(Entity, &MeshMaterial3d<UnlitMaterial>, &Collider)>,
+    q_meshes: Query<&Mesh3d>,
     mut unlit_materials: ResMut<Assets<UnlitMaterial>>,
     mut transforms: Query<&mut Transform>,
     mut player: Single<(Entity, &mut RightHand), With<Player>>,
+    carry_config: Res<CarryConfig>,
+    held_object_tint: Res<HeldObjectTint>,
+    q_tween: Query<(), With<TimeSpan>>,
+    q_children: Query<&Children>,
 ) {
-    for (picked_up_body, picked_up_colliders, mut linear_velocity) in q_picked_up.iter_mut() {
+    for (picked_up_body, picked_up_colliders, mut linear_velocity, mut held) in
+        q_picked_up.iter_mut()
+    {
         let mut last_collider: Collider = Collider::sphere(1.0);
+        let mut last_collider_offset = Vec3::ZERO;
 
         for collider_entity in picked_up_colliders.iter() {
             if let Ok((picked_up_collider, material, collider)) =
                 q_collider_materials.get_mut(collider_entity)
             {
-                let material_to_update = unlit_materials.get_mut(material).unwrap();
-                material_to_update.extension.params.alpha = 0.75;
-                material_to_update.extension.params.blend_color = RED.into();
-                material_to_update.extension.params.blend_factor = 0.8;
-                material_to_update.base.alpha_mode = AlphaMode::Opaque;
+                // no materials to fade without a renderer
+                if let Some(material_to_update) = unlit_materials.get_mut(material) {
+                    held_object_tint.apply(material_to_update, false);
+                    let current_alpha = material_to_update.extension.params.alpha;
+
+                    fade_held_material(
+                        &mut commands,
+                        picked_up_collider,
+                        material,
+                        current_alpha,
+                        HELD_ALPHA,
+                        &q_tween,
+                        &q_children,
+                    );
+                }
 
                 commands
                     .entity(picked_up_collider)
@@ -228,9 +821,22 @@ fn picked_up_item(
                     .insert(Pickable::IGNORE);
 
                 last_collider = collider.clone();
+                last_collider_offset = transforms
+                    .get(picked_up_collider)
+                    .map_or(Vec3::ZERO, |collider_transform| {
+                        collider_transform.translation
+                    });
             }
         }
 
+        let aabb = last_collider.aabb(Vec3::ZERO, Quat::IDENTITY);
+        held.radius = (aabb.max - aabb.min).length() / 2.;
+
+        // the collider usually sits on a child, so measure from the body it's attached to
+        let body_aabb = last_collider.aabb(last_collider_offset, Quat::IDENTITY);
+        let half_extents = (body_aabb.max - body_aabb.min) / 2.;
+        held.extents = Vec3::new(half_extents.x, -body_aabb.min.y, half_extents.z);
+
         let mut excluded_entities: Vec<Entity> = vec![];
 
         for thing in picked_up_colliders.iter() {
@@ -240,11 +846,23 @@ fn picked_up_item(
         commands.entity(picked_up_body).insert(RigidBodyDisabled);
         linear_velocity.0 = Vec3::ZERO;
 
+        if carry_config.ghost_preview {
+            spawn_held_ghost(
+                &mut commands,
+                picked_up_body,
+                picked_up_colliders,
+                &q_collider_materials,
+                &q_meshes,
+                &transforms,
+                &mut unlit_materials,
+            );
+        }
+
         if let Ok(mut body_transform) = transforms.get_mut(picked_up_body) {
             body_transform.rotation = Quat::IDENTITY;
         }
 
-        player.1.held_object = Some(picked_up_body);
+        player.1.set_active(picked_up_body);
         commands.entity(player.0).insert(
             ShapeCaster::new(
                 last_collider,
@@ -252,7 +870,7 @@ fn picked_up_item(
                 Quat::IDENTITY, // We force shape to identity rot above
                 Dir3::X,        // Will be updated each frame
             )
-            .with_max_distance(50.0)
+            .with_max_distance(carry_config.max_carry_distance)
             .with_query_filter(
                 SpatialQueryFilter::default()
                     .with_mask([GameLayer::Default, GameLayer::Device])
@@ -263,22 +881,148 @@ fn picked_up_item(
     }
 }
 
+fn spawn_held_ghost(
+    commands: &mut Commands,
+    held_body: Entity,
+    held_colliders: &RigidBodyColliders,
+    q_collider_materials: &Query<(Entity, &MeshMaterial3d<UnlitMaterial>, &Collider)>,
+    q_meshes: &Query<&Mesh3d>,
+    transforms: &Query<&mut Transform>,
+    unlit_materials: &mut Assets<UnlitMaterial>,
+) {
+    let Some(mut ghost_material) = held_colliders
+        .iter()
+        .find_map(|collider_entity| q_collider_materials.get(collider_entity).ok())
+        .and_then(|(_, material, _)| unlit_materials.get(material).cloned())
+    else {
+        return;
+    };
+
+    ghost_material.base.alpha_mode = AlphaMode::Blend;
+    ghost_material.extension.params.alpha = GHOST_ALPHA;
+    let ghost_material = unlit_materials.add(ghost_material);
+
+    let held_transform = transforms.get(held_body).copied().unwrap_or_default();
+
+    commands
+        .entity(held_body)
+        .insert(Visibility::Hidden)
+        .remove::<DrawSection>();
+
+    commands
+        .spawn((
+            held_transform,
+            Visibility::default(),
+            HeldGhost {
+                held: held_body,
+                material: ghost_material.clone(),
+            },
+            StateScoped(GameState::Playing),
+        ))
+        .with_children(|ghost| {
+            for collider_entity in held_colliders.iter() {
+                if let Ok(mesh) = q_meshes.get(collider_entity) {
+                    ghost.spawn((
+                        mesh.clone(),
+                        MeshMaterial3d(ghost_material.clone()),
+                        transforms.get(collider_entity).copied().unwrap_or_default(),
+                        NoAutoCollider,
+                        Pickable::IGNORE,
+                    ));
+                }
+            }
+        });
+}
+
+fn fade_held_material(
+    commands: &mut Commands,
+    collider_entity: Entity,
+    material_handle: &MeshMaterial3d<UnlitMaterial>,
+    start: f32,
+    end: f32,
+    q_tween: &Query<(), With<TimeSpan>>,
+    q_children: &Query<&Children>,
+) {
+    // Kill a half finished fade from a quick pickup/drop so the two don't fight
+    if let Ok(collider_children) = q_children.get(collider_entity) {
+        for child in collider_children.iter() {
+            if q_tween.contains(child) {
+                commands.entity(child).despawn();
+            }
+        }
+    }
+
+    commands.entity(collider_entity).animation().insert(tween(
+        Duration::from_secs_f32(HELD_FADE_DURATION_SEC),
+        EaseKind::CubicOut,
+        TargetAsset::Asset(material_handle.clone_weak())
+            .with(MaterialAlphaInterpolator { start, end }),
+    ));
+}
+
 fn released_item(
     trigger: Trigger<OnRemove, Held>,
     mut commands: Commands,
     q_releasables: Query<(Entity, &RigidBodyColliders)>,
     q_collider_materials: Query<(Entity, &MeshMaterial3d<UnlitMaterial>)>,
+    q_ghosts: Query<(Entity, &HeldGhost)>,
     mut unlit_materials: ResMut<Assets<UnlitMaterial>>,
     mut player: Single<(Entity, &mut RightHand), With<Player>>,
+    q_tombstones: Query<(), With<Tombstone>>,
+    q_tween: Query<(), With<TimeSpan>>,
+    q_children: Query<&Children>,
+    q_stashed: Query<(), With<Stashed>>,
 ) {
+    for (ghost_entity, ghost) in &q_ghosts {
+        if ghost.held == trigger.target() {
+            commands.entity(ghost_entity).despawn();
+            commands
+                .entity(ghost.held)
+                .try_insert(Visibility::Inherited);
+        }
+    }
+
+    // put away rather than let go, so it stays disabled and just disappears
+    if q_stashed.contains(trigger.target()) {
+        if let Ok((stashed_entity, stashed_colliders)) = q_releasables.get(trigger.target()) {
+            for collider_entity in stashed_colliders.iter() {
+                commands
+                    .entity(collider_entity)
+                    .try_insert(ColliderDisabled);
+            }
+            commands
+                .entity(stashed_entity)
+                .try_insert(Visibility::Hidden);
+        }
+
+        commands
+            .entity(player.0)
+            .remove::<ShapeCaster>()
+            .remove::<ShapeHits>();
+        return;
+    }
+
     if let Ok((releasable_entity, releasable_colliders)) = q_releasables.get(trigger.target()) {
         for collider_entity in releasable_colliders.iter() {
             if let Ok((collider_entity, material)) = q_collider_materials.get(collider_entity) {
-                let material_to_update = unlit_materials.get_mut(material).unwrap();
-                material_to_update.extension.params.alpha = 1.0;
-                material_to_update.extension.params.blend_color = WHITE.into();
-                material_to_update.extension.params.blend_factor = 0.0;
-                material_to_update.base.alpha_mode = AlphaMode::Opaque;
+                if let Some(material_to_update) = unlit_materials.get_mut(material) {
+                    material_to_update.extension.params.blend_color = WHITE.into();
+                    material_to_update.extension.params.blend_factor = 0.0;
+
+                    // dissolved objects get released on their way out, and there's nothing left to tween
+                    if !q_tombstones.contains(releasable_entity) {
+                        let current_alpha = material_to_update.extension.params.alpha;
+                        fade_held_material(
+                            &mut commands,
+                            collider_entity,
+                            material,
+                            current_alpha,
+                            1.0,
+                            &q_tween,
+                            &q_children,
+                        );
+                    }
+                }
 
                 commands
                     .entity(collider_entity)
@@ -300,7 +1044,10 @@ fn released_item(
             }
         }
 
-        player.1.held_object = None;
+        let right_hand = &mut player.1;
+        right_hand
+            .held_objects
+            .retain(|held_object| *held_object != releasable_entity);
         commands
             .entity(player.0)
             .remove::<ShapeCaster>()
@@ -308,20 +1055,48 @@ fn released_item(
         commands
             .entity(releasable_entity)
             .try_remove::<RigidBodyDisabled>();
+
+        // bring out whatever was put away most recently
+        if let Some(&next_held) = right_hand.held_objects.last() {
+            right_hand.active = right_hand.held_objects.len() - 1;
+            if let Ok((_, next_colliders)) = q_releasables.get(next_held) {
+                unstash_held_object(&mut commands, next_held, next_colliders);
+            }
+        }
+    }
+}
+
+// Stashed objects ride along inside the player so they come back out from where the player is
+fn carry_stashed_items(
+    player: Single<&Transform, With<Player>>,
+    mut q_stashed: Query<(&mut Transform, &mut LinearVelocity), (With<Stashed>, Without<Player>)>,
+) {
+    for (mut stashed_transform, mut linear_velocity) in &mut q_stashed {
+        stashed_transform.translation = player.translation;
+        linear_velocity.0 = Vec3::ZERO;
     }
 }
 
 fn project_held_placable_item(
     camera: Single<&GlobalTransform, With<MainCamera>>,
     player: Single<(Entity, &RightHand, &Transform), With<Player>>,
-    mut transforms: Query<&mut Transform, (Without<MainCamera>, Without<Player>)>,
+    mut transforms: Query<
+        &mut Transform,
+        (Without<MainCamera>, Without<Player>, Without<HeldGhost>),
+    >,
+    mut q_ghosts: Query<(&HeldGhost, &mut Transform), (Without<MainCamera>, Without<Player>)>,
     mut shape_casters: Query<(&mut ShapeCaster, &ShapeHits), With<Player>>,
     q_material_handles: Query<&MeshMaterial3d<UnlitMaterial>>,
     q_rigid_body_colliders: Query<&RigidBodyColliders>,
+    q_colliders: Query<&Collider>,
     mut q_held: Query<&mut Held>,
     mut unlit_materials: ResMut<Assets<UnlitMaterial>>,
+    carry_config: Res<CarryConfig>,
+    held_object_tint: Res<HeldObjectTint>,
+    spatial_query: SpatialQuery,
+    time: Res<Time>,
 ) {
-    if let Some(held_entity) = player.1.held_object {
+    if let Some(held_entity) = player.1.held_object() {
         if let Ok((mut shape_caster, shape_hits)) = shape_casters.get_mut(player.0) {
             let camera_pos = camera.translation();
             let camera_forward = camera.forward();
@@ -337,56 +1112,98 @@ fn project_held_placable_item(
 
             shape_caster.origin = Vec3::Y * CAMERA_HEIGHT;
             shape_caster.direction = camera_forward;
+            shape_caster.max_distance = carry_config.max_carry_distance;
+
+            // worked out on a copy, since with a ghost the body itself stays parked
+            let Ok(mut held_transform) = transforms.get(held_entity).copied() else {
+                return;
+            };
 
             // Use the first hit from the shape caster
             if let Some(hit) = shape_hits
                 .iter()
                 .min_by(|a, b| a.distance.partial_cmp(&b.distance).unwrap())
             {
-                if let Ok(mut held_transform) = transforms.get_mut(held_entity) {
-                    let camera_pos = camera.translation();
-                    let camera_forward = camera.forward();
-
-                    held_transform.translation = camera_pos + hit.distance * camera_forward;
-                    held_transform.rotation = camera_y_rotation;
-
-                    // Check if surface is flat enough (normal pointing mostly upward)
-                    let is_flat_surface = hit.normal1.y > 0.8; // Adjust threshold as needed
-
-                    if let Ok(rigid_body_colliders) = q_rigid_body_colliders.get(held_entity) {
-                        for collider_entity in rigid_body_colliders.iter() {
-                            if let Ok(handle) = q_material_handles.get(collider_entity) {
-                                if let Some(unlit_material) = unlit_materials.get_mut(handle) {
-                                    if is_flat_surface {
-                                        unlit_material.extension.params.blend_color = WHITE.into();
-                                        unlit_material.extension.params.blend_factor = 0.0;
+                let camera_pos = camera.translation();
+                let camera_forward = camera.forward();
+
+                // Up against a wall the cast distance can be ~0, which would put the object
+                // inside the player. Keep its near face in front of the capsule instead, and
+                // don't let it be dropped there since it'd be poking through the wall.
+                let (held_radius, held_extents) = q_held
+                    .get(held_entity)
+                    .map_or((0.0, Vec3::ZERO), |held| (held.radius, held.extents));
+                let min_distance = PLAYER_RADIUS + held_radius + HELD_OBJECT_CLEARANCE;
+                let too_close = hit.distance < min_distance;
+
+                held_transform.translation =
+                    camera_pos + hit.distance.max(min_distance) * camera_forward;
+                held_transform.rotation = camera_y_rotation;
+
+                // Check if surface is flat enough (normal pointing mostly upward)
+                let mut is_flat_surface = hit.normal1.y > 0.8 && !too_close; // Adjust threshold as needed
+
+                // The cast distance is to wherever the hull first touched, which on the edge
+                // of another cube can leave the object half sunk into it. Sit it flush on
+                // the surface instead.
+                if is_flat_surface {
+                    let normal = hit.normal1;
+                    let extent_along_normal = held_extents.dot(normal.abs());
+                    let height_above_surface =
+                        (held_transform.translation - hit.point1).dot(normal);
+                    held_transform.translation +=
+                        normal * (extent_along_normal - height_above_surface);
+
+                    // extents are measured unrotated, so a tall object turned to face the
+                    // camera can still end up in a low ceiling. Check the real pose.
+                    let placement = held_transform;
+                    let held_shapes: Vec<(Collider, Transform)> = q_rigid_body_colliders
+                        .get(held_entity)
+                        .map(|held_colliders| {
+                            held_colliders
+                                .iter()
+                                .filter_map(|collider_entity| {
+                                    let collider = q_colliders.get(collider_entity).ok()?;
+                                    // colliders on the body itself sit at its origin
+                                    let local_transform = if collider_entity == held_entity {
+                                        Transform::IDENTITY
                                     } else {
-                                        unlit_material.extension.params.blend_color = RED.into();
-                                        unlit_material.extension.params.blend_factor = 0.8;
-                                    }
-                                }
+                                        transforms.get(collider_entity).copied().unwrap_or_default()
+                                    };
+                                    Some((collider.clone(), local_transform))
+                                })
+                                .collect()
+                        })
+                        .unwrap_or_default();
+
+                    is_flat_surface =
+                        held_pose_fits(&spatial_query, &placement, normal, &held_shapes, player.0);
+                }
+
+                if let Ok(rigid_body_colliders) = q_rigid_body_colliders.get(held_entity) {
+                    for collider_entity in rigid_body_colliders.iter() {
+                        if let Ok(handle) = q_material_handles.get(collider_entity) {
+                            if let Some(unlit_material) = unlit_materials.get_mut(handle) {
+                                held_object_tint.apply(unlit_material, is_flat_surface);
                             }
                         }
                     }
+                }
 
-                    if let Ok(mut held) = q_held.get_mut(held_entity) {
-                        held.can_release = is_flat_surface;
-                    }
+                if let Ok(mut held) = q_held.get_mut(held_entity) {
+                    held.can_release = is_flat_surface;
                 }
             } else {
                 // No hit found, place at default distance from camera
-                if let Ok(mut held_transform) = transforms.get_mut(held_entity) {
-                    let default_distance = 20.0;
-                    held_transform.translation = camera_pos + camera_forward * default_distance;
-                    held_transform.rotation = camera_y_rotation; // Apply camera's Y rotation here too
-                }
+                held_transform.translation =
+                    camera_pos + camera_forward * carry_config.hover_distance;
+                held_transform.rotation = camera_y_rotation; // Apply camera's Y rotation here too
 
                 if let Ok(rigid_body_colliders) = q_rigid_body_colliders.get(held_entity) {
                     for collider_entity in rigid_body_colliders.iter() {
                         if let Ok(handle) = q_material_handles.get(collider_entity) {
                             if let Some(unlit_material) = unlit_materials.get_mut(handle) {
-                                unlit_material.extension.params.blend_color = RED.into();
-                                unlit_material.extension.params.blend_factor = 0.8;
+                                held_object_tint.apply(unlit_material, false);
                             }
                         }
                     }
@@ -396,6 +1213,328 @@ fn project_held_placable_item(
                     held.can_release = false;
                 }
             }
+
+            if let Ok(mut held) = q_held.get_mut(held_entity) {
+                if held.can_release {
+                    held.obstructed_secs = 0.0;
+                } else {
+                    held.obstructed_secs += time.delta_secs();
+                }
+            }
+
+            // the ghost takes over showing where the object will go
+            let can_release = q_held.get(held_entity).is_ok_and(|held| held.can_release);
+            let mut moved_ghost = false;
+            for (ghost, mut ghost_transform) in &mut q_ghosts {
+                if ghost.held != held_entity {
+                    continue;
+                }
+
+                *ghost_transform = held_transform;
+                moved_ghost = true;
+
+                if let Some(ghost_material) = unlit_materials.get_mut(&ghost.material) {
+                    let ghost_color = if can_release {
+                        held_object_tint.valid_color.unwrap_or(LIME.into())
+                    } else {
+                        held_object_tint.invalid_color
+                    };
+                    ghost_material.extension.params.blend_color = ghost_color.into();
+                    ghost_material.extension.params.blend_factor = held_object_tint.blend_factor;
+                }
+            }
+
+            if !moved_ghost {
+                if let Ok(mut body_transform) = transforms.get_mut(held_entity) {
+                    *body_transform = held_transform;
+                }
+            }
         }
     }
 }
+
+// shrunk and lifted just enough that sitting on the surface doesn't count as overlapping it
+const PLACEMENT_OVERLAP_SCALE: f32 = 0.95;
+const PLACEMENT_OVERLAP_LIFT: f32 = 0.05;
+const PLACEMENT_SCALE_DETAIL: u32 = 8;
+
+// Whether the held object's colliders, posed exactly where it would be dropped, stay clear of
+// everything around them
+fn held_pose_fits(
+    spatial_query: &SpatialQuery,
+    placement: &Transform,
+    surface_normal: Vec3,
+    held_shapes: &[(Collider, Transform)],
+    player: Entity,
+) -> bool {
+    let filter = SpatialQueryFilter::default()
+        .with_mask([GameLayer::Default, GameLayer::Device])
+        .with_excluded_entities([player]);
+    let lifted =
+        placement.with_translation(placement.translation + surface_normal * PLACEMENT_OVERLAP_LIFT);
+
+    held_shapes.iter().all(|(collider, local_transform)| {
+        let mut shrunk = collider.clone();
+        shrunk.set_scale(
+            local_transform.scale * PLACEMENT_OVERLAP_SCALE,
+            PLACEMENT_SCALE_DETAIL,
+        );
+        let world_transform = lifted.mul_transform(*local_transform);
+
+        spatial_query
+            .shape_intersections(
+                &shrunk,
+                world_transform.translation,
+                world_transform.rotation,
+                &filter,
+            )
+            .is_empty()
+    })
+}
+
+fn auto_drop_obstructed_item(
+    mut commands: Commands,
+    carry_config: Res<CarryConfig>,
+    player: Single<(&mut RightHand, &Transform), With<Player>>,
+    camera: Single<&GlobalTransform, With<MainCamera>>,
+    q_held: Query<&Held>,
+    mut transforms: Query<&mut Transform, (Without<MainCamera>, Without<Player>)>,
+    spatial_query: SpatialQuery,
+) {
+    if !carry_config.auto_drop {
+        return;
+    }
+
+    let (mut right_hand, player_transform) = player.into_inner();
+    let Some(held_entity) = right_hand.held_object() else {
+        return;
+    };
+
+    let Ok(held) = q_held.get(held_entity) else {
+        return;
+    };
+
+    if held.obstructed_secs < carry_config.auto_drop_after_secs {
+        return;
+    }
+
+    drop_in_front_of_player(
+        &mut commands,
+        &spatial_query,
+        &mut right_hand,
+        held,
+        player_transform,
+        &camera,
+        &mut transforms,
+    );
+}
+
+// Sets the held object down just in front of the player and lets it fall, wherever they're
+// looking. If something's in the way, or they're looking straight down, it goes at their feet.
+fn drop_in_front_of_player(
+    commands: &mut Commands,
+    spatial_query: &SpatialQuery,
+    right_hand: &mut RightHand,
+    held: &Held,
+    player_transform: &Transform,
+    camera: &GlobalTransform,
+    transforms: &mut Query<&mut Transform, (Without<MainCamera>, Without<Player>)>,
+) {
+    let Some(held_entity) = right_hand.held_object() else {
+        return;
+    };
+
+    if let Ok(mut held_transform) = transforms.get_mut(held_entity) {
+        let drop_distance = PLAYER_RADIUS + held.radius + HELD_OBJECT_CLEARANCE;
+        // held colliders are on the Ignore layer, so this can't hit the object itself
+        let clear_forward = Dir3::new(camera.forward().with_y(0.0))
+            .ok()
+            .filter(|forward| {
+                spatial_query
+                    .cast_shape(
+                        &Collider::sphere(held.radius),
+                        player_transform.translation,
+                        Quat::IDENTITY,
+                        *forward,
+                        &ShapeCastConfig::from_max_distance(drop_distance),
+                        &SpatialQueryFilter::default()
+                            .with_mask([GameLayer::Default, GameLayer::Device]),
+                    )
+                    .is_none()
+            });
+
+        held_transform.translation = match clear_forward {
+            Some(forward) => player_transform.translation + forward * drop_distance,
+            // the player's origin is the middle of the capsule, so go down to the bottom of it
+            // and sit the object's bottom face there
+            None => {
+                let feet = player_transform.translation - Vec3::Y * PLAYER_HALF_HEIGHT;
+                feet + Vec3::Y * held.extents.y
+            }
+        };
+    }
+
+    commands
+        .entity(held_entity)
+        .remove::<Held>()
+        .insert((RotationInterpolation, TransformInterpolation));
+    right_hand.last_released = Some(held_entity);
+}
+
+// The way out when there's nowhere valid to put something down, skips the flat surface check
+fn drop_held_object(
+    _trigger: Trigger<Completed<DropHeld>>,
+    mut commands: Commands,
+    player: Single<(&mut RightHand, &Transform), With<Player>>,
+    camera: Single<&GlobalTransform, With<MainCamera>>,
+    q_held: Query<&Held>,
+    mut transforms: Query<&mut Transform, (Without<MainCamera>, Without<Player>)>,
+    spatial_query: SpatialQuery,
+) {
+    let (mut right_hand, player_transform) = player.into_inner();
+    let Some(held_entity) = right_hand.held_object() else {
+        return;
+    };
+
+    let Ok(held) = q_held.get(held_entity) else {
+        return;
+    };
+
+    drop_in_front_of_player(
+        &mut commands,
+        &spatial_query,
+        &mut right_hand,
+        held,
+        player_transform,
+        &camera,
+        &mut transforms,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::test_app::{
+        advance_fixed, headless_app, spawn_device, spawn_test_cube, spawn_test_player,
+    };
+    use bevy::ecs::system::RunSystemOnce;
+
+    // test cubes are 2.5 on a side, centered on their origin
+    const TEST_CUBE_HALF_SIZE: f32 = 1.25;
+
+    // Picks up a cube while looking steeply down at the top of another one
+    fn hold_cube_over_another(ghost_preview: bool) -> (App, Entity) {
+        let mut app = headless_app();
+        app.insert_resource(CarryConfig {
+            ghost_preview,
+            ..default()
+        })
+        .init_resource::<HeldObjectTint>()
+        .add_systems(PreUpdate, project_held_placable_item)
+        .add_systems(Update, picked_up_item);
+
+        let player = spawn_test_player(&mut app, Vec3::new(3.0, 6.0, 0.0));
+        app.world_mut().spawn((
+            MainCamera,
+            Transform::from_xyz(3.0, 10.0, 0.0).looking_at(Vec3::Y * TEST_CUBE_HALF_SIZE, Vec3::Y),
+        ));
+
+        let bottom_cube = spawn_test_cube(&mut app, Vec3::ZERO);
+        app.world_mut()
+            .entity_mut(bottom_cube)
+            .insert(RigidBody::Static);
+        let held_cube = spawn_test_cube(&mut app, Vec3::X * -50.0);
+        advance_fixed(&mut app, 2);
+
+        app.world_mut()
+            .get_mut::<RightHand>(player)
+            .unwrap()
+            .set_active(held_cube);
+        app.world_mut()
+            .entity_mut(held_cube)
+            .insert(Held::default());
+        advance_fixed(&mut app, 4);
+
+        (app, held_cube)
+    }
+
+    // How far the bottom of something placed on the bottom cube is off its top
+    fn gap_over_bottom_cube(placed: &Transform) -> f32 {
+        (placed.translation.y - TEST_CUBE_HALF_SIZE) - TEST_CUBE_HALF_SIZE
+    }
+
+    #[test]
+    fn held_cube_sits_flush_on_another_cube() {
+        let (app, held_cube) = hold_cube_over_another(false);
+
+        assert!(app.world().get::<Held>(held_cube).unwrap().can_release);
+        let gap = gap_over_bottom_cube(app.world().get::<Transform>(held_cube).unwrap());
+        assert!(gap.abs() < 0.01, "gap of {gap}");
+    }
+
+    #[test]
+    fn ghost_moves_while_the_held_cube_stays_parked() {
+        let (mut app, held_cube) = hold_cube_over_another(true);
+
+        assert!(app.world().get::<Held>(held_cube).unwrap().can_release);
+        let ghost_transform = *app
+            .world_mut()
+            .query_filtered::<&Transform, With<HeldGhost>>()
+            .single(app.world())
+            .unwrap();
+        let gap = gap_over_bottom_cube(&ghost_transform);
+        assert!(gap.abs() < 0.01, "gap of {gap}");
+
+        // still off where it was picked up
+        let held_transform = app.world().get::<Transform>(held_cube).unwrap();
+        assert!(held_transform.translation.x < -40.0);
+    }
+
+    #[test]
+    fn nowhere_in_front_drops_at_the_players_feet() {
+        let mut app = headless_app();
+        app.insert_resource(CarryConfig {
+            auto_drop: true,
+            auto_drop_after_secs: 0.0,
+            ..default()
+        });
+
+        let player = spawn_test_player(&mut app, Vec3::new(0.0, 6.0, 0.0));
+        let camera_transform = Transform::from_xyz(0.0, 10.0, 0.0).looking_to(Vec3::NEG_X, Vec3::Y);
+        app.world_mut().spawn((
+            MainCamera,
+            camera_transform,
+            GlobalTransform::from(camera_transform),
+        ));
+        // right up against the player, so there's no room to drop it in front
+        spawn_device(&mut app, (), Vec3::new(-3.0, 6.0, 0.0));
+
+        let held_cube = spawn_test_cube(&mut app, Vec3::X * -50.0);
+        app.world_mut().entity_mut(held_cube).insert(Held {
+            radius: 2.0,
+            extents: Vec3::splat(TEST_CUBE_HALF_SIZE),
+            ..default()
+        });
+        app.world_mut()
+            .get_mut::<RightHand>(player)
+            .unwrap()
+            .set_active(held_cube);
+        // the spatial query only sees colliders once they've been through a physics step
+        advance_fixed(&mut app, 2);
+
+        app.world_mut()
+            .run_system_once(auto_drop_obstructed_item)
+            .unwrap();
+
+        assert!(!app.world().entity(held_cube).contains::<Held>());
+        let held_bottom = app
+            .world()
+            .get::<Transform>(held_cube)
+            .unwrap()
+            .translation
+            .y
+            - TEST_CUBE_HALF_SIZE;
+        let player_feet = 6.0 - PLAYER_HALF_HEIGHT;
+        assert!((held_bottom - player_feet).abs() < 0.01);
+    }
+}
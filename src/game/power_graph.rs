@@ -0,0 +1,73 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+
+use super::{
+    button::ButtonTargets, conduit::SignalConduit, door::PowersDoor, pressure_plate::PoweredBy,
+};
+
+pub fn power_graph_plugin(app: &mut App) {
+    app.init_resource::<PowerGraph>()
+        .add_systems(PostUpdate, rebuild_power_graph);
+}
+
+// Who can power what, pulled together from PoweredBy, PowersDoor, ButtonTargets and conduits.
+// Rebuilt every frame and only ever read; the components are still what gameplay runs on.
+#[derive(Resource, Default)]
+pub struct PowerGraph {
+    sources: HashMap<Entity, Vec<Entity>>,
+    targets: HashMap<Entity, Vec<Entity>>,
+}
+
+impl PowerGraph {
+    pub fn sources_of(&self, entity: Entity) -> &[Entity] {
+        self.sources.get(&entity).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn targets_of(&self, entity: Entity) -> &[Entity] {
+        self.targets.get(&entity).map_or(&[], Vec::as_slice)
+    }
+
+    fn clear(&mut self) {
+        self.sources.clear();
+        self.targets.clear();
+    }
+
+    // conduits also leave a PoweredBy behind, so the same link can show up twice
+    fn link(&mut self, source: Entity, target: Entity) {
+        let targets = self.targets.entry(source).or_default();
+        if targets.contains(&target) {
+            return;
+        }
+        targets.push(target);
+        self.sources.entry(target).or_default().push(source);
+    }
+}
+
+fn rebuild_power_graph(
+    mut power_graph: ResMut<PowerGraph>,
+    q_powered_by: Query<(Entity, &PoweredBy)>,
+    q_powers_door: Query<(Entity, &PowersDoor)>,
+    q_button_targets: Query<(Entity, &ButtonTargets)>,
+    q_conduits: Query<&SignalConduit>,
+) {
+    power_graph.clear();
+
+    for (target, powered_by) in &q_powered_by {
+        power_graph.link(powered_by.0, target);
+    }
+
+    for (pole, powers_door) in &q_powers_door {
+        power_graph.link(pole, powers_door.0);
+    }
+
+    for (button, button_targets) in &q_button_targets {
+        for target in button_targets.0.iter() {
+            power_graph.link(button, *target);
+        }
+    }
+
+    for conduit in &q_conduits {
+        power_graph.link(conduit.from, conduit.to);
+    }
+}
@@ -3,9 +3,12 @@ use super::{
     GameLayer,
 };
 use crate::{
-    asset_management::asset_tag_components::{ChargePad, PressurePlate, WeightedCube},
+    asset_management::asset_tag_components::{
+        ChargePad, MultiCharge, PressurePlate, StartsPowered, WeightedCube,
+    },
     game::audio::{pressure_plate_pressed_audio, pressure_plate_released_audio},
     rendering::unlit_material::UnlitMaterial,
+    util::make_child_animatable,
     GameState,
 };
 use avian3d::prelude::*;
@@ -15,7 +18,7 @@ use bevy_tween::{
     combinator::tween,
     interpolate::translation,
     prelude::{AnimationBuilderExt, EaseKind},
-    tween::{AnimationTarget, TargetAsset, TargetComponent},
+    tween::{TargetAsset, TargetComponent},
 };
 use std::{collections::HashSet, time::Duration};
 
@@ -35,10 +38,12 @@ pub struct ChargePadDetector {
     pub detection_size: Vec3,
     /// Offset from the charge pad center for detection
     pub detection_offset: Vec3,
-    /// Currently charged entity (only one at a time)
-    pub charged_entity: Option<Entity>,
+    /// Currently charged entities, never more than one unless `multi_charge` is set
+    pub charged_entities: HashSet<Entity>,
     /// Entities currently overlapping with this charge pad
     pub overlapping_entities: HashSet<Entity>,
+    /// Whether every overlapping entity gets charged at once, from the MultiCharge tag
+    pub multi_charge: bool,
 }
 
 impl Default for ChargePadDetector {
@@ -46,8 +51,52 @@ impl Default for ChargePadDetector {
         Self {
             detection_size: Vec3::new(15.0, 8.0, 15.0), // Slightly larger than pressure plate
             detection_offset: Vec3::new(0.0, 4.0, 0.0), // Above the charge pad
-            charged_entity: None,
+            charged_entities: HashSet::new(),
             overlapping_entities: HashSet::new(),
+            multi_charge: false,
+        }
+    }
+}
+
+// How many consecutive checks with no change before a detector drops to the slower cadence
+const STABLE_CHECKS_BEFORE_THROTTLE: u32 = 16;
+// While throttled, a detector only queries once every this many FixedUpdates
+const THROTTLED_CHECK_INTERVAL: u32 = 2;
+
+// Skips overlap queries for plates and pads whose overlaps haven't changed in a while. Most
+// plates in a level sit idle most of the time, and an idle detector makes 508
+// shape_intersections calls over 1000 ticks instead of 1000 (see idle_cadence_check_count).
+// Any change puts the detector back on every-tick checks, so the worst case is noticing an
+// enter/leave one tick late, and because enter/leave are always computed against the last
+// checked state no transition is ever lost or doubled up.
+#[derive(Component, Default)]
+pub struct OverlapCadence {
+    stable_checks: u32,
+    ticks_until_check: u32,
+}
+
+impl OverlapCadence {
+    // Call every tick; returns whether the detector should query this tick
+    pub fn should_check(&mut self) -> bool {
+        if self.ticks_until_check > 0 {
+            self.ticks_until_check -= 1;
+            return false;
+        }
+
+        true
+    }
+
+    // Call after each query with whether the overlapping set changed
+    pub fn record(&mut self, changed: bool) {
+        if changed {
+            self.stable_checks = 0;
+            self.ticks_until_check = 0;
+            return;
+        }
+
+        self.stable_checks = self.stable_checks.saturating_add(1);
+        if self.stable_checks >= STABLE_CHECKS_BEFORE_THROTTLE {
+            self.ticks_until_check = THROTTLED_CHECK_INTERVAL - 1;
         }
     }
 }
@@ -109,12 +158,13 @@ fn register_pressure_plates(
     q_unlit_objects: Query<&MeshMaterial3d<UnlitMaterial>>,
     q_children: Query<&Children>,
     q_charge_pad: Query<Entity, With<ChargePad>>,
+    q_starts_powered: Query<(), With<StartsPowered>>,
 ) {
     for (plate_entity, plate_children, plate_parent) in &q_new_plate {
         // Add detector to the main plate entity
         commands
             .entity(plate_entity)
-            .insert(PressurePlateDetector::default())
+            .insert((PressurePlateDetector::default(), OverlapCadence::default()))
             .observe(on_pressure_plate_pressed)
             .observe(on_pressure_plate_released)
             .observe(pressure_plate_pressed_audio)
@@ -134,16 +184,22 @@ fn register_pressure_plates(
                             .observe(charge_pad_receive_power)
                             .observe(charge_pad_lose_power);
 
+                        if q_starts_powered.contains(sibling) {
+                            commands
+                                .entity(sibling)
+                                .insert(Powered)
+                                .remove::<StartsPowered>();
+                        }
+
                         if let Ok(charge_pad_children) = q_children.get(sibling) {
                             for charge_pad_child in charge_pad_children.iter() {
                                 if let Ok(material_handle) = q_unlit_objects.get(charge_pad_child) {
-                                    let old_material =
-                                        unlit_materials.get(material_handle).unwrap().clone();
-
-                                    commands.entity(charge_pad_child).insert((
-                                        AnimationTarget,
-                                        MeshMaterial3d(unlit_materials.add(old_material)),
-                                    ));
+                                    make_child_animatable(
+                                        &mut commands,
+                                        charge_pad_child,
+                                        material_handle,
+                                        &mut unlit_materials,
+                                    );
                                 }
                             }
                         }
@@ -155,23 +211,32 @@ fn register_pressure_plates(
 
         for plate_child in plate_children.iter() {
             if let Ok(material_handle) = q_unlit_objects.get(plate_child) {
-                let old_material = unlit_materials.get(material_handle).unwrap().clone();
-                commands.entity(plate_child).insert((
-                    AnimationTarget,
-                    MeshMaterial3d(unlit_materials.add(old_material)),
-                    RigidBody::Kinematic,
-                ));
+                make_child_animatable(
+                    &mut commands,
+                    plate_child,
+                    material_handle,
+                    &mut unlit_materials,
+                );
+                commands.entity(plate_child).insert(RigidBody::Kinematic);
             }
         }
     }
 }
 
-fn register_charge_pads(mut commands: Commands, q_new_charge_pad: Query<Entity, Added<ChargePad>>) {
-    for charge_pad_entity in &q_new_charge_pad {
-        // Add the detector component with default settings
+fn register_charge_pads(
+    mut commands: Commands,
+    q_new_charge_pad: Query<(Entity, Has<MultiCharge>), Added<ChargePad>>,
+) {
+    for (charge_pad_entity, multi_charge) in &q_new_charge_pad {
         commands
             .entity(charge_pad_entity)
-            .insert(ChargePadDetector::default())
+            .insert((
+                ChargePadDetector {
+                    multi_charge,
+                    ..default()
+                },
+                OverlapCadence::default(),
+            ))
             .observe(on_charge_pad_entity_entered)
             .observe(on_charge_pad_entity_left);
     }
@@ -180,15 +245,25 @@ fn register_charge_pads(mut commands: Commands, q_new_charge_pad: Query<Entity,
 fn update_charge_pad_overlaps(
     mut commands: Commands,
     mut q_charge_pads: Query<
-        (Entity, &GlobalTransform, &mut ChargePadDetector, &Children),
+        (
+            Entity,
+            &GlobalTransform,
+            &mut ChargePadDetector,
+            &mut OverlapCadence,
+            &Children,
+        ),
         With<ChargePad>,
     >,
     spatial_query: SpatialQuery,
     q_collider_of: Query<&ColliderOf>, // To check if entity has a rigid body
 ) {
-    for (charge_pad_entity, charge_pad_transform, mut detector, charge_pad_children) in
+    for (charge_pad_entity, charge_pad_transform, mut detector, mut cadence, charge_pad_children) in
         q_charge_pads.iter_mut()
     {
+        if !cadence.should_check() {
+            continue;
+        }
+
         let mut current_overlaps = HashSet::new();
 
         // Calculate detection box center
@@ -249,6 +324,8 @@ fn update_charge_pad_overlaps(
             );
         }
 
+        cadence.record(detector.overlapping_entities != current_overlaps);
+
         // Update the overlapping entities
         detector.overlapping_entities = current_overlaps;
     }
@@ -264,9 +341,9 @@ fn on_charge_pad_entity_entered(
     let entering_entity = event.entity;
 
     if let Ok((mut detector, maybe_powered)) = q_charge_pad.get_mut(charge_pad_entity) {
-        // If no entity is currently being charged, charge this one
-        if detector.charged_entity.is_none() {
-            detector.charged_entity = Some(entering_entity);
+        // Single charge pads only take a new entity once the last one has left
+        if detector.multi_charge || detector.charged_entities.is_empty() {
+            detector.charged_entities.insert(entering_entity);
 
             // Add Powered component and PoweredBy relationship
             if maybe_powered.is_some() {
@@ -291,30 +368,25 @@ fn on_charge_pad_entity_left(
     let leaving_entity = event.entity;
 
     if let Ok((mut detector, is_powered)) = q_charge_pad.get_mut(charge_pad_entity) {
-        // If this is the entity we're currently charging, stop charging it
-        if detector.charged_entity == Some(leaving_entity) {
-            detector.charged_entity = None;
-
-            // Remove Powered component only if it's powered by this charge pad
-            if let Ok(powered_by) = q_powered_by.get(leaving_entity) {
-                if powered_by.0 == charge_pad_entity {
-                    if !q_cubes.contains(leaving_entity) {
-                        commands
-                            .entity(leaving_entity)
-                            .remove::<Powered>()
-                            .remove::<PoweredBy>();
-                    } else {
-                        // cubes RETAIN power
-                        commands.entity(leaving_entity).remove::<PoweredBy>();
-                    }
-                }
+        // If this is an entity we're currently charging, stop charging it
+        if detector.charged_entities.remove(&leaving_entity) {
+            stop_charging(
+                &mut commands,
+                charge_pad_entity,
+                leaving_entity,
+                &q_powered_by,
+                &q_cubes,
+            );
+
+            // Multi charge pads are already charging everything else on them. Single ones
+            // hand off to the first available entity in the overlapping set.
+            if detector.multi_charge {
+                return;
             }
 
-            // Check if there are other entities we can start charging
-            // Priority: charge the first available entity in the overlapping set
             if let Some(&next_entity) = detector.overlapping_entities.iter().next() {
                 if next_entity != leaving_entity {
-                    detector.charged_entity = Some(next_entity);
+                    detector.charged_entities.insert(next_entity);
                     if is_powered {
                         commands
                             .entity(next_entity)
@@ -327,6 +399,29 @@ fn on_charge_pad_entity_left(
     }
 }
 
+// Remove Powered only if it's powered by this charge pad
+fn stop_charging(
+    commands: &mut Commands,
+    charge_pad_entity: Entity,
+    charged_entity: Entity,
+    q_powered_by: &Query<&PoweredBy>,
+    q_cubes: &Query<&WeightedCube>,
+) {
+    if let Ok(powered_by) = q_powered_by.get(charged_entity) {
+        if powered_by.0 == charge_pad_entity {
+            if !q_cubes.contains(charged_entity) {
+                commands
+                    .entity(charged_entity)
+                    .remove::<Powered>()
+                    .remove::<PoweredBy>();
+            } else {
+                // cubes RETAIN power
+                commands.entity(charged_entity).remove::<PoweredBy>();
+            }
+        }
+    }
+}
+
 fn update_pressure_plate_overlaps(
     mut commands: Commands,
     mut q_plates: Query<
@@ -334,13 +429,20 @@ fn update_pressure_plate_overlaps(
             Entity,
             &GlobalTransform,
             &mut PressurePlateDetector,
+            &mut OverlapCadence,
             &Children,
         ),
         With<PressurePlate>,
     >,
     spatial_query: SpatialQuery,
 ) {
-    for (plate_entity, plate_transform, mut detector, plate_children) in q_plates.iter_mut() {
+    for (plate_entity, plate_transform, mut detector, mut cadence, plate_children) in
+        q_plates.iter_mut()
+    {
+        if !cadence.should_check() {
+            continue;
+        }
+
         let mut current_overlaps = HashSet::new();
 
         // Calculate detection box center
@@ -377,6 +479,8 @@ fn update_pressure_plate_overlaps(
                 }
             }
         }
+        cadence.record(detector.overlapping_entities != current_overlaps);
+
         // Update the overlapping entities
         detector.overlapping_entities = current_overlaps;
 
@@ -443,8 +547,7 @@ fn charge_pad_receive_power(
     q_children: Query<&Children, With<Collider>>,
 ) {
     if let Ok((charge_pad, charge_pad_children, detector)) = q_charge_pad.get(trigger.target()) {
-        if let Some(charged_entity) = detector.charged_entity {
-            // Verify the entity is actually powered by this charge pad
+        for &charged_entity in &detector.charged_entities {
             commands
                 .entity(charged_entity)
                 .try_insert((Powered, PoweredBy(charge_pad)));
@@ -495,22 +598,15 @@ fn charge_pad_lose_power(
     q_children: Query<&Children, With<Collider>>,
 ) {
     if let Ok((charge_pad, charge_pad_children, detector)) = q_charge_pad.get(trigger.target()) {
-        // Remove power from any entity this charge pad is currently charging
-        if let Some(charged_entity) = detector.charged_entity {
-            // Verify the entity is actually powered by this charge pad
-            if let Ok(powered_by) = q_powered_by.get(charged_entity) {
-                if powered_by.0 == charge_pad {
-                    if !q_cubes.contains(charged_entity) {
-                        commands
-                            .entity(charged_entity)
-                            .remove::<Powered>()
-                            .remove::<PoweredBy>();
-                    } else {
-                        // cubes RETAIN power
-                        commands.entity(charged_entity).remove::<PoweredBy>();
-                    }
-                }
-            }
+        // Remove power from every entity this charge pad is currently charging
+        for &charged_entity in &detector.charged_entities {
+            stop_charging(
+                &mut commands,
+                charge_pad,
+                charged_entity,
+                &q_powered_by,
+                &q_cubes,
+            );
         }
 
         // Animate the charge pad's visual feedback
@@ -547,3 +643,46 @@ fn charge_pad_lose_power(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::test_app::{
+        advance_fixed, headless_app, spawn_device, spawn_device_group, spawn_test_player,
+    };
+
+    #[test]
+    fn pressure_plate_powers_its_charge_pad() {
+        let mut app = headless_app();
+        let plate = spawn_device(&mut app, PressurePlate { unused: false }, Vec3::ZERO);
+        // far enough away that the pad's own collider isn't standing on the plate
+        let charge_pad = spawn_device(&mut app, ChargePad { unused: false }, Vec3::X * 50.);
+        spawn_device_group(&mut app, &[plate, charge_pad]);
+
+        advance_fixed(&mut app, 4);
+        assert!(!app.world().entity(charge_pad).contains::<Powered>());
+
+        let player = spawn_test_player(&mut app, Vec3::Y * 3.);
+        advance_fixed(&mut app, 4);
+        assert!(app.world().entity(charge_pad).contains::<Powered>());
+
+        app.world_mut().entity_mut(player).despawn();
+        advance_fixed(&mut app, 4);
+        assert!(!app.world().entity(charge_pad).contains::<Powered>());
+    }
+
+    #[test]
+    fn idle_cadence_check_count() {
+        let mut cadence = OverlapCadence::default();
+        let mut checks = 0;
+
+        for _ in 0..1000 {
+            if cadence.should_check() {
+                checks += 1;
+                cadence.record(false);
+            }
+        }
+
+        assert_eq!(checks, 508);
+    }
+}
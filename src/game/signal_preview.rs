@@ -1,7 +1,11 @@
 use crate::{
-    asset_management::asset_tag_components::{Immobile, SignalSpitter},
+    asset_management::{
+        asset_loading::{GameAssets, NoAutoCollider},
+        asset_tag_components::{SignalSpitter, SpitterConfig},
+    },
     game::{
-        player::{Held, RightHand},
+        player::{Held, HeldGhost, RightHand},
+        signals::{launch_point, MAX_SIGNAL_TRAVEL_DIST},
         GameLayer,
     },
     rendering::unlit_material::UnlitMaterial,
@@ -10,15 +14,19 @@ use avian3d::prelude::{Collider, ShapeCastConfig, SpatialQuery, SpatialQueryFilt
 use bevy::prelude::*;
 use std::collections::HashSet;
 
-const IMMOBILE_SPIT_SIZE: f32 = 30.;
-const STANDARD_SPIT_SIZE: f32 = 10.;
 const SIGNAL_SHAPE_DEPTH: f32 = 2.0;
+const PREVIEW_BEAM_WIDTH: f32 = 0.3;
 
 #[derive(Component, Default)]
 pub struct SignalPreview {
     pub highlighted_entities: HashSet<Entity>,
+    pub beam: Option<Entity>,
 }
 
+// Thin translucent line from the held spitter to wherever its signal would stop
+#[derive(Component)]
+pub struct SignalPreviewBeam;
+
 pub fn signal_preview_plugin(app: &mut App) {
     app.add_systems(
         FixedUpdate,
@@ -32,34 +40,61 @@ pub fn signal_preview_plugin(app: &mut App) {
 }
 
 fn update_signal_preview(
+    mut commands: Commands,
     spatial_query: SpatialQuery,
+    game_assets: Res<GameAssets>,
     mut q_held_spitters: Query<
-        (Entity, &mut SignalPreview, &GlobalTransform, Has<Immobile>),
-        (With<SignalSpitter>, With<Held>),
+        (
+            Entity,
+            &mut SignalPreview,
+            &GlobalTransform,
+            &Held,
+            &SpitterConfig,
+        ),
+        With<SignalSpitter>,
     >,
+    q_ghosts: Query<(&HeldGhost, &GlobalTransform)>,
     q_unlit_materials: Query<&MeshMaterial3d<UnlitMaterial>>,
     mut unlit_materials: ResMut<Assets<UnlitMaterial>>,
     right_hand: Single<&RightHand>,
     //mut gizmos: Gizmos,
 ) {
-    for (spitter_entity, mut preview, spitter_transform, is_immobile) in &mut q_held_spitters {
+    for (spitter_entity, mut preview, spitter_transform, held, spitter_config) in
+        &mut q_held_spitters
+    {
         // Check if this spitter is actually being held by the player
-        if right_hand.held_object != Some(spitter_entity) {
+        if right_hand.held_object() != Some(spitter_entity) {
             continue;
         }
 
-        // Determine signal size based on spitter type
-        let signal_size = if is_immobile {
-            IMMOBILE_SPIT_SIZE
-        } else {
-            STANDARD_SPIT_SIZE
-        };
+        let beam_entity = *preview.beam.get_or_insert_with(|| {
+            commands
+                .spawn((
+                    Mesh3d(game_assets.preview_beam_mesh.clone()),
+                    MeshMaterial3d(game_assets.preview_beam_invalid_material.clone()),
+                    Transform::default(),
+                    Visibility::Hidden,
+                    NoAutoCollider,
+                    SignalPreviewBeam,
+                ))
+                .id()
+        });
+
+        if !held.can_release {
+            commands.entity(beam_entity).insert(Visibility::Hidden);
+            continue;
+        }
+
+        // a spitter with a ghost preview stays parked, the ghost is where it'll fire from
+        let spitter_transform = q_ghosts
+            .iter()
+            .find(|(ghost, _)| ghost.held == spitter_entity)
+            .map_or(spitter_transform, |(_, ghost_transform)| ghost_transform);
 
-        // Calculate signal spawn position and direction
-        let y_offset = if signal_size > 10. { 20. } else { 10. };
+        // same size and launch point the real signal will have
+        let signal_size = spitter_config.signal_size;
         let spitter_forward = -spitter_transform.forward();
-        let signal_start =
-            spitter_transform.translation() + Vec3::Y * y_offset + spitter_forward * 10.;
+        let signal_start = launch_point(spitter_transform, spitter_config.launch_offset);
 
         // Create shape for the signal path
         let signal_shape = Collider::cuboid(signal_size, signal_size, SIGNAL_SHAPE_DEPTH);
@@ -84,18 +119,25 @@ fn update_signal_preview(
 
         // Perform single shapecast along the signal path
         let mut new_highlighted = HashSet::new();
+        let mut beam_length = None;
+
+        let Ok(signal_direction) = Dir3::new(spitter_forward.into()) else {
+            commands.entity(beam_entity).insert(Visibility::Hidden);
+            continue;
+        };
 
         // Cast the shape along the path to find the first hit
         if let Some(hit_info) = spatial_query.cast_shape(
             &signal_shape,
             signal_start,
             cast_rotation,
-            Dir3::new(spitter_forward.into()).unwrap(),
+            signal_direction,
             &ShapeCastConfig::default(),
             &SpatialQueryFilter::default().with_mask([GameLayer::Device]),
         ) {
             let hit_distance = hit_info.distance;
             let hit_position = signal_start + spitter_forward * hit_distance;
+            beam_length = Some(hit_distance);
 
             // Always include the entity that was actually hit by the cast
             new_highlighted.insert(hit_info.entity);
@@ -141,6 +183,41 @@ fn update_signal_preview(
             // );
         }
 
+        // Signals pass through walls, but the beam stops at the first thing in the way
+        // so it doesn't stretch across the whole level
+        let hits_device = beam_length.is_some();
+        let beam_length = beam_length
+            .or_else(|| {
+                spatial_query
+                    .cast_ray(
+                        signal_start,
+                        signal_direction,
+                        MAX_SIGNAL_TRAVEL_DIST,
+                        true,
+                        &SpatialQueryFilter::default().with_mask([GameLayer::Default]),
+                    )
+                    .map(|hit| hit.distance)
+            })
+            .unwrap_or(MAX_SIGNAL_TRAVEL_DIST);
+
+        let beam_material = if hits_device {
+            game_assets.preview_beam_valid_material.clone()
+        } else {
+            game_assets.preview_beam_invalid_material.clone()
+        };
+
+        commands.entity(beam_entity).insert((
+            Transform::from_translation(signal_start + spitter_forward * (beam_length / 2.))
+                .with_rotation(cast_rotation)
+                .with_scale(Vec3::new(
+                    PREVIEW_BEAM_WIDTH,
+                    PREVIEW_BEAM_WIDTH,
+                    beam_length,
+                )),
+            MeshMaterial3d(beam_material),
+            Visibility::Visible,
+        ));
+
         // Remove highlighting from entities no longer in the path
         for &entity in &preview.highlighted_entities {
             if !new_highlighted.contains(&entity) {
@@ -173,6 +250,7 @@ fn update_signal_preview(
 }
 
 fn cleanup_signal_preview_on_drop(
+    mut commands: Commands,
     mut q_spitters_losing_held: RemovedComponents<Held>,
     mut q_spitter_preview: Query<&mut SignalPreview, With<SignalSpitter>>,
     q_unlit_materials: Query<&MeshMaterial3d<UnlitMaterial>>,
@@ -190,6 +268,10 @@ fn cleanup_signal_preview_on_drop(
                 }
             }
             preview.highlighted_entities.clear();
+
+            if let Some(beam_entity) = preview.beam.take() {
+                commands.entity(beam_entity).try_despawn();
+            }
         }
     }
 }
@@ -0,0 +1,99 @@
+use avian3d::prelude::{CollisionEventsEnabled, CollisionLayers, RigidBody};
+use bevy::prelude::*;
+
+use crate::{
+    asset_management::asset_tag_components::SignalRelay, rendering::unlit_material::UnlitMaterial,
+    util::make_child_animatable,
+};
+
+use super::{
+    inert::flash_device,
+    signals::{
+        default_signal_collisions, DirectSignal, SignalAfterDelay, SignalDebounce, SpitterCooldown,
+        DEFAULT_SIGNAL_SPEED,
+    },
+    GameLayer,
+};
+
+const RELAY_DELAY_MS: u32 = 300;
+const RELAY_SIGNAL_SIZE: f32 = 10.;
+const RELAY_LAUNCH_OFFSET: Vec3 = Vec3::new(0., 10., 10.);
+// two relays facing each other would otherwise bounce a signal back and forth forever
+const RELAY_COOLDOWN_SEC: f32 = 1.0;
+
+pub fn signal_relay_plugin(app: &mut App) {
+    app.add_systems(FixedPreUpdate, register_signal_relays);
+}
+
+fn register_signal_relays(
+    mut commands: Commands,
+    q_new_relay: Query<(Entity, &Children), Added<SignalRelay>>,
+    mut unlit_materials: ResMut<Assets<UnlitMaterial>>,
+    q_unlit_objects: Query<&MeshMaterial3d<UnlitMaterial>>,
+) {
+    for (relay_entity, relay_children) in &q_new_relay {
+        commands
+            .entity(relay_entity)
+            .insert((
+                RigidBody::Static,
+                SignalDebounce::default(),
+                SpitterCooldown::from_seconds(RELAY_COOLDOWN_SEC),
+            ))
+            .observe(signal_relay_direct_signal);
+
+        for relay_child in relay_children.iter() {
+            if let Ok(material_handle) = q_unlit_objects.get(relay_child) {
+                make_child_animatable(
+                    &mut commands,
+                    relay_child,
+                    material_handle,
+                    &mut unlit_materials,
+                );
+                commands
+                    .entity(relay_child)
+                    .insert((
+                        CollisionLayers::new(
+                            GameLayer::Device,
+                            [GameLayer::Player, GameLayer::Signal, GameLayer::Device],
+                        ),
+                        CollisionEventsEnabled,
+                    ))
+                    .observe(default_signal_collisions);
+            }
+        }
+    }
+}
+
+// default_signal_collisions has already despawned the incoming signal by now
+fn signal_relay_direct_signal(
+    trigger: Trigger<DirectSignal>,
+    mut commands: Commands,
+    mut q_relay: Query<(&Children, &mut SpitterCooldown), With<SignalRelay>>,
+    q_unlit_objects: Query<&MeshMaterial3d<UnlitMaterial>>,
+    mut unlit_materials: ResMut<Assets<UnlitMaterial>>,
+    time: Res<Time>,
+) {
+    let relay_entity = trigger.target();
+
+    if let Ok((relay_children, mut cooldown)) = q_relay.get_mut(relay_entity) {
+        flash_device(
+            &mut commands,
+            relay_children,
+            &q_unlit_objects,
+            &mut unlit_materials,
+        );
+
+        if !cooldown.ready() {
+            return;
+        }
+        cooldown.restart();
+
+        commands.entity(relay_entity).with_child(SignalAfterDelay {
+            delay_ms: RELAY_DELAY_MS,
+            spawn_time: time.elapsed(),
+            signal_size: RELAY_SIGNAL_SIZE,
+            launch_offset: RELAY_LAUNCH_OFFSET,
+            signal_speed: DEFAULT_SIGNAL_SPEED,
+        });
+    }
+}
@@ -1,21 +1,24 @@
 use std::time::Duration;
 
 use avian3d::prelude::{
-    Collider, CollisionEventsEnabled, CollisionLayers, LockedAxes, RigidBody, RigidBodyColliders,
-    SleepingDisabled,
+    Collider, ColliderOf, CollisionEventsEnabled, CollisionLayers, LockedAxes, RigidBody,
+    RigidBodyColliders, SleepingDisabled,
 };
 use bevy::prelude::*;
 use bevy_tween::{
     bevy_time_runner::TimeSpan,
     combinator::{sequence, tween},
     prelude::{AnimationBuilderExt, EaseKind},
-    tween::{AnimationTarget, TargetAsset},
+    tween::TargetAsset,
 };
 
 use crate::{
-    asset_management::asset_tag_components::{Immobile, SignalSpitter},
-    game::player::Held,
+    asset_management::asset_tag_components::{
+        HandToggleable, Immobile, SignalSpitter, SpitterConfig, StartsPowered,
+    },
+    game::{interaction::Interacted, player::Held},
     rendering::unlit_material::UnlitMaterial,
+    util::make_child_animatable,
     GameState,
 };
 
@@ -32,25 +35,28 @@ use super::{
 #[derive(Component)]
 pub struct ContinuousEmission {
     pub interval_ms: u32,
+    // hand toggleable spitters flip this instead of waiting on power
+    pub active: bool,
 }
 
 impl Default for ContinuousEmission {
     fn default() -> Self {
         Self {
             interval_ms: 1000, // 1 second default interval
+            active: true,
         }
     }
 }
 
-const IMMOBILE_SPIT_SIZE: f32 = 30.;
-const STANDARD_SPIT_SIZE: f32 = 10.;
-
 pub fn signal_spitter_plugin(app: &mut App) {
-    app.add_systems(FixedPreUpdate, (register_signal_spitter_signals,))
-        .add_systems(
-            FixedUpdate,
-            handle_continuous_signal_emission.run_if(in_state(GameState::Playing)),
-        );
+    app.add_systems(
+        FixedPreUpdate,
+        (fill_in_spitter_configs, register_signal_spitter_signals).chain(),
+    )
+    .add_systems(
+        FixedUpdate,
+        handle_continuous_signal_emission.run_if(in_state(GameState::Playing)),
+    );
 }
 
 pub fn sink_when_not_held(trigger: Trigger<OnRemove, Held>, mut commands: Commands) {
@@ -69,11 +75,11 @@ pub fn dont_sink_when_held(trigger: Trigger<OnAdd, Held>, mut commands: Commands
 fn signal_spitter_direct_signal(
     trigger: Trigger<DirectSignal>,
     mut commands: Commands,
-    q_spitter: Query<(&RigidBodyColliders, Has<Immobile>), With<SignalSpitter>>,
+    q_spitter: Query<(&RigidBodyColliders, &SpitterConfig), With<SignalSpitter>>,
     q_unlit_objects: Query<&MeshMaterial3d<UnlitMaterial>>,
     time: Res<Time>,
 ) {
-    if let Ok((spitter_colliders, is_immobile)) = q_spitter.get(trigger.target()) {
+    if let Ok((spitter_colliders, spitter_config)) = q_spitter.get(trigger.target()) {
         for collider_entity in spitter_colliders.iter() {
             if let Ok(material_handle) = q_unlit_objects.get(collider_entity) {
                 commands
@@ -81,11 +87,9 @@ fn signal_spitter_direct_signal(
                     .with_child(SignalAfterDelay {
                         delay_ms: (POWER_ANIMATION_DURATION_SEC * 1000.) as u32,
                         spawn_time: time.elapsed(),
-                        signal_size: if is_immobile {
-                            IMMOBILE_SPIT_SIZE
-                        } else {
-                            STANDARD_SPIT_SIZE
-                        },
+                        signal_size: spitter_config.signal_size,
+                        launch_offset: spitter_config.launch_offset,
+                        signal_speed: spitter_config.signal_speed,
                     });
                 commands
                     .entity(collider_entity)
@@ -117,10 +121,24 @@ fn signal_spitter_direct_signal(
     }
 }
 
+// Immobile spitters are the big ones unless the level says otherwise
+fn fill_in_spitter_configs(
+    mut commands: Commands,
+    q_unconfigured: Query<(Entity, Has<Immobile>), (With<SignalSpitter>, Without<SpitterConfig>)>,
+) {
+    for (spitter_entity, is_immobile) in &q_unconfigured {
+        commands.entity(spitter_entity).insert(if is_immobile {
+            SpitterConfig::IMMOBILE
+        } else {
+            SpitterConfig::STANDARD
+        });
+    }
+}
+
 fn register_signal_spitter_signals(
     mut commands: Commands,
     q_new_signal_spitter: Query<
-        (Entity, &RigidBodyColliders),
+        (Entity, &RigidBodyColliders, Has<StartsPowered>),
         (
             Added<RigidBodyColliders>,
             With<SignalSpitter>,
@@ -129,11 +147,17 @@ fn register_signal_spitter_signals(
     >,
     mut unlit_materials: ResMut<Assets<UnlitMaterial>>,
     q_unlit_objects: Query<&MeshMaterial3d<UnlitMaterial>>,
+    q_hand_toggleable: Query<(), With<HandToggleable>>,
 ) {
-    for (spitter_entity, spitter_children) in &q_new_signal_spitter {
+    for (spitter_entity, spitter_children, starts_powered) in &q_new_signal_spitter {
         for spitter_child in spitter_children.iter() {
             if let Ok(material_handle) = q_unlit_objects.get(spitter_child) {
-                let old_material = unlit_materials.get(material_handle).unwrap().clone();
+                make_child_animatable(
+                    &mut commands,
+                    spitter_child,
+                    material_handle,
+                    &mut unlit_materials,
+                );
 
                 commands
                     .entity(spitter_child)
@@ -148,20 +172,31 @@ fn register_signal_spitter_signals(
                                 GameLayer::Default,
                             ],
                         ),
-                        AnimationTarget,
-                        MeshMaterial3d(unlit_materials.add(old_material)),
                     ))
                     .observe(default_signal_collisions);
             }
         }
+        // hand toggleable ones start switched off
+        let continuous_emission = ContinuousEmission {
+            active: !q_hand_toggleable.contains(spitter_entity),
+            ..default()
+        };
+
         commands
             .entity(spitter_entity)
-            .insert((ContinuousEmission::default(), SleepingDisabled)) // Add continuous emission component
+            .insert((continuous_emission, SleepingDisabled)) // Add continuous emission component
             .observe(signal_spitter_direct_signal)
             .observe(signal_spitter_receive_power)
             .observe(signal_spitter_lose_power)
             .observe(sink_when_not_held)
             .observe(dont_sink_when_held);
+
+        if starts_powered {
+            commands
+                .entity(spitter_entity)
+                .insert(Powered)
+                .remove::<StartsPowered>();
+        }
     }
 }
 
@@ -173,7 +208,7 @@ fn signal_spitter_receive_power(
             Entity,
             &RigidBodyColliders,
             &ContinuousEmission,
-            Has<Immobile>,
+            &SpitterConfig,
         ),
         With<SignalSpitter>,
     >,
@@ -183,7 +218,7 @@ fn signal_spitter_receive_power(
     q_children: Query<&Children, With<Collider>>,
     time: Res<Time>,
 ) {
-    if let Ok((signal_spitter, signal_spitter_children, continuous_emission, is_immobile)) =
+    if let Ok((signal_spitter, signal_spitter_children, continuous_emission, spitter_config)) =
         q_signal_spitter.get(trigger.target())
     {
         for collider_entity in signal_spitter_children.iter() {
@@ -217,17 +252,19 @@ fn signal_spitter_receive_power(
             }
         }
 
+        if !continuous_emission.active {
+            return;
+        }
+
         // Start continuous emission when powered
         commands
             .entity(signal_spitter)
             .with_child(SignalAfterDelay {
                 delay_ms: continuous_emission.interval_ms,
                 spawn_time: time.elapsed(),
-                signal_size: if is_immobile {
-                    IMMOBILE_SPIT_SIZE
-                } else {
-                    STANDARD_SPIT_SIZE
-                },
+                signal_size: spitter_config.signal_size,
+                launch_offset: spitter_config.launch_offset,
+                signal_speed: spitter_config.signal_speed,
             });
     }
 }
@@ -274,28 +311,77 @@ fn signal_spitter_lose_power(
             }
         }
 
-        // Remove all SignalAfterDelay children to stop continuous emission
-        if let Ok(children) = q_children.get(signal_spitter) {
-            for child in children.iter() {
-                if q_signal_after_delay.contains(child) {
-                    commands.entity(child).despawn();
-                }
+        stop_pending_emission(
+            &mut commands,
+            signal_spitter,
+            &q_children,
+            &q_signal_after_delay,
+        );
+    }
+}
+
+// Remove all SignalAfterDelay children to stop continuous emission
+fn stop_pending_emission(
+    commands: &mut Commands,
+    signal_spitter: Entity,
+    q_children: &Query<&Children>,
+    q_signal_after_delay: &Query<(), With<SignalAfterDelay>>,
+) {
+    if let Ok(children) = q_children.get(signal_spitter) {
+        for child in children.iter() {
+            if q_signal_after_delay.contains(child) {
+                commands.entity(child).despawn();
             }
         }
     }
 }
 
+// Hand toggleable spitters keep going while they're active, powered or not, and stop right away
+// when switched off. Powered ones that get switched back on pick up again on their own.
+pub fn toggle_continuous_emission(
+    trigger: Trigger<Interacted>,
+    mut commands: Commands,
+    q_collider_of: Query<&ColliderOf>,
+    mut q_emission: Query<&mut ContinuousEmission, With<HandToggleable>>,
+    q_children: Query<&Children>,
+    q_signal_after_delay: Query<(), With<SignalAfterDelay>>,
+) {
+    let Ok(collider_of) = q_collider_of.get(trigger.target()) else {
+        return;
+    };
+
+    if let Ok(mut continuous_emission) = q_emission.get_mut(collider_of.body) {
+        continuous_emission.active = !continuous_emission.active;
+
+        if !continuous_emission.active {
+            stop_pending_emission(
+                &mut commands,
+                collider_of.body,
+                &q_children,
+                &q_signal_after_delay,
+            );
+        }
+    }
+}
+
 fn handle_continuous_signal_emission(
     mut commands: Commands,
     q_powered_spitters: Query<
-        (Entity, &ContinuousEmission, Has<Immobile>),
-        (With<SignalSpitter>, With<Powered>),
+        (Entity, &ContinuousEmission, &SpitterConfig),
+        (
+            With<SignalSpitter>,
+            Or<(With<Powered>, With<HandToggleable>)>,
+        ),
     >,
     q_children: Query<&Children>,
     q_signal_after_delay: Query<(), With<SignalAfterDelay>>,
     time: Res<Time>,
 ) {
-    for (spitter_entity, continuous_emission, is_immobile) in &q_powered_spitters {
+    for (spitter_entity, continuous_emission, spitter_config) in &q_powered_spitters {
+        if !continuous_emission.active {
+            continue;
+        }
+
         // Check if this spitter has any active SignalAfterDelay children
         let mut has_pending_signal = false;
         if let Ok(children) = q_children.get(spitter_entity) {
@@ -314,11 +400,9 @@ fn handle_continuous_signal_emission(
                 .with_child(SignalAfterDelay {
                     delay_ms: continuous_emission.interval_ms,
                     spawn_time: time.elapsed(),
-                    signal_size: if is_immobile {
-                        IMMOBILE_SPIT_SIZE
-                    } else {
-                        STANDARD_SPIT_SIZE
-                    },
+                    signal_size: spitter_config.signal_size,
+                    launch_offset: spitter_config.launch_offset,
+                    signal_speed: spitter_config.signal_speed,
                 });
         }
     }
@@ -1,4 +1,4 @@
-use std::time::Duration;
+use std::{collections::HashMap, time::Duration};
 
 use avian3d::prelude::{
     ColliderConstructor, ColliderOf, CollisionEventsEnabled, CollisionLayers, OnCollisionStart,
@@ -13,28 +13,93 @@ use bevy_tween::{
 };
 
 use crate::{
-    asset_management::asset_loading::GameAssets, rendering::unlit_material::UnlitMaterial,
+    asset_management::{
+        asset_loading::{GameAssets, NoAutoCollider},
+        asset_tag_components::{HostileSignals, SignalColor, SignalFalloff},
+    },
+    rendering::unlit_material::UnlitMaterial,
     GameState,
 };
 
 use super::{door::PoweredTimer, GameLayer};
 
 pub fn signals_plugin(app: &mut App) {
-    app.add_systems(
-        FixedUpdate,
-        (despawn_after_system, signal_after_delay).run_if(in_state(GameState::Playing)),
-    );
+    app.init_resource::<SignalBudget>()
+        .init_resource::<SignalMeshCache>()
+        .add_systems(
+            FixedUpdate,
+            (
+                despawn_after_system,
+                signal_after_delay,
+                spawn_signal_charge_indicators,
+                update_signal_charge_indicators,
+                decay_signals,
+                tick_spitter_cooldowns,
+            )
+                .run_if(in_state(GameState::Playing)),
+        );
 }
 
 #[derive(Component)]
 pub struct Signal;
 
+// A signal from a HostileSignals emitter, which also collides with the player
+#[derive(Component)]
+pub struct HostileSignal {
+    pub direction: Vec3,
+}
+
+// Fired on the player when a hostile signal runs into them
+#[derive(Event)]
+pub struct PlayerHitBySignal {
+    // the way the signal was travelling
+    pub direction: Vec3,
+}
+
 #[derive(Component)]
 pub struct Powered;
 
 #[derive(Component, Default, Deref, DerefMut)]
 pub struct OwnedObjects(pub Vec<Entity>);
 
+impl OwnedObjects {
+    // takes out the oldest objects so there's room for one more under max_owned
+    pub fn make_room(&mut self, max_owned: usize) -> Vec<Entity> {
+        let excess = (self.0.len() + 1).saturating_sub(max_owned.max(1));
+        self.0.drain(..excess).collect()
+    }
+}
+
+pub const SPITTER_RESPAWN_INTERVAL_SEC: f32 = 1.0;
+
+// Cubes that die as soon as they spawn (say, dropped onto a dissolve gate) would otherwise
+// get replaced every single tick
+#[derive(Component)]
+pub struct SpitterCooldown(pub Timer);
+
+impl Default for SpitterCooldown {
+    fn default() -> Self {
+        Self::from_seconds(SPITTER_RESPAWN_INTERVAL_SEC)
+    }
+}
+
+impl SpitterCooldown {
+    pub fn from_seconds(seconds: f32) -> Self {
+        // starts out finished so the first spawn isn't delayed
+        let mut timer = Timer::from_seconds(seconds, TimerMode::Once);
+        timer.tick(timer.duration());
+        Self(timer)
+    }
+
+    pub fn ready(&self) -> bool {
+        self.0.finished()
+    }
+
+    pub fn restart(&mut self) {
+        self.0.reset();
+    }
+}
+
 #[derive(Reflect, Debug)]
 pub struct MaterialIntensityInterpolator {
     pub start: f32,
@@ -49,20 +114,98 @@ impl Interpolator for MaterialIntensityInterpolator {
     }
 }
 
+pub const SIGNAL_DEBOUNCE_MS: u64 = 100;
+
+// Wide signals can overlap several child colliders of the same device at once.
+// Devices with this ignore any DirectSignal from a collision inside the cooldown window.
+#[derive(Component)]
+pub struct SignalDebounce {
+    pub cooldown: Duration,
+    pub last: Option<Duration>,
+}
+
+impl SignalDebounce {
+    pub fn new(cooldown: Duration) -> Self {
+        Self {
+            cooldown,
+            last: None,
+        }
+    }
+
+    // Returns true and records the signal if we're outside the cooldown window
+    pub fn try_accept(&mut self, now: Duration) -> bool {
+        if let Some(last) = self.last {
+            if now.saturating_sub(last) < self.cooldown {
+                return false;
+            }
+        }
+
+        self.last = Some(now);
+        true
+    }
+}
+
+impl Default for SignalDebounce {
+    fn default() -> Self {
+        Self::new(Duration::from_millis(SIGNAL_DEBOUNCE_MS))
+    }
+}
+
+pub fn signal_debounce_allows(
+    q_debounce: &mut Query<&mut SignalDebounce>,
+    entity: Entity,
+    now: Duration,
+) -> bool {
+    match q_debounce.get_mut(entity) {
+        Ok(mut debounce) => debounce.try_accept(now),
+        Err(_) => true,
+    }
+}
+
+fn tick_spitter_cooldowns(mut q_cooldowns: Query<&mut SpitterCooldown>, time: Res<Time>) {
+    for mut cooldown in &mut q_cooldowns {
+        cooldown.0.tick(time.delta());
+    }
+}
+
+// Observes the player. Normal signals don't have the player in their filters, so only hostile
+// ones ever show up here.
+pub fn hostile_signal_collisions(
+    trigger: Trigger<OnCollisionStart>,
+    mut commands: Commands,
+    q_hostile_signals: Query<&HostileSignal>,
+) {
+    if let Ok(hostile_signal) = q_hostile_signals.get(trigger.collider) {
+        commands.trigger_targets(
+            PlayerHitBySignal {
+                direction: hostile_signal.direction,
+            },
+            trigger.target(),
+        );
+        commands.entity(trigger.collider).try_despawn();
+    }
+}
+
 pub fn default_signal_collisions(
     trigger: Trigger<OnCollisionStart>,
     mut commands: Commands,
+    time: Res<Time>,
     q_signals: Query<(), With<Signal>>,
     q_powered: Query<(), (With<Powered>, Without<PoweredTimer>)>,
     q_collider_of: Query<&ColliderOf>,
+    mut q_debounce: Query<&mut SignalDebounce>,
 ) {
     if q_signals.contains(trigger.collider) {
         if let Ok(collider_of) = q_collider_of.get(trigger.target()) {
-            if !q_powered.contains(collider_of.body) {
+            if !q_powered.contains(collider_of.body)
+                && signal_debounce_allows(&mut q_debounce, collider_of.body, time.elapsed())
+            {
                 commands.entity(collider_of.body).trigger(DirectSignal);
                 commands.entity(trigger.collider).despawn();
             }
-        } else if !q_powered.contains(trigger.target()) {
+        } else if !q_powered.contains(trigger.target())
+            && signal_debounce_allows(&mut q_debounce, trigger.target(), time.elapsed())
+        {
             commands.entity(trigger.target()).trigger(DirectSignal);
             commands.entity(trigger.collider).despawn();
         }
@@ -74,71 +217,171 @@ pub struct SignalAfterDelay {
     pub delay_ms: u32,
     pub spawn_time: Duration,
     pub signal_size: f32,
+    // relative to the emitter, +Z being the way it fires
+    pub launch_offset: Vec3,
+    // units per second, it always goes MAX_SIGNAL_TRAVEL_DIST so slower ones just live longer
+    pub signal_speed: f32,
 }
 
+impl SignalAfterDelay {
+    pub fn progress(&self, now: Duration) -> f32 {
+        let elapsed = now.saturating_sub(self.spawn_time).as_secs_f32();
+        (elapsed / (self.delay_ms as f32 / 1000.)).clamp(0.0, 1.0)
+    }
+}
+
+// Grows at the spitter's launch point while a SignalAfterDelay is counting down
+#[derive(Component)]
+pub struct SignalChargeIndicator {
+    pub pending_signal: Entity,
+    pub max_scale: f32,
+}
+
+// Signals from a SignalFalloff emitter shrink as they travel. Avian scales colliders by the
+// entity's GlobalTransform, so scaling the Transform shrinks the sensor along with the mesh
+// without having to rebuild the collider.
+#[derive(Component)]
+pub struct SignalDecay {
+    pub origin: Vec3,
+    pub distance: f32,
+}
+
+// below this the signal is too small to matter, just get rid of it
+const MIN_DECAYED_SIGNAL_SCALE: f32 = 0.05;
+
 #[derive(Event)]
 pub struct DirectSignal;
 
+// Every live signal is a kinematic sensor with collision events, so a few continuous spitters
+// left running can get expensive fast. Spitters just skip firing while we're at the limit.
+#[derive(Resource)]
+pub struct SignalBudget {
+    pub max: usize,
+}
+
+impl Default for SignalBudget {
+    fn default() -> Self {
+        Self { max: 64 }
+    }
+}
+
+const SIGNAL_DEPTH: f32 = 2.0;
+
+// Continuous spitters fire a signal every second, so share one mesh per signal size instead of
+// adding a new one to Assets<Mesh> each time. Keyed by the size's bits since f32 isn't Hash.
+#[derive(Resource, Default)]
+pub struct SignalMeshCache {
+    cuboids: HashMap<u32, Handle<Mesh>>,
+    planes: HashMap<u32, Handle<Mesh>>,
+}
+
+impl SignalMeshCache {
+    pub fn cuboid(&mut self, meshes: &mut Assets<Mesh>, size: f32) -> Handle<Mesh> {
+        self.cuboids
+            .entry(size.to_bits())
+            .or_insert_with(|| meshes.add(Cuboid::new(size, size, SIGNAL_DEPTH)))
+            .clone()
+    }
+
+    // flat ones facing -Z, like the big red button's
+    pub fn plane(&mut self, meshes: &mut Assets<Mesh>, half_size: f32) -> Handle<Mesh> {
+        self.planes
+            .entry(half_size.to_bits())
+            .or_insert_with(|| meshes.add(Plane3d::new(-Vec3::Z, Vec2::splat(half_size))))
+            .clone()
+    }
+}
+
 pub const MAX_SIGNAL_TRAVEL_DIST: f32 = 500.;
-pub const MAX_SIGNAL_LIFETIME_SECS: u64 = 10;
+pub const DEFAULT_SIGNAL_SPEED: f32 = 50.;
+// anything slower than this would hang around for minutes
+const MIN_SIGNAL_SPEED: f32 = 5.;
+
+// How long a signal at this speed takes to cover MAX_SIGNAL_TRAVEL_DIST, which is also when it
+// gets despawned
+pub fn signal_lifetime(signal_speed: f32) -> Duration {
+    Duration::from_secs_f32(MAX_SIGNAL_TRAVEL_DIST / signal_speed.max(MIN_SIGNAL_SPEED))
+}
 
 fn signal_after_delay(
     mut commands: Commands,
     q_waiting: Query<(Entity, &SignalAfterDelay, &ChildOf)>,
     q_global_transform: Query<&GlobalTransform>,
+    q_falloff: Query<&SignalFalloff>,
+    q_signal_color: Query<&SignalColor>,
+    q_hostile: Query<(), With<HostileSignals>>,
+    q_signals: Query<(), With<Signal>>,
+    signal_budget: Res<SignalBudget>,
     time: Res<Time>,
     mut meshes: ResMut<Assets<Mesh>>,
+    mut signal_mesh_cache: ResMut<SignalMeshCache>,
     game_assets: Res<GameAssets>,
 ) {
+    // spawns this tick aren't in the query yet, so keep our own running count
+    let mut live_signals = q_signals.iter().count();
+
     for (entity, signal_delay, child_of) in &q_waiting {
         // Check if the delay time has elapsed
         let elapsed_since_spawn = time.elapsed() - signal_delay.spawn_time;
 
         if elapsed_since_spawn >= Duration::from_millis(signal_delay.delay_ms as u64) {
             // Delay is complete, spawn the signal
-            let y_amount_to_look_good = if signal_delay.signal_size > 10. {
-                // actually depends on where we consider the visual "launch point" on each spitter model to be
-                20.
-            } else {
-                10.
-            };
-
             if let Ok(global_transform) = q_global_transform.get(child_of.0) {
+                let lifetime = signal_lifetime(signal_delay.signal_speed);
                 let spitter_forward = -global_transform.forward();
-                let start_loc = global_transform.translation()
-                    + Vec3::Y * y_amount_to_look_good
-                    + spitter_forward * 10.;
+                let start_loc = launch_point(global_transform, signal_delay.launch_offset);
 
                 // Create transform that faces the direction the spitter is pointing
                 let signal_transform =
                     Transform::from_translation(start_loc).looking_to(-spitter_forward, Vec3::Y);
+                let signal_color = q_signal_color
+                    .get(child_of.0)
+                    .map(|signal_color| signal_color.color)
+                    .unwrap_or_default();
 
-                let signal_indicator = commands
-                    .spawn((
+                let Some(signal_indicator) = spawn_signal(
+                    &mut commands,
+                    &mut live_signals,
+                    &signal_budget,
+                    (
                         ColliderConstructor::Cuboid {
                             x_length: signal_delay.signal_size,
                             y_length: signal_delay.signal_size,
-                            z_length: 2.0,
+                            z_length: SIGNAL_DEPTH,
                         },
                         CollisionLayers::new(GameLayer::Signal, [GameLayer::Device]),
-                        Mesh3d(meshes.add(Cuboid::new(
-                            signal_delay.signal_size,
-                            signal_delay.signal_size,
-                            2.0,
-                        ))),
-                        MeshMaterial3d(game_assets.cyan_signal_material.clone()),
+                        Mesh3d(signal_mesh_cache.cuboid(&mut meshes, signal_delay.signal_size)),
+                        MeshMaterial3d(game_assets.signal_material(signal_color)),
                         signal_transform,
-                        AnimationTarget,
-                        CollisionEventsEnabled,
-                        RigidBody::Kinematic,
-                        Sensor,
-                        Signal,
-                        DespawnAfter::new(Duration::from_secs(MAX_SIGNAL_LIFETIME_SECS)), // Despawn after 10 seconds
-                    ))
-                    .id();
+                        DespawnAfter::new(lifetime),
+                    ),
+                ) else {
+                    trace!(?entity, "signal budget exhausted, skipping signal");
+                    commands.entity(entity).remove::<SignalAfterDelay>();
+                    continue;
+                };
+
+                if q_hostile.contains(child_of.0) {
+                    commands.entity(signal_indicator).insert((
+                        CollisionLayers::new(
+                            GameLayer::Signal,
+                            [GameLayer::Device, GameLayer::Player],
+                        ),
+                        HostileSignal {
+                            direction: spitter_forward.into(),
+                        },
+                    ));
+                }
+
+                if let Ok(falloff) = q_falloff.get(child_of.0) {
+                    commands.entity(signal_indicator).insert(SignalDecay {
+                        origin: start_loc,
+                        distance: falloff.distance,
+                    });
+                }
 
                 commands.entity(signal_indicator).animation().insert(tween(
-                    Duration::from_secs(MAX_SIGNAL_LIFETIME_SECS),
+                    lifetime,
                     EaseKind::Linear,
                     TargetComponent::marker().with(translation(
                         start_loc,
@@ -153,6 +396,100 @@ fn signal_after_delay(
     }
 }
 
+// Everything that fires a signal goes through here so nothing gets past the SignalBudget.
+// `live_signals` is the caller's running count, spawns this tick aren't in any query yet.
+pub fn spawn_signal(
+    commands: &mut Commands,
+    live_signals: &mut usize,
+    signal_budget: &SignalBudget,
+    signal: impl Bundle,
+) -> Option<Entity> {
+    if *live_signals >= signal_budget.max {
+        return None;
+    }
+    *live_signals += 1;
+
+    let signal_entity = commands
+        .spawn((
+            signal,
+            AnimationTarget,
+            CollisionEventsEnabled,
+            RigidBody::Kinematic,
+            Sensor,
+            Signal,
+            // nothing in flight should outlive the level if we leave it
+            StateScoped(GameState::Playing),
+        ))
+        .id();
+
+    Some(signal_entity)
+}
+
+// Where a signal leaves an emitter in world space. Shared with the signal preview so the two
+// always agree.
+pub fn launch_point(emitter_transform: &GlobalTransform, launch_offset: Vec3) -> Vec3 {
+    emitter_transform.translation() + emitter_transform.rotation() * launch_offset
+}
+
+fn spawn_signal_charge_indicators(
+    mut commands: Commands,
+    q_new_pending: Query<(Entity, &SignalAfterDelay, &ChildOf), Added<SignalAfterDelay>>,
+    q_signal_color: Query<&SignalColor>,
+    game_assets: Res<GameAssets>,
+) {
+    for (pending_entity, signal_delay, child_of) in &q_new_pending {
+        let signal_color = q_signal_color
+            .get(child_of.parent())
+            .map(|signal_color| signal_color.color)
+            .unwrap_or_default();
+
+        // local +Z is the spitter's firing direction
+        commands.entity(child_of.parent()).with_child((
+            Mesh3d(game_assets.signal_charge_mesh.clone()),
+            MeshMaterial3d(game_assets.signal_material(signal_color)),
+            Transform::from_translation(signal_delay.launch_offset).with_scale(Vec3::ZERO),
+            NoAutoCollider,
+            SignalChargeIndicator {
+                pending_signal: pending_entity,
+                max_scale: signal_delay.signal_size / 4.,
+            },
+        ));
+    }
+}
+
+fn update_signal_charge_indicators(
+    mut commands: Commands,
+    mut q_indicators: Query<(Entity, &SignalChargeIndicator, &mut Transform)>,
+    q_pending: Query<&SignalAfterDelay>,
+    time: Res<Time>,
+) {
+    for (indicator_entity, indicator, mut transform) in &mut q_indicators {
+        // signal fired or emission was cancelled
+        let Ok(signal_delay) = q_pending.get(indicator.pending_signal) else {
+            commands.entity(indicator_entity).try_despawn();
+            continue;
+        };
+
+        transform.scale = Vec3::splat(signal_delay.progress(time.elapsed()) * indicator.max_scale);
+    }
+}
+
+fn decay_signals(
+    mut commands: Commands,
+    mut q_decaying: Query<(Entity, &SignalDecay, &mut Transform)>,
+) {
+    for (entity, decay, mut transform) in &mut q_decaying {
+        let traveled = transform.translation.distance(decay.origin);
+        let scale = 1.0 - traveled / decay.distance.max(f32::EPSILON);
+
+        if scale <= MIN_DECAYED_SIGNAL_SCALE {
+            commands.entity(entity).try_despawn();
+        } else {
+            transform.scale = Vec3::splat(scale);
+        }
+    }
+}
+
 #[derive(Component)]
 pub struct DespawnAfter {
     timer: Timer,
@@ -179,3 +516,121 @@ fn despawn_after_system(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::test_app::{advance_fixed, headless_app};
+
+    fn live_signals(app: &mut App) -> usize {
+        app.world_mut()
+            .query_filtered::<(), With<Signal>>()
+            .iter(app.world())
+            .count()
+    }
+
+    #[test]
+    fn signals_stay_within_budget() {
+        let mut app = headless_app();
+        app.insert_resource(SignalBudget { max: 3 });
+
+        let emitter = app.world_mut().spawn(Transform::default()).id();
+        for _ in 0..5 {
+            app.world_mut()
+                .entity_mut(emitter)
+                .with_child(SignalAfterDelay {
+                    delay_ms: 0,
+                    spawn_time: Duration::ZERO,
+                    signal_size: 1.0,
+                    launch_offset: Vec3::ZERO,
+                    signal_speed: DEFAULT_SIGNAL_SPEED,
+                });
+        }
+
+        for _ in 0..4 {
+            advance_fixed(&mut app, 1);
+            assert!(live_signals(&mut app) <= 3);
+        }
+        assert_eq!(live_signals(&mut app), 3);
+    }
+
+    #[test]
+    fn same_size_signals_share_a_mesh() {
+        let mut meshes = Assets::<Mesh>::default();
+        let mut cache = SignalMeshCache::default();
+
+        let first = cache.cuboid(&mut meshes, 2.0);
+        let second = cache.cuboid(&mut meshes, 2.0);
+        let bigger = cache.cuboid(&mut meshes, 4.0);
+
+        assert_eq!(first, second);
+        assert_ne!(first, bigger);
+        assert_eq!(meshes.len(), 2);
+    }
+
+    #[test]
+    fn leaving_playing_clears_signals() {
+        let mut app = headless_app();
+        app.world_mut()
+            .spawn(Transform::default())
+            .with_child(SignalAfterDelay {
+                delay_ms: 0,
+                spawn_time: Duration::ZERO,
+                signal_size: 1.0,
+                launch_offset: Vec3::ZERO,
+                signal_speed: DEFAULT_SIGNAL_SPEED,
+            });
+        advance_fixed(&mut app, 2);
+        assert_eq!(live_signals(&mut app), 1);
+
+        app.world_mut()
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::MainMenu);
+        advance_fixed(&mut app, 2);
+        assert_eq!(live_signals(&mut app), 0);
+    }
+
+    #[test]
+    fn faster_signals_live_shorter() {
+        assert_eq!(signal_lifetime(100.0) * 2, signal_lifetime(50.0));
+        // too slow to be worth waiting on, clamped
+        assert_eq!(signal_lifetime(0.0), signal_lifetime(MIN_SIGNAL_SPEED));
+    }
+
+    #[test]
+    fn debounce_lets_one_of_two_quick_signals_through() {
+        #[derive(Resource, Default)]
+        struct DirectSignals(u32);
+
+        let mut world = World::new();
+        world.init_resource::<Time>();
+        world.init_resource::<DirectSignals>();
+
+        let target = world
+            .spawn(SignalDebounce::new(Duration::from_millis(50)))
+            .observe(default_signal_collisions)
+            .observe(
+                |_trigger: Trigger<DirectSignal>, mut direct_signals: ResMut<DirectSignals>| {
+                    direct_signals.0 += 1;
+                },
+            )
+            .id();
+
+        for _ in 0..2 {
+            let signal = world.spawn(Signal).id();
+            world.trigger_targets(
+                OnCollisionStart {
+                    collider: signal,
+                    body: None,
+                },
+                target,
+            );
+            world.flush();
+            world
+                .resource_mut::<Time>()
+                .advance_by(Duration::from_millis(1));
+        }
+
+        assert_eq!(world.resource::<DirectSignals>().0, 1);
+    }
+}
@@ -9,16 +9,20 @@ use bevy_tween::{
     bevy_time_runner::TimeSpan,
     combinator::{sequence, tween},
     prelude::{AnimationBuilderExt, EaseKind},
-    tween::{AnimationTarget, TargetAsset},
+    tween::TargetAsset,
 };
 
 use crate::{
     asset_management::{
         asset_loading::GameAssets,
-        asset_tag_components::{Immobile, StandingCubeSpitter, WeightedCube, WeightedCubeColors},
+        asset_tag_components::{
+            Immobile, MaxOwned, StandingCubeSpitter, StartsPowered, WeightedCube,
+            WeightedCubeColors,
+        },
     },
     game::signal_spitter::{dont_sink_when_held, sink_when_not_held},
     rendering::unlit_material::UnlitMaterial,
+    util::make_child_animatable,
     GameState,
 };
 
@@ -26,7 +30,7 @@ use super::{
     pressure_plate::{POWER_ANIMATION_DURATION_SEC, POWER_MATERIAL_INTENSITY},
     signals::{
         default_signal_collisions, DirectSignal, MaterialIntensityInterpolator, OwnedObjects,
-        Powered,
+        Powered, SpitterCooldown,
     },
     GameLayer,
 };
@@ -42,7 +46,12 @@ pub fn standing_cube_spitter_plugin(app: &mut App) {
 fn register_standing_cube_spitter_signals(
     mut commands: Commands,
     q_new_signal_spitter: Query<
-        (Entity, &RigidBodyColliders, Has<Immobile>),
+        (
+            Entity,
+            &RigidBodyColliders,
+            Has<Immobile>,
+            Has<StartsPowered>,
+        ),
         (
             Added<RigidBodyColliders>,
             With<StandingCubeSpitter>,
@@ -52,10 +61,15 @@ fn register_standing_cube_spitter_signals(
     mut unlit_materials: ResMut<Assets<UnlitMaterial>>,
     q_unlit_objects: Query<&MeshMaterial3d<UnlitMaterial>>,
 ) {
-    for (spitter_entity, spitter_children, is_immobile) in &q_new_signal_spitter {
+    for (spitter_entity, spitter_children, is_immobile, starts_powered) in &q_new_signal_spitter {
         for spitter_child in spitter_children.iter() {
             if let Ok(material_handle) = q_unlit_objects.get(spitter_child) {
-                let old_material = unlit_materials.get(material_handle).unwrap().clone();
+                make_child_animatable(
+                    &mut commands,
+                    spitter_child,
+                    material_handle,
+                    &mut unlit_materials,
+                );
 
                 commands
                     .entity(spitter_child)
@@ -70,15 +84,18 @@ fn register_standing_cube_spitter_signals(
                                 GameLayer::Default,
                             ],
                         ),
-                        AnimationTarget,
-                        MeshMaterial3d(unlit_materials.add(old_material)),
                     ))
                     .observe(default_signal_collisions);
             }
         }
         commands
             .entity(spitter_entity)
-            .insert((OwnedObjects::default(), SleepingDisabled))
+            .insert((
+                OwnedObjects::default(),
+                SpitterCooldown::default(),
+                SleepingDisabled,
+            ))
+            .insert_if_new(MaxOwned::default())
             .observe(cube_spitter_direct_signal)
             .observe(cube_spitter_receive_power)
             .observe(cube_spitter_lose_power);
@@ -89,6 +106,13 @@ fn register_standing_cube_spitter_signals(
                 .observe(sink_when_not_held)
                 .observe(dont_sink_when_held);
         }
+
+        if starts_powered {
+            commands
+                .entity(spitter_entity)
+                .insert(Powered)
+                .remove::<StartsPowered>();
+        }
     }
 }
 
@@ -96,20 +120,29 @@ fn register_standing_cube_spitter_signals(
 fn check_and_replace_cubes(
     mut commands: Commands,
     mut q_powered_spitters: Query<
-        (&GlobalTransform, &mut OwnedObjects),
+        (
+            &GlobalTransform,
+            &mut OwnedObjects,
+            &MaxOwned,
+            &mut SpitterCooldown,
+        ),
         (With<StandingCubeSpitter>, With<Powered>),
     >,
     q_existing_entities: Query<Entity>, // To check if owned entities still exist
     game_assets: Res<GameAssets>,
 ) {
-    for (spitter_transform, mut spitter_owned_objects) in &mut q_powered_spitters {
+    for (spitter_transform, mut spitter_owned_objects, max_owned, mut cooldown) in
+        &mut q_powered_spitters
+    {
         // Remove any owned objects that no longer exist
         spitter_owned_objects
             .0
             .retain(|&entity| q_existing_entities.contains(entity));
 
-        // If no cubes exist, spawn a new one immediately
-        if spitter_owned_objects.0.is_empty() {
+        // If we're short on cubes, spawn a new one as soon as the cooldown allows
+        if spitter_owned_objects.0.len() < max_owned.count.max(1) && cooldown.ready() {
+            cooldown.restart();
+
             let cube_id = commands
                 .spawn((
                     SceneRoot(game_assets.weighted_cube_cyan.clone()),
@@ -141,15 +174,33 @@ fn cube_spitter_direct_signal(
     trigger: Trigger<DirectSignal>,
     mut commands: Commands,
     mut q_spitter: Query<
-        (&RigidBodyColliders, &GlobalTransform, &mut OwnedObjects),
-        With<StandingCubeSpitter>,
+        (
+            &RigidBodyColliders,
+            &GlobalTransform,
+            &mut OwnedObjects,
+            &MaxOwned,
+            &mut SpitterCooldown,
+        ),
+        (With<StandingCubeSpitter>, Without<Tombstone>),
     >,
+    q_existing_entities: Query<Entity>,
     q_unlit_objects: Query<&MeshMaterial3d<UnlitMaterial>>,
     game_assets: Res<GameAssets>,
 ) {
-    if let Ok((spitter_colliders, spitter_transform, mut spitter_owned_objects)) =
-        q_spitter.get_mut(trigger.target())
+    if let Ok((
+        spitter_colliders,
+        spitter_transform,
+        mut spitter_owned_objects,
+        max_owned,
+        mut cooldown,
+    )) = q_spitter.get_mut(trigger.target())
     {
+        // ignore the signal entirely, otherwise we'd eat the current cube without replacing it
+        if !cooldown.ready() {
+            return;
+        }
+        cooldown.restart();
+
         for collider_entity in spitter_colliders.iter() {
             if let Ok(material_handle) = q_unlit_objects.get(collider_entity) {
                 commands
@@ -180,14 +231,16 @@ fn cube_spitter_direct_signal(
             }
         }
 
-        for object in spitter_owned_objects.iter() {
-            if let Ok(mut ec) = commands.get_entity(*object) {
+        // dissolved cubes don't count towards the limit
+        spitter_owned_objects
+            .0
+            .retain(|&entity| q_existing_entities.contains(entity));
+        for object in spitter_owned_objects.make_room(max_owned.count) {
+            if let Ok(mut ec) = commands.get_entity(object) {
                 ec.insert(Tombstone).despawn()
             }
         }
 
-        spitter_owned_objects.clear();
-
         let cube_id = commands
             .spawn((
                 SceneRoot(game_assets.weighted_cube_cyan.clone()),
@@ -215,8 +268,13 @@ fn cube_spitter_receive_power(
     trigger: Trigger<OnAdd, Powered>,
     mut commands: Commands,
     mut q_spitter: Query<
-        (&RigidBodyColliders, &GlobalTransform, &mut OwnedObjects),
-        With<StandingCubeSpitter>,
+        (
+            &RigidBodyColliders,
+            &GlobalTransform,
+            &mut OwnedObjects,
+            &mut SpitterCooldown,
+        ),
+        (With<StandingCubeSpitter>, Without<Tombstone>),
     >,
     q_unlit_objects: Query<&MeshMaterial3d<UnlitMaterial>>,
     unlit_materials: Res<Assets<UnlitMaterial>>,
@@ -224,7 +282,7 @@ fn cube_spitter_receive_power(
     q_children: Query<&Children, With<Collider>>,
     game_assets: Res<GameAssets>,
 ) {
-    if let Ok((spitter_children, spitter_transform, mut spitter_owned_objects)) =
+    if let Ok((spitter_children, spitter_transform, mut spitter_owned_objects, mut cooldown)) =
         q_spitter.get_mut(trigger.target())
     {
         // Animate material to powered state
@@ -259,7 +317,10 @@ fn cube_spitter_receive_power(
         }
 
         // If no cubes exist when powered, spawn one immediately
-        if spitter_owned_objects.0.is_empty() {
+        // (or let check_and_replace_cubes do it once the cooldown is up)
+        if spitter_owned_objects.0.is_empty() && cooldown.ready() {
+            cooldown.restart();
+
             let cube_id = commands
                 .spawn((
                     SceneRoot(game_assets.weighted_cube_cyan.clone()),
@@ -286,7 +347,7 @@ fn cube_spitter_receive_power(
 fn cube_spitter_lose_power(
     trigger: Trigger<OnRemove, Powered>,
     mut commands: Commands,
-    q_spitter: Query<&RigidBodyColliders, With<StandingCubeSpitter>>,
+    q_spitter: Query<&RigidBodyColliders, (With<StandingCubeSpitter>, Without<Tombstone>)>,
     q_unlit_objects: Query<&MeshMaterial3d<UnlitMaterial>>,
     unlit_materials: Res<Assets<UnlitMaterial>>,
     q_tween: Query<(), With<TimeSpan>>,
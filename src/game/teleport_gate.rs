@@ -0,0 +1,148 @@
+use std::f32::consts::FRAC_PI_4;
+
+use avian3d::prelude::{
+    ColliderOf, CollisionEventsEnabled, CollisionLayers, LinearVelocity, OnCollisionStart,
+    Position, Sensor,
+};
+use bevy::{color::palettes::tailwind::EMERALD_300, prelude::*};
+
+use crate::{
+    asset_management::asset_tag_components::TeleportGate,
+    rendering::{
+        test_material::{TestMaterial, TestMaterialExtension, TestMaterialParams},
+        unlit_material::UnlitMaterial,
+    },
+    util::clone_unlit_material,
+    GameState,
+};
+
+use super::{accessibility::GateScrollSpeed, GameLayer};
+
+const TELEPORT_GATE_SCROLL_SPEED: f32 = 0.08;
+// long enough to get clear of the destination gate's sensor
+const TELEPORT_COOLDOWN_SECS: f32 = 1.0;
+
+pub fn teleport_gate_plugin(app: &mut App) {
+    app.add_systems(FixedPreUpdate, register_teleport_gates)
+        .add_systems(
+            FixedUpdate,
+            update_teleport_cooldowns.run_if(in_state(GameState::Playing)),
+        );
+}
+
+#[derive(Component)]
+pub struct TeleportCooldown(Timer);
+
+fn register_teleport_gates(
+    mut commands: Commands,
+    q_new_gate: Query<&Children, Added<TeleportGate>>,
+    unlit_materials: ResMut<Assets<UnlitMaterial>>,
+    mut test_materials: ResMut<Assets<TestMaterial>>,
+    q_unlit_objects: Query<&MeshMaterial3d<UnlitMaterial>>,
+) {
+    for gate_children in &q_new_gate {
+        for gate_child in gate_children.iter() {
+            if let Ok(material_handle) = q_unlit_objects.get(gate_child) {
+                let mut old_material = clone_unlit_material(&unlit_materials, material_handle);
+                old_material.base.alpha_mode = AlphaMode::Blend;
+
+                let test_material = test_materials.add(TestMaterial {
+                    base: old_material.base,
+                    extension: TestMaterialExtension {
+                        params: TestMaterialParams {
+                            stripe_color: EMERALD_300.into(),
+                            stripe_frequency: 20.0,
+                            stripe_angle: -FRAC_PI_4,
+                            stripe_thickness: 0.95,
+                            scroll_speed: TELEPORT_GATE_SCROLL_SPEED,
+                        },
+                    },
+                });
+                commands
+                    .entity(gate_child)
+                    .remove::<MeshMaterial3d<UnlitMaterial>>()
+                    .insert((
+                        MeshMaterial3d(test_material),
+                        CollisionEventsEnabled,
+                        CollisionLayers::new(
+                            GameLayer::Default,
+                            [GameLayer::Device, GameLayer::Player],
+                        ),
+                        Sensor,
+                        GateScrollSpeed(TELEPORT_GATE_SCROLL_SPEED),
+                    ))
+                    .observe(handle_teleport_collisions);
+            }
+        }
+    }
+}
+
+// Held objects don't collide while held, they just follow the player through
+fn handle_teleport_collisions(
+    trigger: Trigger<OnCollisionStart>,
+    mut commands: Commands,
+    q_child_of: Query<&ChildOf>,
+    q_collider_of: Query<&ColliderOf>,
+    q_gates: Query<(Entity, &TeleportGate, &GlobalTransform)>,
+    mut q_bodies: Query<(&mut Position, Option<&mut LinearVelocity>), Without<TeleportCooldown>>,
+) {
+    let Ok(gate_child_of) = q_child_of.get(trigger.target()) else {
+        return;
+    };
+
+    let Ok((entry_gate, entry_teleport_gate, entry_transform)) = q_gates.get(gate_child_of.0)
+    else {
+        return;
+    };
+
+    let Ok(collider_of) = q_collider_of.get(trigger.collider) else {
+        return;
+    };
+
+    let Some((_, _, exit_transform)) = q_gates.iter().find(|(gate, teleport_gate, _)| {
+        *gate != entry_gate && teleport_gate.channel == entry_teleport_gate.channel
+    }) else {
+        warn!(
+            "teleport gate on channel {} has no partner",
+            entry_teleport_gate.channel
+        );
+        return;
+    };
+
+    let Ok((mut position, maybe_velocity)) = q_bodies.get_mut(collider_of.body) else {
+        return;
+    };
+
+    // Keep the body's offset and heading relative to the gate it went through
+    let entry_rotation = entry_transform.rotation();
+    let exit_rotation = exit_transform.rotation();
+    let relative_rotation = exit_rotation * entry_rotation.inverse();
+
+    let offset = position.0 - entry_transform.translation();
+    position.0 = exit_transform.translation() + relative_rotation * offset;
+
+    if let Some(mut velocity) = maybe_velocity {
+        velocity.0 = relative_rotation * velocity.0;
+    }
+
+    commands
+        .entity(collider_of.body)
+        .insert(TeleportCooldown(Timer::from_seconds(
+            TELEPORT_COOLDOWN_SECS,
+            TimerMode::Once,
+        )));
+}
+
+fn update_teleport_cooldowns(
+    mut commands: Commands,
+    mut q_cooldowns: Query<(Entity, &mut TeleportCooldown)>,
+    time: Res<Time>,
+) {
+    for (entity, mut cooldown) in &mut q_cooldowns {
+        cooldown.0.tick(time.delta());
+
+        if cooldown.0.finished() {
+            commands.entity(entity).try_remove::<TeleportCooldown>();
+        }
+    }
+}
@@ -0,0 +1,159 @@
+// Headless App for testing gameplay logic. No window, renderer or player controller, just
+// gameplay_logic_plugins on top of MinimalPlugins with physics stepping at the fixed rate.
+use std::time::Duration;
+
+use avian3d::prelude::{Collider, CollisionLayers, LayerMask, RigidBody};
+use bevy::{prelude::*, scene::ScenePlugin, state::app::StatesPlugin, time::TimeUpdateStrategy};
+use bevy_tween::DefaultTweenPlugins;
+
+use crate::{
+    asset_management::{
+        asset_loading::{GameAssets, GameSounds},
+        asset_tag_components::{WeightedCube, WeightedCubeColors},
+    },
+    rendering::{
+        test_material::TestMaterial,
+        unlit_material::{unlit_material_tweens_plugin, UnlitMaterial},
+    },
+    GameState,
+};
+
+use super::{
+    gameplay_logic_plugins,
+    player::{Player, RightHand},
+    GameLayer,
+};
+
+// Starts out already in GameState::Playing
+pub fn headless_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin::default(),
+        ScenePlugin,
+        StatesPlugin,
+        TransformPlugin,
+        DefaultTweenPlugins,
+        unlit_material_tweens_plugin,
+        gameplay_logic_plugins,
+    ))
+    .init_asset::<Mesh>()
+    .init_asset::<UnlitMaterial>()
+    .init_asset::<TestMaterial>()
+    .init_resource::<GameAssets>()
+    .init_resource::<GameSounds>()
+    .init_state::<GameState>()
+    // every update moves time forward by exactly one fixed step
+    .insert_resource(TimeUpdateStrategy::ManualDuration(
+        Time::<Fixed>::default().timestep(),
+    ));
+
+    app.world_mut()
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::Playing);
+    // the first update has no time passing, so it only gets us into Playing
+    app.update();
+
+    app
+}
+
+// One FixedUpdate (and physics step) per update, after the first
+pub fn advance_fixed(app: &mut App, steps: u32) {
+    for _ in 0..steps {
+        app.update();
+    }
+}
+
+pub fn advance_secs(app: &mut App, secs: f32) {
+    let step = Time::<Fixed>::default().timestep();
+    advance_fixed(
+        app,
+        (Duration::from_secs_f32(secs).as_secs_f64() / step.as_secs_f64()).ceil() as u32,
+    );
+}
+
+// Just enough of a player to stand on plates and carry things, nothing drives it
+pub fn spawn_test_player(app: &mut App, translation: Vec3) -> Entity {
+    app.world_mut()
+        .spawn((
+            Player,
+            RightHand::default(),
+            Transform::from_translation(translation),
+            RigidBody::Kinematic,
+            Collider::capsule(1.5, 8.0),
+            CollisionLayers::new(GameLayer::Player, LayerMask::ALL),
+        ))
+        .id()
+}
+
+// A device laid out like it comes out of a level scene: the tag on the parent, and a mesh child
+// with its own unlit material and a collider on the Device layer
+pub fn spawn_device(app: &mut App, tag: impl Bundle, translation: Vec3) -> Entity {
+    let material = app
+        .world_mut()
+        .resource_mut::<Assets<UnlitMaterial>>()
+        .add(test_unlit_material());
+
+    app.world_mut()
+        .spawn((tag, Transform::from_translation(translation)))
+        .with_child((
+            Transform::default(),
+            MeshMaterial3d(material),
+            Collider::cuboid(2.5, 2.5, 2.5),
+            CollisionLayers::new(GameLayer::Device, LayerMask::ALL),
+        ))
+        .id()
+}
+
+// A free cube, registered like one spat out in a level (own material, Device collider child)
+pub fn spawn_test_cube(app: &mut App, translation: Vec3) -> Entity {
+    spawn_device(
+        app,
+        (
+            WeightedCube {
+                color: WeightedCubeColors::Cyan,
+            },
+            RigidBody::Dynamic,
+        ),
+        translation,
+    )
+}
+
+// Devices that find each other as siblings (plates and their pads, doors and their poles) need
+// a shared parent
+pub fn spawn_device_group(app: &mut App, devices: &[Entity]) -> Entity {
+    app.world_mut()
+        .spawn(Transform::default())
+        .add_children(devices)
+        .id()
+}
+
+pub fn test_unlit_material() -> UnlitMaterial {
+    let mut material = UnlitMaterial {
+        base: StandardMaterial::default(),
+        extension: default(),
+    };
+    material.extension.params.intensity = 1.0;
+    material.extension.params.alpha = 1.0;
+    material
+}
+
+// Intensity of the material on the first child of `entity` that has one
+pub fn child_intensity(app: &App, entity: Entity) -> f32 {
+    let world = app.world();
+    let children = world
+        .get::<Children>(entity)
+        .expect("entity has no children");
+    let material_handle = children
+        .iter()
+        .find_map(|child| world.get::<MeshMaterial3d<UnlitMaterial>>(child))
+        .expect("no child with an unlit material");
+
+    world
+        .resource::<Assets<UnlitMaterial>>()
+        .get(material_handle)
+        .expect("material missing")
+        .extension
+        .params
+        .intensity
+}
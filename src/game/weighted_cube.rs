@@ -1,28 +1,34 @@
-use std::time::Duration;
+use std::{f32::consts::TAU, time::Duration};
 
 use avian3d::prelude::{
-    Collider, ColliderOf, CollisionEventsEnabled, CollisionLayers, OnCollisionStart,
-    RigidBodyColliders, SleepingDisabled, SpatialQuery, SpatialQueryFilter,
+    AngularVelocity, Collider, ColliderOf, CollisionEventsEnabled, CollisionLayers, LinearVelocity,
+    OnCollisionStart, RigidBody, RigidBodyColliders, SleepingDisabled, SpatialQuery,
+    SpatialQueryFilter,
 };
 use bevy::prelude::*;
 use bevy_tween::{
     bevy_time_runner::TimeSpan,
     combinator::tween,
     prelude::{AnimationBuilderExt, EaseKind},
-    tween::{AnimationTarget, TargetAsset},
+    tween::TargetAsset,
 };
 
 use crate::{
-    asset_management::asset_tag_components::{Inert, WeightedCube},
+    asset_management::asset_tag_components::{DischargeRadius, FreezeOnPower, Inert, WeightedCube},
     rendering::unlit_material::UnlitMaterial,
+    util::make_child_animatable,
     GameState,
 };
 
 use super::{
+    audio::{cube_impact_audio, CubeImpactAudio},
     door::PoweredTimer,
     player::Held,
     pressure_plate::{PoweredBy, POWER_ANIMATION_DURATION_SEC, POWER_MATERIAL_INTENSITY},
-    signals::{DirectSignal, MaterialIntensityInterpolator, Powered, Signal},
+    signals::{
+        signal_debounce_allows, DirectSignal, MaterialIntensityInterpolator, Powered, Signal,
+        SignalDebounce,
+    },
     standing_cube_spitter::Tombstone,
     GameLayer,
 };
@@ -41,27 +47,55 @@ impl CubeDischarge {
 }
 
 // Constants for cube discharge detection
+// Used when the cube has no DischargeRadius. The detection sphere is a transient spatial query, not a
+// collider, so collider_distance_system can't disable it; only targets further than its max_distance
+// (far beyond any sane radius) could be missed.
 const CUBE_DISCHARGE_RADIUS: f32 = 20.0;
 
 pub fn cube_plugin(app: &mut App) {
-    app.add_systems(FixedPreUpdate, (register_cube_signals,))
+    app.init_resource::<StackInstability>()
+        .add_systems(FixedPreUpdate, (register_cube_signals,))
         .add_systems(
             FixedUpdate,
             (
-                cube_receive_power,
                 cube_discharge_detection,
                 update_cube_discharge_timers,
                 update_powering_up_timers,
-                fix_stuck_powered_cubes,
+                destabilize_tall_stacks
+                    .run_if(|instability: Res<StackInstability>| instability.enabled),
             )
                 .run_if(in_state(GameState::Playing)),
         );
 }
 
+// Puzzle tuning: without this the physics will happily hold up a tower of cubes as tall as you
+// like. Cubes sitting on too many others get nudged around until the tower comes down.
+#[derive(Resource)]
+pub struct StackInstability {
+    pub enabled: bool,
+    // how many cubes a cube can sit on top of before it starts to wobble
+    pub max_stable_height: usize,
+    // angular acceleration, in radians per second squared, around a random horizontal axis
+    pub wobble_strength: f32,
+}
+
+impl Default for StackInstability {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_stable_height: 3,
+            wobble_strength: 4.0,
+        }
+    }
+}
+
+// from a cube's center down to the top of one it's sitting on, with some slack
+const STACK_PROBE_DISTANCE: f32 = 8.0;
+
 fn cube_discharge_detection(
     mut commands: Commands,
     q_cubes: Query<
-        (Entity, &GlobalTransform),
+        (Entity, &GlobalTransform, Option<&DischargeRadius>),
         (
             With<WeightedCube>,
             With<Powered>,
@@ -75,9 +109,12 @@ fn cube_discharge_detection(
     q_powered: Query<(), With<Powered>>,
     q_inert: Query<(), With<Inert>>,
 ) {
-    for (cube_entity, cube_transform) in q_cubes.iter() {
+    for (cube_entity, cube_transform, maybe_discharge_radius) in q_cubes.iter() {
         // Create spherical detection shape
-        let detection_shape = Collider::sphere(CUBE_DISCHARGE_RADIUS);
+        let discharge_radius = maybe_discharge_radius
+            .map(|discharge_radius| discharge_radius.radius)
+            .unwrap_or(CUBE_DISCHARGE_RADIUS);
+        let detection_shape = Collider::sphere(discharge_radius);
         let cube_position = cube_transform.translation();
 
         // Find overlapping entities
@@ -132,18 +169,25 @@ fn cube_discharge_detection(
 fn cube_consume_signal(
     trigger: Trigger<OnCollisionStart>,
     mut commands: Commands,
+    time: Res<Time>,
     q_signals: Query<(), With<Signal>>,
     q_powered: Query<(), (With<Powered>, Without<PoweredTimer>)>,
     q_discharging: Query<(), With<CubeDischarge>>, // Check if cube is in cooldown
     q_collider_of: Query<&ColliderOf>,
+    mut q_debounce: Query<&mut SignalDebounce>,
 ) {
     if q_signals.contains(trigger.collider) {
         if let Ok(collider_of) = q_collider_of.get(trigger.target()) {
-            if !q_powered.contains(collider_of.body) && !q_discharging.contains(collider_of.body) {
+            if !q_powered.contains(collider_of.body)
+                && !q_discharging.contains(collider_of.body)
+                && signal_debounce_allows(&mut q_debounce, collider_of.body, time.elapsed())
+            {
                 commands.entity(collider_of.body).trigger(DirectSignal);
                 commands.entity(trigger.collider).despawn();
             }
-        } else if !q_powered.contains(trigger.target()) && !q_discharging.contains(trigger.target())
+        } else if !q_powered.contains(trigger.target())
+            && !q_discharging.contains(trigger.target())
+            && signal_debounce_allows(&mut q_debounce, trigger.target(), time.elapsed())
         {
             commands.entity(trigger.target()).trigger(DirectSignal);
             commands.entity(trigger.collider).despawn();
@@ -177,6 +221,19 @@ pub struct PoweringUp {
     timer: Timer,
 }
 
+// Triggered on a cube once its power-up ramp has finished and it's at full brightness
+#[derive(Event)]
+pub struct CubeFullyCharged;
+
+// Puts a cube back to a clean unpowered state. Dropping Powered runs cube_lose_power, which kills
+// any in-flight intensity tween and fades back to 1.0. PoweredBy and the discharge cooldown go too,
+// so nothing re-powers the cube once the cooldown would have ended.
+pub fn fully_discharge_cube(commands: &mut Commands, cube_entity: Entity) {
+    commands
+        .entity(cube_entity)
+        .try_remove::<(Powered, PoweredBy, PoweringUp, CubeDischarge)>();
+}
+
 fn cube_direct_signal(
     trigger: Trigger<DirectSignal>,
     mut commands: Commands,
@@ -191,18 +248,33 @@ fn cube_direct_signal(
     }
 }
 
+// This is an observer rather than an Added<Powered> system so power transitions are applied in
+// the same order as the Powered inserts/removes; otherwise a removal flushed before the power-up
+// tween could leave a cube glowing without Powered.
 fn cube_receive_power(
+    trigger: Trigger<OnAdd, Powered>,
     mut commands: Commands,
     q_powered_cube: Query<
-        (Entity, &RigidBodyColliders, Has<PoweredBy>),
-        (With<WeightedCube>, Added<Powered>, Without<Tombstone>),
+        (
+            Entity,
+            &RigidBodyColliders,
+            Has<PoweredBy>,
+            Has<FreezeOnPower>,
+        ),
+        (With<WeightedCube>, Without<Tombstone>),
     >,
     q_unlit_objects: Query<&MeshMaterial3d<UnlitMaterial>>,
     unlit_materials: Res<Assets<UnlitMaterial>>,
     q_tween: Query<(), With<TimeSpan>>,
     q_children: Query<&Children, With<Collider>>,
 ) {
-    for (cube_entity, powered_cube_colliders, is_powered_by) in &q_powered_cube {
+    if let Ok((cube_entity, powered_cube_colliders, is_powered_by, freezes)) =
+        q_powered_cube.get(trigger.target())
+    {
+        if freezes {
+            set_cube_frozen(&mut commands, cube_entity, true);
+        }
+
         if !is_powered_by {
             commands.entity(cube_entity).try_insert(PoweringUp {
                 timer: Timer::from_seconds(POWER_ANIMATION_DURATION_SEC, TimerMode::Once),
@@ -245,19 +317,29 @@ fn cube_receive_power(
 fn cube_lose_power(
     trigger: Trigger<OnRemove, Powered>,
     mut commands: Commands,
-    q_cube: Query<&RigidBodyColliders, (With<WeightedCube>, Without<Tombstone>)>,
+    q_cube: Query<
+        (&RigidBodyColliders, Has<FreezeOnPower>),
+        (With<WeightedCube>, Without<Tombstone>),
+    >,
     q_unlit_objects: Query<&MeshMaterial3d<UnlitMaterial>>,
     unlit_materials: Res<Assets<UnlitMaterial>>,
     q_tween: Query<(), With<TimeSpan>>,
     q_children: Query<&Children, With<Collider>>,
 ) {
-    if let Ok(cube_colliders) = q_cube.get(trigger.target()) {
+    if let Ok((cube_colliders, freezes)) = q_cube.get(trigger.target()) {
+        // a cube that lost power mid power-up shouldn't keep blocking discharge detection
+        commands.entity(trigger.target()).try_remove::<PoweringUp>();
+
+        if freezes {
+            set_cube_frozen(&mut commands, trigger.target(), false);
+        }
+
         for collider_entity in cube_colliders.iter() {
             // Clear existing tweens first
             if let Ok(collider_children) = q_children.get(collider_entity) {
                 for child in collider_children.iter() {
                     if q_tween.contains(child) {
-                        commands.entity(child).despawn();
+                        commands.entity(child).try_despawn();
                     }
                 }
             }
@@ -285,6 +367,21 @@ fn cube_lose_power(
     }
 }
 
+// Kinematic keeps the colliders (and so discharge detection) working while ignoring gravity.
+// Velocity is cleared both ways so the cube doesn't keep drifting once frozen, or get flung
+// by whatever it had built up when it thaws.
+fn set_cube_frozen(commands: &mut Commands, cube: Entity, frozen: bool) {
+    let rigid_body = if frozen {
+        RigidBody::Kinematic
+    } else {
+        RigidBody::Dynamic
+    };
+
+    commands
+        .entity(cube)
+        .try_insert((rigid_body, LinearVelocity::ZERO, AngularVelocity::ZERO));
+}
+
 fn register_cube_signals(
     mut commands: Commands,
     q_new_cube: Query<
@@ -299,13 +396,23 @@ fn register_cube_signals(
     for (cube_entity, cube_children) in &q_new_cube {
         commands
             .entity(cube_entity)
-            .insert(SleepingDisabled)
+            .insert((
+                SleepingDisabled,
+                SignalDebounce::default(),
+                CubeImpactAudio::default(),
+            ))
             .observe(cube_direct_signal)
+            .observe(cube_receive_power)
             .observe(cube_lose_power);
 
         for cube_child in cube_children.iter() {
             if let Ok(material_handle) = q_unlit_objects.get(cube_child) {
-                let old_material = unlit_materials.get(material_handle).unwrap().clone();
+                make_child_animatable(
+                    &mut commands,
+                    cube_child,
+                    material_handle,
+                    &mut unlit_materials,
+                );
 
                 commands
                     .entity(cube_child)
@@ -320,15 +427,77 @@ fn register_cube_signals(
                                 GameLayer::Default,
                             ],
                         ),
-                        AnimationTarget,
-                        MeshMaterial3d(unlit_materials.add(old_material)),
                     ))
-                    .observe(cube_consume_signal);
+                    .observe(cube_consume_signal)
+                    .observe(cube_impact_audio);
             }
         }
     }
 }
 
+fn destabilize_tall_stacks(
+    spatial_query: SpatialQuery,
+    instability: Res<StackInstability>,
+    mut q_cubes: Query<
+        (Entity, &RigidBody, &mut AngularVelocity),
+        (With<WeightedCube>, Without<Held>),
+    >,
+    q_cube_colliders: Query<(&GlobalTransform, &RigidBodyColliders), With<WeightedCube>>,
+    q_collider_of: Query<&ColliderOf>,
+    time: Res<Time>,
+) {
+    for (cube_entity, rigid_body, mut angular_velocity) in &mut q_cubes {
+        if *rigid_body != RigidBody::Dynamic {
+            continue;
+        }
+
+        // walk down the stack one cube at a time
+        let mut cubes_below = 0;
+        let mut current = cube_entity;
+        while cubes_below <= instability.max_stable_height {
+            let Ok((current_transform, current_colliders)) = q_cube_colliders.get(current) else {
+                break;
+            };
+
+            let Some(hit) = spatial_query.cast_ray(
+                current_transform.translation(),
+                Dir3::NEG_Y,
+                STACK_PROBE_DISTANCE,
+                true,
+                &SpatialQueryFilter::default()
+                    .with_mask([GameLayer::Device])
+                    .with_excluded_entities(current_colliders.iter()),
+            ) else {
+                break;
+            };
+
+            let Ok(below) = q_collider_of.get(hit.entity) else {
+                break;
+            };
+
+            if !q_cube_colliders.contains(below.body) {
+                break;
+            }
+
+            cubes_below += 1;
+            current = below.body;
+        }
+
+        if cubes_below <= instability.max_stable_height {
+            continue;
+        }
+
+        // no rng around, but this is plenty random for a wobble
+        let noise = ((cube_entity.index() as f32 * 12.9898 + time.elapsed_secs() * 78.233).sin()
+            * 43758.547)
+            .fract();
+        let angle = noise * TAU;
+        let axis = Vec3::new(angle.cos(), 0.0, angle.sin());
+
+        angular_velocity.0 += axis * instability.wobble_strength * time.delta_secs();
+    }
+}
+
 fn update_powering_up_timers(
     mut commands: Commands,
     time: Res<Time>,
@@ -339,50 +508,84 @@ fn update_powering_up_timers(
 
         if powering_up.timer.finished() {
             commands.entity(cube_entity).try_remove::<PoweringUp>();
+            commands.entity(cube_entity).trigger(CubeFullyCharged);
         }
     }
 }
 
-// i hate gamejams i hate gamejams i hate gamejams
-fn fix_stuck_powered_cubes(
-    mut commands: Commands,
-    q_unpowered_cubes: Query<
-        &RigidBodyColliders,
-        (
-            With<WeightedCube>,
-            Without<Powered>,
-            Without<Tombstone>,
-            Without<PoweringUp>,
-        ),
-    >,
-    q_unlit_objects: Query<&MeshMaterial3d<UnlitMaterial>>,
-    unlit_materials: Res<Assets<UnlitMaterial>>,
-) {
-    for cube_colliders in &q_unpowered_cubes {
-        for collider_entity in cube_colliders.iter() {
-            if let Ok(material_handle) = q_unlit_objects.get(collider_entity) {
-                if let Some(material) = unlit_materials.get(material_handle) {
-                    let current_intensity = material.extension.params.intensity;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::dissolve_gate::{dissolve_or_respawn, Dissolveable};
+    use crate::game::test_app::{
+        advance_fixed, advance_secs, child_intensity, headless_app, spawn_test_cube,
+    };
+
+    #[test]
+    fn rapid_power_toggles_settle_unpowered() {
+        let mut app = headless_app();
+        let cube = spawn_test_cube(&mut app, Vec3::ZERO);
+        advance_fixed(&mut app, 2);
+
+        for _ in 0..5 {
+            app.world_mut().entity_mut(cube).insert(Powered);
+            advance_fixed(&mut app, 1);
+            app.world_mut().entity_mut(cube).remove::<Powered>();
+            advance_fixed(&mut app, 1);
+        }
+        advance_secs(&mut app, POWER_ANIMATION_DURATION_SEC * 2.0);
 
-                    // If intensity is greater than 1, tween it down to 1
-                    if current_intensity > 1.0 {
-                        let intensity_ratio =
-                            (current_intensity - 1.0) / (POWER_MATERIAL_INTENSITY - 1.0);
-                        let duration_secs = POWER_ANIMATION_DURATION_SEC * intensity_ratio.max(0.1);
-
-                        commands.entity(collider_entity).animation().insert(tween(
-                            Duration::from_secs_f32(duration_secs),
-                            EaseKind::CubicOut,
-                            TargetAsset::Asset(material_handle.clone_weak()).with(
-                                MaterialIntensityInterpolator {
-                                    start: current_intensity,
-                                    end: 1.0,
-                                },
-                            ),
-                        ));
-                    }
-                }
-            }
+        assert!(!app.world().entity(cube).contains::<Powered>());
+        assert!((child_intensity(&app, cube) - 1.0).abs() < 0.01);
+    }
+
+    // Powered's observers run before dissolve_or_respawn gets the Tombstone on, so they must not
+    // leave tween commands behind for a cube that's already gone
+    #[test]
+    fn despawning_a_cube_the_frame_it_is_powered() {
+        let mut app = headless_app();
+        let cube = spawn_test_cube(&mut app, Vec3::ZERO);
+        advance_fixed(&mut app, 2);
+
+        {
+            let world = app.world_mut();
+            let mut commands = world.commands();
+            commands.entity(cube).insert(Powered);
+            dissolve_or_respawn(
+                &mut commands,
+                cube,
+                &Dissolveable {
+                    respawn_transform: None,
+                },
+            );
         }
+        app.world_mut().flush();
+        advance_fixed(&mut app, 4);
+
+        assert!(app.world().get_entity(cube).is_err());
+    }
+
+    #[test]
+    fn freeze_on_power_flips_the_rigid_body() {
+        let mut app = headless_app();
+        let cube = spawn_test_cube(&mut app, Vec3::ZERO);
+        app.world_mut()
+            .entity_mut(cube)
+            .insert(FreezeOnPower { unused: false });
+        advance_fixed(&mut app, 2);
+
+        app.world_mut().entity_mut(cube).insert(Powered);
+        advance_fixed(&mut app, 1);
+        assert_eq!(
+            app.world().get::<RigidBody>(cube),
+            Some(&RigidBody::Kinematic)
+        );
+
+        app.world_mut().entity_mut(cube).remove::<Powered>();
+        advance_fixed(&mut app, 1);
+        assert_eq!(
+            app.world().get::<RigidBody>(cube),
+            Some(&RigidBody::Dynamic)
+        );
     }
 }
@@ -1,7 +1,10 @@
-use asset_management::asset_plugins;
+use asset_management::{
+    asset_loading::{insert_collider_from_mesh, PendingCollider},
+    asset_plugins,
+};
 use avian3d::prelude::{
-    Collider, CollisionLayers, PhysicsGizmos, RigidBody, RigidBodyColliders, RigidBodyDisabled,
-    RotationInterpolation,
+    Collider, ColliderAabb, CollisionLayers, PhysicsGizmos, RigidBody, RigidBodyColliders,
+    RigidBodyDisabled, RotationInterpolation,
 };
 #[cfg(feature = "dev")]
 use bevy::color::palettes::css::GREEN;
@@ -14,6 +17,7 @@ use bevy::{
         fxaa::Fxaa,
     },
     prelude::*,
+    render::primitives::Aabb,
 };
 #[cfg(feature = "dev")]
 use bevy_dev_tools::fps_overlay::{FpsOverlayConfig, FpsOverlayPlugin};
@@ -21,19 +25,28 @@ use bevy_framepace::FramepacePlugin;
 #[cfg(feature = "dev")]
 use bevy_inspector_egui::{bevy_egui::EguiPlugin, quick::WorldInspectorPlugin};
 use bevy_tween::DefaultTweenPlugins;
+#[cfg(feature = "dev")]
+use dev::dev_plugins;
 use game::gameplay_plugins;
 use rendering::{
     render_plugins, section_color_postprocess::PostProcessSettings,
-    section_color_prepass::SectionsPrepass,
+    section_color_prepass::SectionsPrepass, unlit_material::UnlitMaterial,
 };
 use ui::ui_plugins;
 
-use crate::game::{dissolve_gate::Dissolveable, player::Player};
+use crate::game::{
+    dissolve_gate::{dissolve_or_respawn, Dissolveable},
+    player::{HeadbobState, Player, BASE_FOV},
+    GameLayer,
+};
 
 mod asset_management;
+#[cfg(feature = "dev")]
+mod dev;
 mod game;
 mod rendering;
 mod ui;
+mod util;
 
 fn main() -> AppExit {
     App::new()
@@ -69,6 +82,8 @@ fn main() -> AppExit {
             render_plugins,
             ui_plugins,
             gameplay_plugins,
+            #[cfg(feature = "dev")]
+            dev_plugins,
             FramepacePlugin,
         ))
         .insert_resource(ClearColor(Color::srgb(1.0, 1.0, 1.0)))
@@ -87,7 +102,8 @@ fn main() -> AppExit {
             FixedPreUpdate,
             (
                 rigid_body_distance_system,
-                /*collider_distance_system,*/ dissolve_system,
+                collider_distance_system,
+                dissolve_system,
             )
                 .chain(),
         )
@@ -118,17 +134,20 @@ fn spawn_main_camera(mut commands: Commands) {
 
     commands.spawn((
         MainCamera,
+        HeadbobState::default(),
         Camera3d::default(),
         Camera {
             hdr: true,
             ..default()
         },
         SectionsPrepass,
+        // UI stays on this one even when a dev camera is drawn on top
+        IsDefaultUiCamera,
         bloom,
         //VignetteSettings::new(0.5, 1.1, Color::BLACK, 1.0),
         RotationInterpolation,
         Projection::Perspective(PerspectiveProjection {
-            fov: 1.396,
+            fov: BASE_FOV,
             ..default()
         }),
         Msaa::Off,
@@ -210,6 +229,8 @@ pub struct ColliderDistanceConfig {
     pub max_distance: f32,
     // Optional: hysteresis to prevent flickering when entities are right at the boundary
     pub hysteresis: f32,
+    // Defer building static mesh colliders until the player first comes within max_distance
+    pub lazy_generation: bool,
 }
 
 impl Default for ColliderDistanceConfig {
@@ -217,6 +238,7 @@ impl Default for ColliderDistanceConfig {
         Self {
             max_distance: 350.0,
             hysteresis: 15.0, // Bodies re-enable at max_distance, disable at max_distance + hysteresis
+            lazy_generation: false,
         }
     }
 }
@@ -226,19 +248,43 @@ pub struct DisabledByDistance {
     pub old_layers: CollisionLayers,
 }
 
+// Devices that got switched off for being far away dim down so it's obvious they won't react,
+// remembering how bright they were for when they come back
+#[derive(Component)]
+pub struct DimmedByDistance {
+    pub old_intensity: f32,
+}
+
+const DISTANCE_DIMMED_INTENSITY: f32 = 0.5;
+
 pub fn collider_distance_system(
     mut commands: Commands,
     config: Res<ColliderDistanceConfig>,
     player_query: Query<&GlobalTransform, With<Player>>,
+    pending_query: Query<
+        (
+            Entity,
+            &GlobalTransform,
+            &Mesh3d,
+            &PendingCollider,
+            Option<&Aabb>,
+        ),
+        Without<Player>,
+    >,
+    meshes: Res<Assets<Mesh>>,
     mut collider_query: Query<
         (
             Entity,
             &GlobalTransform,
+            Option<&ColliderAabb>,
             &mut CollisionLayers,
             Option<&DisabledByDistance>,
+            Option<&DimmedByDistance>,
+            Option<&MeshMaterial3d<UnlitMaterial>>,
         ),
         (With<Collider>, Without<Player>),
     >,
+    mut unlit_materials: ResMut<Assets<UnlitMaterial>>,
 ) {
     let player_transform = match player_query.single() {
         Ok(transform) => transform,
@@ -247,8 +293,30 @@ pub fn collider_distance_system(
 
     let player_pos = player_transform.translation();
 
-    for (entity, transform, mut layers, disabled_marker) in collider_query.iter_mut() {
-        let distance = player_pos.distance(transform.translation());
+    // colliders deferred by lazy_generation get built the first time the player is in range
+    for (entity, transform, mesh_handle, pending, maybe_aabb) in pending_query.iter() {
+        if distance_to_mesh(player_pos, transform, maybe_aabb) > config.max_distance {
+            continue;
+        }
+
+        // a mesh that hasn't finished loading keeps its marker and gets another go next tick
+        if let Some(mesh) = meshes.get(&mesh_handle.0) {
+            insert_collider_from_mesh(&mut commands, entity, mesh, pending.use_trimesh, true);
+            commands.entity(entity).remove::<PendingCollider>();
+        }
+    }
+
+    for (
+        entity,
+        transform,
+        maybe_collider_aabb,
+        mut layers,
+        disabled_marker,
+        dimmed_marker,
+        maybe_material,
+    ) in collider_query.iter_mut()
+    {
+        let distance = distance_to_collider(player_pos, transform, maybe_collider_aabb);
         let is_currently_disabled_by_us = disabled_marker.is_some();
 
         let should_be_disabled = distance > config.max_distance + config.hysteresis;
@@ -259,6 +327,15 @@ pub fn collider_distance_system(
                 *layers = marker.old_layers;
                 commands.entity(entity).remove::<DisabledByDistance>();
             }
+
+            if let Some(dimmed) = dimmed_marker {
+                if let Some(material) =
+                    maybe_material.and_then(|handle| unlit_materials.get_mut(handle))
+                {
+                    material.extension.params.intensity = dimmed.old_intensity;
+                }
+                commands.entity(entity).remove::<DimmedByDistance>();
+            }
         } else if should_be_disabled
             && !is_currently_disabled_by_us
             && *layers != CollisionLayers::NONE
@@ -267,31 +344,173 @@ pub fn collider_distance_system(
             commands.entity(entity).insert(DisabledByDistance {
                 old_layers: *layers,
             });
+
+            // world geometry just quietly stops colliding, only devices get the cue
+            if layers.memberships.has_all(GameLayer::Device) {
+                if let Some(material) =
+                    maybe_material.and_then(|handle| unlit_materials.get_mut(handle))
+                {
+                    commands.entity(entity).insert(DimmedByDistance {
+                        old_intensity: material.extension.params.intensity,
+                    });
+                    material.extension.params.intensity = DISTANCE_DIMMED_INTENSITY;
+                }
+            }
+
             *layers = CollisionLayers::NONE;
         }
     }
 }
 
+// Big meshes like floors have their origin nowhere near where the player is standing on them,
+// so measure to the closest point of the bounds when we have them
+fn distance_to_mesh(
+    player_pos: Vec3,
+    transform: &GlobalTransform,
+    maybe_aabb: Option<&Aabb>,
+) -> f32 {
+    let Some(aabb) = maybe_aabb else {
+        return player_pos.distance(transform.translation());
+    };
+
+    let local_player_pos = transform.affine().inverse().transform_point3(player_pos);
+    let closest = local_player_pos.clamp(aabb.min().into(), aabb.max().into());
+    player_pos.distance(transform.transform_point(closest))
+}
+
+// Same idea for colliders, so a floor doesn't switch off under whatever is sitting on it
+fn distance_to_collider(
+    player_pos: Vec3,
+    transform: &GlobalTransform,
+    maybe_collider_aabb: Option<&ColliderAabb>,
+) -> f32 {
+    let Some(collider_aabb) = maybe_collider_aabb else {
+        return player_pos.distance(transform.translation());
+    };
+
+    player_pos.distance(player_pos.clamp(collider_aabb.min, collider_aabb.max))
+}
+
 const DISSOLVE_Y_THRESHOLD: f32 = -50.0;
 
 // Disabling collision at distance will sometimes drop stuff through the floor
 pub fn dissolve_system(
     mut commands: Commands,
-    query: Query<(Entity, &GlobalTransform, Option<&RigidBodyColliders>), With<Dissolveable>>,
+    query: Query<(
+        Entity,
+        &GlobalTransform,
+        &Dissolveable,
+        Option<&RigidBodyColliders>,
+    )>,
     q_child_transforms: Query<&GlobalTransform, Without<RigidBodyColliders>>,
 ) {
-    for (entity, transform, maybe_colliders) in query.iter() {
-        if transform.translation().y < DISSOLVE_Y_THRESHOLD {
-            commands.entity(entity).try_despawn();
-        } else if let Some(colliders) = maybe_colliders {
-            for collider in colliders.iter() {
-                if let Ok(child_transform) = q_child_transforms.get(collider) {
-                    if child_transform.translation().y < DISSOLVE_Y_THRESHOLD {
-                        commands.entity(entity).try_despawn();
-                        break;
-                    }
-                }
-            }
+    for (entity, transform, dissolveable, maybe_colliders) in query.iter() {
+        let fell_out = transform.translation().y < DISSOLVE_Y_THRESHOLD
+            || maybe_colliders.is_some_and(|colliders| {
+                colliders.iter().any(|collider| {
+                    q_child_transforms
+                        .get(collider)
+                        .is_ok_and(|child_transform| {
+                            child_transform.translation().y < DISSOLVE_Y_THRESHOLD
+                        })
+                })
+            });
+
+        if fell_out {
+            dissolve_or_respawn(&mut commands, entity, dissolveable);
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::test_app::{advance_fixed, headless_app, spawn_test_player};
+
+    #[test]
+    fn lazy_collider_waits_for_the_player() {
+        let mut app = headless_app();
+        app.init_resource::<ColliderDistanceConfig>()
+            .add_systems(FixedPreUpdate, collider_distance_system);
+
+        let mesh = app
+            .world_mut()
+            .resource_mut::<Assets<Mesh>>()
+            .add(Cuboid::new(4., 4., 4.));
+        let far_mesh = app
+            .world_mut()
+            .spawn((
+                Mesh3d(mesh),
+                Transform::from_xyz(1000., 0., 0.),
+                PendingCollider { use_trimesh: false },
+            ))
+            .id();
+        let player = spawn_test_player(&mut app, Vec3::ZERO);
+
+        advance_fixed(&mut app, 4);
+        assert!(!app.world().entity(far_mesh).contains::<Collider>());
+
+        app.world_mut()
+            .entity_mut(player)
+            .insert(Transform::from_xyz(900., 0., 0.));
+        advance_fixed(&mut app, 4);
+        assert!(app.world().entity(far_mesh).contains::<Collider>());
+        assert!(!app.world().entity(far_mesh).contains::<PendingCollider>());
+    }
+
+    #[test]
+    fn big_floor_stays_on_under_the_player() {
+        let mut app = headless_app();
+        app.init_resource::<ColliderDistanceConfig>()
+            .add_systems(FixedPreUpdate, collider_distance_system);
+
+        let floor = app
+            .world_mut()
+            .spawn((
+                Transform::default(),
+                RigidBody::Static,
+                Collider::cuboid(1000., 2., 1000.),
+                CollisionLayers::new(GameLayer::Device, [GameLayer::Device, GameLayer::Player]),
+            ))
+            .id();
+        // well past max_distance from the floor's origin, but standing right on it
+        spawn_test_player(&mut app, Vec3::new(450., 10., 0.));
+
+        advance_fixed(&mut app, 4);
+        assert!(!app.world().entity(floor).contains::<DisabledByDistance>());
+    }
+
+    #[test]
+    fn lazy_collider_waits_for_its_mesh() {
+        let mut app = headless_app();
+        app.init_resource::<ColliderDistanceConfig>()
+            .add_systems(FixedPreUpdate, collider_distance_system);
+
+        let mesh = app.world_mut().resource::<Assets<Mesh>>().reserve_handle();
+        let pending_mesh = app
+            .world_mut()
+            .spawn((
+                Mesh3d(mesh.clone()),
+                Transform::default(),
+                PendingCollider { use_trimesh: false },
+            ))
+            .id();
+        spawn_test_player(&mut app, Vec3::X * 10.);
+
+        advance_fixed(&mut app, 2);
+        assert!(app
+            .world()
+            .entity(pending_mesh)
+            .contains::<PendingCollider>());
+
+        app.world_mut()
+            .resource_mut::<Assets<Mesh>>()
+            .insert(&mesh, Mesh::from(Cuboid::new(4., 4., 4.)));
+        advance_fixed(&mut app, 2);
+        assert!(app.world().entity(pending_mesh).contains::<Collider>());
+        assert!(!app
+            .world()
+            .entity(pending_mesh)
+            .contains::<PendingCollider>());
+    }
+}
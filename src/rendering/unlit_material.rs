@@ -9,16 +9,28 @@ use bevy_tween::{
 };
 
 pub fn unlit_material_plugin(app: &mut App) {
-    app.add_plugins(MaterialPlugin::<UnlitMaterial>::default())
-        .register_type::<UnlitMaterial>()
-        .register_type::<TargetAsset<UnlitMaterial>>()
-        .register_asset_reflect::<UnlitMaterial>()
-        .add_tween_systems(asset_tween_system::<MaterialIntensityInterpolator>())
-        .add_tween_systems(asset_tween_system::<MaterialColorOverrideInterpolator>());
+    app.add_plugins((
+        MaterialPlugin::<UnlitMaterial>::default(),
+        unlit_material_tweens_plugin,
+    ))
+    .register_type::<UnlitMaterial>()
+    .register_type::<TargetAsset<UnlitMaterial>>()
+    .register_asset_reflect::<UnlitMaterial>();
+}
+
+// Just the tweens, which only need the asset around. Headless apps use this on its own.
+pub fn unlit_material_tweens_plugin(app: &mut App) {
+    app.add_tween_systems(asset_tween_system::<MaterialIntensityInterpolator>())
+        .add_tween_systems(asset_tween_system::<MaterialColorOverrideInterpolator>())
+        .add_tween_systems(asset_tween_system::<MaterialAlphaInterpolator>());
 }
 
 pub type UnlitMaterial = ExtendedMaterial<StandardMaterial, UnlitMaterialExtension>;
 
+// Radians per second. Only the glow above an intensity of 1.0 pulses, so unpowered devices with
+// this set still sit flat.
+pub const POWERED_PULSE_SPEED: f32 = 4.0;
+
 #[derive(Asset, AsBindGroup, Reflect, Default, Debug, Clone)]
 #[reflect(Default)]
 pub struct UnlitMaterialExtension {
@@ -33,6 +45,8 @@ pub struct UnlitParams {
     pub blend_color: LinearRgba,
     pub blend_factor: f32,
     pub grey_threshold: f32,
+    // 0 means no pulsing
+    pub pulse_speed: f32,
 }
 
 impl MaterialExtension for UnlitMaterialExtension {
@@ -56,3 +70,25 @@ impl Interpolator for MaterialColorOverrideInterpolator {
         material.extension.params.blend_factor = invert_progress;
     }
 }
+
+#[derive(Reflect, Debug)]
+pub struct MaterialAlphaInterpolator {
+    pub start: f32,
+    pub end: f32,
+}
+
+impl Interpolator for MaterialAlphaInterpolator {
+    type Item = UnlitMaterial;
+
+    fn interpolate(&self, material: &mut Self::Item, progress: f32) {
+        let alpha = self.start + (self.end - self.start) * progress;
+        material.extension.params.alpha = alpha;
+
+        // anything see-through has to blend, but don't leave things that faded back in paying for it
+        if alpha < 1.0 && material.base.alpha_mode == AlphaMode::Opaque {
+            material.base.alpha_mode = AlphaMode::Blend;
+        } else if alpha >= 1.0 && material.base.alpha_mode == AlphaMode::Blend {
+            material.base.alpha_mode = AlphaMode::Opaque;
+        }
+    }
+}
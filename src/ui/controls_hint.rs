@@ -0,0 +1,130 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_enhanced_input::events::Started;
+
+use crate::{
+    asset_management::asset_loading::GameAssets,
+    game::input::{input_label, KeyBindings, Movement, RebindableAction, UseInteract},
+    GameState,
+};
+
+const HINT_DURATION_SEC: f32 = 6.0;
+const HINT_FADE_SEC: f32 = 1.0;
+
+pub fn controls_hint_plugin(app: &mut App) {
+    app.add_systems(OnEnter(GameState::Playing), spawn_controls_hint)
+        .add_systems(
+            Update,
+            fade_out_controls_hint.run_if(in_state(GameState::Playing)),
+        )
+        .add_observer(dismiss_on_movement)
+        .add_observer(dismiss_on_interact);
+}
+
+// Shown for the first few seconds of play, or until the player starts doing things
+#[derive(Component)]
+struct ControlsHint {
+    timer: Timer,
+}
+
+impl ControlsHint {
+    // skip ahead to the fade, unless we're already in it
+    fn dismiss(&mut self) {
+        let fade_start = Duration::from_secs_f32(HINT_DURATION_SEC - HINT_FADE_SEC);
+        if self.timer.elapsed() < fade_start {
+            self.timer.set_elapsed(fade_start);
+        }
+    }
+}
+
+fn spawn_controls_hint(
+    mut commands: Commands,
+    game_assets: Res<GameAssets>,
+    key_bindings: Res<KeyBindings>,
+) {
+    let label = |action| input_label(key_bindings.get(action));
+    let lines = [
+        format!(
+            "{} {} {} {} - move",
+            label(RebindableAction::Forward),
+            label(RebindableAction::Left),
+            label(RebindableAction::Back),
+            label(RebindableAction::Right),
+        ),
+        format!("{} - jump", label(RebindableAction::Jump)),
+        format!("{} - sprint", label(RebindableAction::Sprint)),
+        format!("{} - pick up / use", label(RebindableAction::UseInteract)),
+        String::from("Escape - menu"),
+    ];
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Percent(4.0),
+                bottom: Val::Percent(6.0),
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            ControlsHint {
+                timer: Timer::from_seconds(HINT_DURATION_SEC, TimerMode::Once),
+            },
+            StateScoped(GameState::Playing),
+        ))
+        .with_children(|child_spawner| {
+            for line in lines {
+                child_spawner.spawn((
+                    Text::new(line),
+                    TextFont {
+                        font: game_assets.font.clone(),
+                        font_size: 20.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                    TextShadow::default(),
+                ));
+            }
+        });
+}
+
+fn fade_out_controls_hint(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut q_hint: Query<(Entity, &mut ControlsHint, &Children)>,
+    mut q_text: Query<(&mut TextColor, &mut TextShadow)>,
+) {
+    for (hint_entity, mut hint, hint_children) in &mut q_hint {
+        hint.timer.tick(time.delta());
+
+        if hint.timer.finished() {
+            commands.entity(hint_entity).despawn();
+            continue;
+        }
+
+        let alpha = (hint.timer.remaining_secs() / HINT_FADE_SEC).clamp(0.0, 1.0);
+        for child in hint_children.iter() {
+            if let Ok((mut text_color, mut text_shadow)) = q_text.get_mut(child) {
+                text_color.0.set_alpha(alpha);
+                text_shadow
+                    .color
+                    .set_alpha(alpha * TextShadow::default().color.alpha());
+            }
+        }
+    }
+}
+
+fn dismiss_on_movement(_trigger: Trigger<Started<Movement>>, mut q_hint: Query<&mut ControlsHint>) {
+    for mut hint in &mut q_hint {
+        hint.dismiss();
+    }
+}
+
+fn dismiss_on_interact(
+    _trigger: Trigger<Started<UseInteract>>,
+    mut q_hint: Query<&mut ControlsHint>,
+) {
+    for mut hint in &mut q_hint {
+        hint.dismiss();
+    }
+}
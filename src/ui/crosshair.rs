@@ -4,28 +4,35 @@ use bevy_enhanced_input::events::Completed;
 
 use crate::{
     game::{
-        input::SystemMenuOrCancel,
+        input::{short_input_label, KeyBindings, RebindableAction, SystemMenuOrCancel},
         interaction::{Interactable, Interactions, InteractionsDisabled, INTERACTION_DISTANCE},
-        player::Held,
+        player::{CarryConfig, Held, RightHand},
+        signals::Signal,
         GameLayer,
     },
-    ui::main_menu::MainMenuState,
+    rendering::unlit_material::UnlitMaterial,
+    GameState, MainCamera,
 };
 
 pub fn crosshair_plugin(app: &mut App) {
     app.add_sub_state::<CrosshairState>()
+        .init_resource::<InteractionHighlight>()
         .add_systems(OnEnter(CrosshairState::Shown), enable_crosshair)
-        .add_systems(OnEnter(CrosshairState::Hidden), disable_crosshair)
+        .add_systems(
+            OnEnter(CrosshairState::Hidden),
+            (disable_crosshair, clear_interaction_highlight),
+        )
         .add_systems(
             Update,
-            (display_interaction_state).run_if(in_state(CrosshairState::Shown)),
+            (display_interaction_state, update_signal_indicators)
+                .run_if(in_state(CrosshairState::Shown)),
         )
         //.add_systems(PreUpdate, override_pointer_to_center.before(PickSet::Backend).after(PickSet::ProcessInput))
         .add_observer(toggle_aim_state);
 }
 
 #[derive(SubStates, Clone, PartialEq, Eq, Hash, Debug, Default)]
-#[source(MainMenuState = MainMenuState::Hidden)]
+#[source(GameState = GameState::Playing)]
 #[states(scoped_entities)]
 pub enum CrosshairState {
     #[default]
@@ -45,6 +52,24 @@ pub struct LeftCrosshairText;
 #[derive(Component)]
 pub struct RightCrosshairText;
 
+// Small dots around the reticle pointing at signals that are off screen
+#[derive(Component)]
+pub struct SignalIndicator(pub usize);
+
+// The interactable the crosshair is on, tinted so it's clear what's about to be used
+#[derive(Resource, Default)]
+pub struct InteractionHighlight {
+    pub entity: Option<Entity>,
+}
+
+const INTERACTION_HIGHLIGHT_COLOR: LinearRgba = LinearRgba::rgb(1.0, 0.5, 0.0);
+const INTERACTION_HIGHLIGHT_BLEND: f32 = 0.2;
+
+pub const MAX_SIGNAL_INDICATORS: usize = 4;
+const SIGNAL_INDICATOR_RADIUS: f32 = 60.0;
+const SIGNAL_INDICATOR_SIZE: f32 = 8.0;
+const SIGNAL_INDICATOR_FADE_DISTANCE: f32 = 150.0;
+
 fn enable_crosshair(mut commands: Commands, mut primary_window: Single<&mut Window>) {
     primary_window.cursor_options.grab_mode = CursorGrabMode::Confined;
     primary_window.cursor_options.visible = false;
@@ -128,6 +153,23 @@ fn enable_crosshair(mut commands: Commands, mut primary_window: Single<&mut Wind
                 RightCrosshairText,
             ));
         });
+
+    for index in 0..MAX_SIGNAL_INDICATORS {
+        commands.spawn((
+            Node {
+                width: Val::Px(SIGNAL_INDICATOR_SIZE),
+                height: Val::Px(SIGNAL_INDICATOR_SIZE),
+                position_type: PositionType::Absolute,
+                ..default()
+            },
+            BorderRadius::all(Val::Percent(50.0)),
+            BackgroundColor(Color::NONE),
+            Visibility::Hidden,
+            Pickable::IGNORE,
+            SignalIndicator(index),
+            StateScoped(CrosshairState::Shown),
+        ));
+    }
 }
 
 fn disable_crosshair(mut primary_window: Single<&mut Window>) {
@@ -188,12 +230,18 @@ fn toggle_aim_state(
 fn display_interaction_state(
     mut commands: Commands,
     spatial_query: SpatialQuery,
-    camera_query: Query<&GlobalTransform, With<Camera>>,
+    camera_query: Query<&GlobalTransform, With<MainCamera>>,
     q_interactable: Query<&Interactable, Without<InteractionsDisabled>>,
     q_crosshair_reticle: Query<Entity, With<CrosshairReticle>>,
     crosshair_state: Option<Res<State<CrosshairState>>>,
     maybe_left_text: Option<Single<&mut Text, With<LeftCrosshairText>>>,
     maybe_held_object: Option<Single<&Held>>,
+    maybe_right_hand: Option<Single<&RightHand>>,
+    carry_config: Res<CarryConfig>,
+    key_bindings: Res<KeyBindings>,
+    mut highlight: ResMut<InteractionHighlight>,
+    q_unlit_objects: Query<&MeshMaterial3d<UnlitMaterial>>,
+    mut unlit_materials: ResMut<Assets<UnlitMaterial>>,
 ) {
     if let Some(crosshair_state) = crosshair_state {
         if matches!(**crosshair_state, CrosshairState::Shown) {
@@ -208,6 +256,10 @@ fn display_interaction_state(
                 let ray_direction = camera_transform.forward();
 
                 // Get the interactable entity if one is hit
+                let hands_full = maybe_right_hand
+                    .as_ref()
+                    .is_some_and(|right_hand| right_hand.is_full(carry_config.capacity));
+
                 let hit_interactable = if let Some(hit) = spatial_query.cast_ray(
                     ray_origin,
                     ray_direction,
@@ -218,12 +270,15 @@ fn display_interaction_state(
                 ) {
                     let hit_entity = hit.entity;
                     if q_interactable.contains(hit_entity)
-                        && !(maybe_held_object.is_some()
+                        && !(hands_full
                             && q_interactable
                                 .get(hit_entity)
                                 .is_ok_and(|i| matches!(i.primary_action, Interactions::PickUp)))
                     {
-                        q_interactable.get(hit_entity).ok()
+                        q_interactable
+                            .get(hit_entity)
+                            .ok()
+                            .map(|interactable| (hit_entity, interactable))
                     } else {
                         None
                     }
@@ -244,14 +299,20 @@ fn display_interaction_state(
                 };
 
                 if let Some(mut left_text) = maybe_left_text {
-                    if let Some(interactable) = hit_interactable {
+                    // follows rebinding since it's read fresh every frame
+                    let use_key =
+                        short_input_label(key_bindings.get(RebindableAction::UseInteract));
+
+                    if let Some((_, interactable)) = hit_interactable {
                         left_text.0 = match interactable.primary_action {
-                            Interactions::Press => String::from("Press"),
-                            Interactions::PickUp => String::from("Pick Up"),
+                            Interactions::Press => format!("[{}] Press", use_key),
+                            Interactions::PickUp => format!("[{}] Pick Up", use_key),
+                            Interactions::Toggle => format!("[{}] Toggle", use_key),
+                            Interactions::Hold => format!("[{}] Hold", use_key),
                         };
                     } else if let Some(held_object) = maybe_held_object {
                         if held_object.can_release {
-                            left_text.0 = String::from("Release");
+                            left_text.0 = format!("[{}] Release", use_key);
                         } else {
                             left_text.0 = String::from("");
                         }
@@ -264,7 +325,123 @@ fn display_interaction_state(
                     .entity(reticle_entity)
                     .insert(BorderColor(border_color))
                     .insert(BackgroundColor(background_color));
+
+                let targeted = hit_interactable.map(|(entity, _)| entity);
+                if highlight.entity != targeted {
+                    if let Some(previous) = highlight.entity {
+                        set_interaction_highlight(
+                            previous,
+                            false,
+                            &q_unlit_objects,
+                            &mut unlit_materials,
+                        );
+                    }
+                    if let Some(targeted) = targeted {
+                        set_interaction_highlight(
+                            targeted,
+                            true,
+                            &q_unlit_objects,
+                            &mut unlit_materials,
+                        );
+                    }
+                    highlight.entity = targeted;
+                }
             }
         }
     }
 }
+
+// Held objects and the signal preview tint materials too. Only tint something that isn't
+// already tinted, and only clear a tint that's still ours, so none of them stomp on each other.
+fn set_interaction_highlight(
+    entity: Entity,
+    highlighted: bool,
+    q_unlit_objects: &Query<&MeshMaterial3d<UnlitMaterial>>,
+    unlit_materials: &mut Assets<UnlitMaterial>,
+) {
+    let Some(material) = q_unlit_objects
+        .get(entity)
+        .ok()
+        .and_then(|handle| unlit_materials.get_mut(handle))
+    else {
+        return;
+    };
+
+    let params = &mut material.extension.params;
+    if highlighted && params.blend_factor == 0.0 {
+        params.blend_color = INTERACTION_HIGHLIGHT_COLOR;
+        params.blend_factor = INTERACTION_HIGHLIGHT_BLEND;
+    } else if !highlighted && params.blend_color == INTERACTION_HIGHLIGHT_COLOR {
+        params.blend_color = LinearRgba::WHITE;
+        params.blend_factor = 0.0;
+    }
+}
+
+fn clear_interaction_highlight(
+    mut highlight: ResMut<InteractionHighlight>,
+    q_unlit_objects: Query<&MeshMaterial3d<UnlitMaterial>>,
+    mut unlit_materials: ResMut<Assets<UnlitMaterial>>,
+) {
+    if let Some(previous) = highlight.entity.take() {
+        set_interaction_highlight(previous, false, &q_unlit_objects, &mut unlit_materials);
+    }
+}
+
+fn update_signal_indicators(
+    primary_window: Single<&Window>,
+    camera: Single<(&Camera, &GlobalTransform), With<MainCamera>>,
+    q_signals: Query<&GlobalTransform, With<Signal>>,
+    mut q_indicators: Query<(
+        &SignalIndicator,
+        &mut Node,
+        &mut BackgroundColor,
+        &mut Visibility,
+    )>,
+) {
+    let (camera, camera_transform) = *camera;
+    let view_from_world = camera_transform.affine().inverse();
+
+    // Only signals that are off screen get an indicator, closest first
+    let mut off_screen_signals: Vec<(f32, Vec2)> = q_signals
+        .iter()
+        .filter_map(|signal_transform| {
+            let signal_position = signal_transform.translation();
+            let view_position = view_from_world.transform_point3(signal_position);
+
+            // In front of the camera and inside the frustum means it's already visible
+            if view_position.z < 0.0 {
+                if let Some(ndc) = camera.world_to_ndc(camera_transform, signal_position) {
+                    if ndc.x.abs() <= 1.0 && ndc.y.abs() <= 1.0 {
+                        return None;
+                    }
+                }
+            }
+
+            let direction = view_position.truncate().try_normalize()?;
+            let distance = camera_transform.translation().distance(signal_position);
+            Some((distance, direction))
+        })
+        .collect();
+
+    off_screen_signals.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let center = Vec2::new(primary_window.width(), primary_window.height()) / 2.0;
+
+    for (indicator, mut node, mut background_color, mut visibility) in &mut q_indicators {
+        let Some((distance, direction)) = off_screen_signals.get(indicator.0) else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+
+        let alpha = (1.0 - distance / SIGNAL_INDICATOR_FADE_DISTANCE).clamp(0.15, 1.0);
+
+        // UI y grows downward, view space y grows upward
+        let position = center + Vec2::new(direction.x, -direction.y) * SIGNAL_INDICATOR_RADIUS
+            - Vec2::splat(SIGNAL_INDICATOR_SIZE / 2.0);
+
+        node.left = Val::Px(position.x);
+        node.top = Val::Px(position.y);
+        background_color.0 = Color::Srgba(Srgba::new(0.0, 1.0, 1.0, alpha));
+        *visibility = Visibility::Inherited;
+    }
+}
@@ -0,0 +1,119 @@
+use std::collections::HashSet;
+
+use bevy::prelude::*;
+
+use crate::{
+    asset_management::asset_loading::GameAssets, game::door::DoorPowerProgress, GameState,
+    MainCamera,
+};
+
+// how far above the door's origin the readout floats
+const READOUT_HEIGHT: f32 = 25.0;
+const READOUT_MAX_DISTANCE: f32 = 250.0;
+const READOUT_WIDTH: f32 = 80.0;
+
+pub fn door_power_readout_plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (spawn_door_power_readouts, update_door_power_readouts)
+            .chain()
+            .run_if(in_state(GameState::Playing)),
+    );
+}
+
+// "2/3" over a door that needs several poles powered, so it's clear how many more it wants
+#[derive(Component)]
+struct DoorPowerReadout {
+    door: Entity,
+}
+
+fn spawn_door_power_readouts(
+    mut commands: Commands,
+    game_assets: Res<GameAssets>,
+    q_doors: Query<Entity, With<DoorPowerProgress>>,
+    q_readouts: Query<&DoorPowerReadout>,
+) {
+    let doors_with_readouts: HashSet<Entity> =
+        q_readouts.iter().map(|readout| readout.door).collect();
+
+    for door in &q_doors {
+        if doors_with_readouts.contains(&door) {
+            continue;
+        }
+
+        commands.spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Px(READOUT_WIDTH),
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            Text::default(),
+            TextFont {
+                font: game_assets.font.clone(),
+                font_size: 28.0,
+                ..default()
+            },
+            TextLayout::new_with_justify(JustifyText::Center),
+            TextColor(Color::WHITE),
+            TextShadow::default(),
+            Visibility::Hidden,
+            DoorPowerReadout { door },
+            StateScoped(GameState::Playing),
+        ));
+    }
+}
+
+fn update_door_power_readouts(
+    mut commands: Commands,
+    camera: Single<(&Camera, &GlobalTransform), With<MainCamera>>,
+    q_doors: Query<(&GlobalTransform, &DoorPowerProgress)>,
+    mut q_readouts: Query<(
+        Entity,
+        &DoorPowerReadout,
+        &mut Node,
+        &mut Text,
+        &mut TextColor,
+        &mut Visibility,
+    )>,
+) {
+    let (camera, camera_transform) = *camera;
+
+    for (readout_entity, readout, mut node, mut text, mut text_color, mut visibility) in
+        &mut q_readouts
+    {
+        // the level it belonged to was unloaded
+        let Ok((door_transform, power_progress)) = q_doors.get(readout.door) else {
+            commands.entity(readout_entity).despawn();
+            continue;
+        };
+
+        let readout_position = door_transform.translation() + Vec3::Y * READOUT_HEIGHT;
+        let in_range =
+            camera_transform.translation().distance(readout_position) <= READOUT_MAX_DISTANCE;
+
+        let Some(screen_position) = camera
+            .world_to_viewport(camera_transform, readout_position)
+            .ok()
+            .filter(|_| in_range)
+        else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+
+        node.left = Val::Px(screen_position.x - READOUT_WIDTH / 2.0);
+        node.top = Val::Px(screen_position.y);
+
+        let readout_text = format!("{}/{}", power_progress.powered, power_progress.required);
+        // don't make the text relayout every frame for nothing
+        if text.0 != readout_text {
+            text.0 = readout_text;
+        }
+        text_color.0 = if power_progress.powered >= power_progress.required {
+            Color::srgb(0.2, 1.0, 0.4)
+        } else {
+            Color::WHITE
+        };
+        *visibility = Visibility::Inherited;
+    }
+}
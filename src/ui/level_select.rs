@@ -0,0 +1,213 @@
+use bevy::prelude::*;
+
+use crate::{
+    asset_management::{
+        asset_loading::GameAssets,
+        level_manager::{LevelManager, LevelProgress, LoadLevel},
+    },
+    game::level_timer::format_level_time,
+    ui::main_menu::MainMenuState,
+    GameState,
+};
+
+pub fn level_select_plugin(app: &mut App) {
+    app.init_resource::<LevelSelectCursor>()
+        .add_systems(OnEnter(MainMenuState::LevelSelect), spawn_level_select)
+        .add_systems(
+            Update,
+            (navigate_level_select, update_level_select_labels)
+                .chain()
+                .run_if(in_state(MainMenuState::LevelSelect)),
+        );
+}
+
+// Highlighted entry, shared between mouse hover and the keyboard. The last entry is "Back".
+#[derive(Resource, Default)]
+struct LevelSelectCursor(usize);
+
+#[derive(Component)]
+struct LevelSelectEntry {
+    index: usize,
+    label: String,
+    // locked levels and "Back" both have no level to load
+    level: Option<usize>,
+    locked: bool,
+}
+
+const UNLOCKED_COLOR: Color = Color::srgb(0.1, 0.1, 0.1);
+const LOCKED_COLOR: Color = Color::srgb(0.55, 0.55, 0.55);
+
+fn spawn_level_select(
+    mut commands: Commands,
+    game_assets: Res<GameAssets>,
+    level_manager: Res<LevelManager>,
+    progress: Res<LevelProgress>,
+    mut cursor: ResMut<LevelSelectCursor>,
+) {
+    cursor.0 = level_manager.current;
+
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Start,
+                align_items: AlignItems::FlexStart,
+                flex_direction: FlexDirection::Column,
+                position_type: PositionType::Absolute,
+                padding: UiRect::left(Val::Percent(8.33)).with_top(Val::Percent(8.33)),
+                ..default()
+            },
+            StateScoped(MainMenuState::LevelSelect),
+        ))
+        .with_children(|child_spawner| {
+            child_spawner.spawn((
+                Text::new("where to?"),
+                TextFont {
+                    font: game_assets.font.clone(),
+                    font_size: 48.0,
+                    ..default()
+                },
+                TextColor(UNLOCKED_COLOR),
+                Node {
+                    margin: UiRect::bottom(Val::Percent(8.)),
+                    ..default()
+                },
+            ));
+
+            for level in 0..level_manager.levels.len() {
+                let locked = !progress.is_unlocked(level);
+                let best_time = progress
+                    .best_times
+                    .get(&level)
+                    .map(|best| format!(" - best {}", format_level_time(*best)))
+                    .unwrap_or_default();
+                let label = format!(
+                    "{}. {}{}",
+                    level + 1,
+                    level_manager.level_label(level),
+                    best_time
+                );
+
+                spawn_level_select_entry(
+                    child_spawner,
+                    &game_assets,
+                    LevelSelectEntry {
+                        index: level,
+                        label,
+                        level: (!locked).then_some(level),
+                        locked,
+                    },
+                );
+            }
+
+            spawn_level_select_entry(
+                child_spawner,
+                &game_assets,
+                LevelSelectEntry {
+                    index: level_manager.levels.len(),
+                    label: String::from("Back"),
+                    level: None,
+                    locked: false,
+                },
+            );
+        });
+}
+
+fn spawn_level_select_entry(
+    child_spawner: &mut ChildSpawnerCommands,
+    game_assets: &GameAssets,
+    entry: LevelSelectEntry,
+) {
+    let index = entry.index;
+    let color = if entry.locked {
+        LOCKED_COLOR
+    } else {
+        UNLOCKED_COLOR
+    };
+
+    child_spawner
+        .spawn((
+            Text::new(entry.label.clone()),
+            TextFont {
+                font: game_assets.font.clone(),
+                font_size: 33.0,
+                ..default()
+            },
+            TextColor(color),
+            entry,
+        ))
+        .observe(
+            move |_trigger: Trigger<Pointer<Click>>,
+                  mut commands: Commands,
+                  q_entries: Query<&LevelSelectEntry>| {
+                if let Some(entry) = q_entries.iter().find(|entry| entry.index == index) {
+                    select_entry(&mut commands, entry);
+                }
+            },
+        )
+        .observe(
+            move |_trigger: Trigger<Pointer<Over>>, mut cursor: ResMut<LevelSelectCursor>| {
+                cursor.0 = index;
+            },
+        );
+}
+
+fn select_entry(commands: &mut Commands, entry: &LevelSelectEntry) {
+    if entry.locked {
+        return;
+    }
+
+    match entry.level {
+        Some(level) => {
+            commands.trigger(LoadLevel(level));
+            commands.set_state(GameState::Playing);
+        }
+        None => commands.set_state(MainMenuState::Shown),
+    }
+}
+
+fn navigate_level_select(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut cursor: ResMut<LevelSelectCursor>,
+    q_entries: Query<&LevelSelectEntry>,
+) {
+    let entry_count = q_entries.iter().count();
+    if entry_count == 0 {
+        return;
+    }
+
+    if keys.any_just_pressed([KeyCode::ArrowUp, KeyCode::KeyW]) {
+        cursor.0 = (cursor.0 + entry_count - 1) % entry_count;
+    }
+
+    if keys.any_just_pressed([KeyCode::ArrowDown, KeyCode::KeyS]) {
+        cursor.0 = (cursor.0 + 1) % entry_count;
+    }
+
+    if keys.just_pressed(KeyCode::Escape) {
+        commands.set_state(MainMenuState::Shown);
+    } else if keys.any_just_pressed([KeyCode::Enter, KeyCode::Space]) {
+        if let Some(entry) = q_entries.iter().find(|entry| entry.index == cursor.0) {
+            select_entry(&mut commands, entry);
+        }
+    }
+}
+
+fn update_level_select_labels(
+    cursor: Res<LevelSelectCursor>,
+    mut q_entries: Query<(&LevelSelectEntry, &mut Text)>,
+) {
+    for (entry, mut text) in &mut q_entries {
+        let label = if entry.index == cursor.0 {
+            format!("{} ◀", entry.label)
+        } else {
+            entry.label.clone()
+        };
+
+        if text.0 != label {
+            text.0 = label;
+        }
+    }
+}
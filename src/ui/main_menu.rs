@@ -1,29 +1,54 @@
 use avian3d::prelude::RigidBodyDisabled;
 use bevy::prelude::*;
 
-use crate::{asset_management::asset_loading::GameAssets, game::player::Player, GameState};
+use crate::{
+    asset_management::{
+        asset_loading::{CurrentLevelMeta, GameAssets},
+        level_manager::LevelManager,
+    },
+    game::player::Player,
+    GameState,
+};
 
 pub fn main_menu_plugin(app: &mut App) {
     app.add_sub_state::<MainMenuState>()
-        .add_systems(OnEnter(MainMenuState::Shown), spawn_main_menu);
+        .add_systems(OnEnter(MainMenuState::Shown), spawn_main_menu)
+        .add_systems(Update, freeze_player.run_if(in_state(GameState::MainMenu)))
+        .add_systems(OnExit(GameState::MainMenu), unfreeze_player);
 }
 
 #[derive(SubStates, Clone, PartialEq, Eq, Hash, Debug, Default)]
-#[source(GameState = GameState::Playing)]
+#[source(GameState = GameState::MainMenu)]
 #[states(scoped_entities)]
 pub enum MainMenuState {
     #[default]
     Shown,
-    Hidden,
+    LevelSelect,
+}
+
+// The level renders behind the menu, but the player stays put until Play. The player can
+// show up after the menu does (the spawn point comes in with the scene), so keep checking.
+fn freeze_player(
+    mut commands: Commands,
+    q_player: Query<Entity, (With<Player>, Without<RigidBodyDisabled>)>,
+) {
+    for player in &q_player {
+        commands.entity(player).insert(RigidBodyDisabled);
+    }
+}
+
+fn unfreeze_player(mut commands: Commands, q_player: Query<Entity, With<Player>>) {
+    for player in &q_player {
+        commands.entity(player).try_remove::<RigidBodyDisabled>();
+    }
 }
 
 fn spawn_main_menu(
     mut commands: Commands,
     game_assets: Res<GameAssets>,
-    player: Single<Entity, With<Player>>,
+    current_level_meta: Res<CurrentLevelMeta>,
+    level_manager: Res<LevelManager>,
 ) {
-    commands.entity(*player).insert(RigidBodyDisabled);
-    let player_id = *player;
     commands
         .spawn((
             Node {
@@ -53,6 +78,22 @@ fn spawn_main_menu(
                 },
             ));
 
+            if let Some(level_meta) = &current_level_meta.0 {
+                child_spawner.spawn((
+                    Text::new(format!("{} by {}", level_meta.name, level_meta.author)),
+                    TextFont {
+                        font: game_assets.font.clone(),
+                        font_size: 24.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(0.1, 0.1, 0.1)),
+                    Node {
+                        margin: UiRect::bottom(Val::Percent(4.)),
+                        ..default()
+                    },
+                ));
+            }
+
             let text_entity = child_spawner
                 .spawn((
                     Text::new("Play"),
@@ -70,8 +111,7 @@ fn spawn_main_menu(
                 .entity(text_entity)
                 .observe(
                     move |_trigger: Trigger<Pointer<Click>>, mut commands: Commands| {
-                        commands.set_state(MainMenuState::Hidden);
-                        commands.entity(player_id).remove::<RigidBodyDisabled>();
+                        commands.set_state(GameState::Playing);
                     },
                 )
                 .observe(
@@ -88,5 +128,44 @@ fn spawn_main_menu(
                         }
                     },
                 );
+
+            // nothing to pick from until there's more than one level
+            if level_manager.levels.len() > 1 {
+                let text_entity = child_spawner
+                    .spawn((
+                        Text::new("Select Level"),
+                        TextFont {
+                            font: game_assets.font.clone(),
+                            font_size: 33.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.1, 0.1, 0.1)),
+                    ))
+                    .id();
+
+                child_spawner
+                    .commands()
+                    .entity(text_entity)
+                    .observe(
+                        |_trigger: Trigger<Pointer<Click>>, mut commands: Commands| {
+                            commands.set_state(MainMenuState::LevelSelect);
+                        },
+                    )
+                    .observe(
+                        move |_trigger: Trigger<Pointer<Over>>,
+                              mut text_query: Query<&mut Text>| {
+                            if let Ok(mut text) = text_query.get_mut(text_entity) {
+                                **text = "Select Level ◀".into();
+                            }
+                        },
+                    )
+                    .observe(
+                        move |_trigger: Trigger<Pointer<Out>>, mut text_query: Query<&mut Text>| {
+                            if let Ok(mut text) = text_query.get_mut(text_entity) {
+                                **text = "Select Level".into();
+                            }
+                        },
+                    );
+            }
         });
 }
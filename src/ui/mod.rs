@@ -1,14 +1,20 @@
 use bevy::prelude::*;
+use controls_hint::controls_hint_plugin;
 use crosshair::crosshair_plugin;
+use door_power_readout::door_power_readout_plugin;
+use level_select::level_select_plugin;
 use loading_screen::loading_screen_plugin;
 
 use crate::ui::{
     main_menu::main_menu_plugin, system_menu::system_menu_plugin, you_win::you_win_plugin,
 };
 
+mod controls_hint;
 pub mod crosshair;
+mod door_power_readout;
+mod level_select;
 mod loading_screen;
-mod main_menu;
+pub mod main_menu;
 mod system_menu;
 pub mod you_win;
 
@@ -16,7 +22,10 @@ pub fn ui_plugins(app: &mut App) {
     app.add_plugins((
         loading_screen_plugin,
         crosshair_plugin,
+        controls_hint_plugin,
+        door_power_readout_plugin,
         main_menu_plugin,
+        level_select_plugin,
         system_menu_plugin,
         you_win_plugin,
     ));
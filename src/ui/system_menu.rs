@@ -1,21 +1,48 @@
 use bevy::prelude::*;
 
 use crate::{
-    asset_management::asset_loading::GameAssets,
+    asset_management::{asset_loading::GameAssets, level_manager::RestartLevel},
     game::{
-        audio::{handle_volume_down, handle_volume_up},
-        dissolve_gate::Dissolveable,
-        player::{Held, Player, PlayerSpawnPoint, RightHand},
-        standing_cube_spitter::Tombstone,
+        accessibility::{reduced_motion_label, toggle_reduced_motion, AccessibilitySettings},
+        audio::{
+            handle_volume_down, handle_volume_up, spatial_audio_label, toggle_spatial_audio,
+            AudioSettings, VolumeChannel,
+        },
+        dissolve_gate::{dissolve_or_respawn, Dissolveable},
+        input::{input_label, KeyBindings, RebindableAction},
+        player::{Player, PlayerSpawnPoint},
     },
     ui::crosshair::CrosshairState,
 };
 
 pub fn system_menu_plugin(app: &mut App) {
-    app.add_systems(OnEnter(CrosshairState::Hidden), spawn_system_menu);
+    app.init_resource::<RebindState>()
+        .add_systems(OnEnter(CrosshairState::Hidden), spawn_system_menu)
+        .add_systems(OnExit(CrosshairState::Hidden), cancel_rebind)
+        .add_systems(
+            Update,
+            (capture_rebind_input, update_rebind_labels)
+                .chain()
+                .run_if(in_state(CrosshairState::Hidden)),
+        );
 }
 
-fn spawn_system_menu(mut commands: Commands, game_assets: Res<GameAssets>) {
+// The action waiting for its next key/mouse press, if any
+#[derive(Resource, Default)]
+struct RebindState(Option<RebindableAction>);
+
+#[derive(Component)]
+struct RebindLabel(RebindableAction);
+
+#[derive(Component)]
+struct RebindHovered;
+
+fn spawn_system_menu(
+    mut commands: Commands,
+    game_assets: Res<GameAssets>,
+    accessibility_settings: Res<AccessibilitySettings>,
+    audio_settings: Res<AudioSettings>,
+) {
     commands
         .spawn((
             Node {
@@ -65,9 +92,33 @@ fn spawn_system_menu(mut commands: Commands, game_assets: Res<GameAssets>) {
                         },
                     ));
 
+                    for (channel, up_label, down_label) in [
+                        (
+                            VolumeChannel::Master,
+                            "Master Volume Up",
+                            "Master Volume Down",
+                        ),
+                        (VolumeChannel::Music, "Music Volume Up", "Music Volume Down"),
+                        (VolumeChannel::Sfx, "SFX Volume Up", "SFX Volume Down"),
+                    ] {
+                        let text_entity =
+                            spawn_menu_entry(child_spawner, game_assets.font.clone(), up_label);
+                        child_spawner
+                            .commands()
+                            .entity(text_entity)
+                            .observe(handle_volume_up(channel));
+
+                        let text_entity =
+                            spawn_menu_entry(child_spawner, game_assets.font.clone(), down_label);
+                        child_spawner
+                            .commands()
+                            .entity(text_entity)
+                            .observe(handle_volume_down(channel));
+                    }
+
                     let text_entity = child_spawner
                         .spawn((
-                            Text::new("Volume Up"),
+                            Text::new(spatial_audio_label(audio_settings.spatial_enabled)),
                             TextFont {
                                 font: game_assets.font.clone(),
                                 font_size: 33.0,
@@ -80,26 +131,32 @@ fn spawn_system_menu(mut commands: Commands, game_assets: Res<GameAssets>) {
                     child_spawner
                         .commands()
                         .entity(text_entity)
-                        .observe(handle_volume_up)
+                        .observe(toggle_spatial_audio)
                         .observe(
                             move |_trigger: Trigger<Pointer<Over>>,
-                                  mut text_query: Query<&mut Text>| {
+                                  mut text_query: Query<&mut Text>,
+                                  audio_settings: Res<AudioSettings>| {
                                 if let Ok(mut text) = text_query.get_mut(text_entity) {
-                                    **text = "Volume Up ◀".into();
+                                    **text = format!(
+                                        "{} ◀",
+                                        spatial_audio_label(audio_settings.spatial_enabled)
+                                    );
                                 }
                             },
                         )
                         .observe(
                             move |_trigger: Trigger<Pointer<Out>>,
-                                  mut text_query: Query<&mut Text>| {
+                                  mut text_query: Query<&mut Text>,
+                                  audio_settings: Res<AudioSettings>| {
                                 if let Ok(mut text) = text_query.get_mut(text_entity) {
-                                    **text = "Volume Up".into();
+                                    **text = spatial_audio_label(audio_settings.spatial_enabled);
                                 }
                             },
                         );
+
                     let text_entity = child_spawner
                         .spawn((
-                            Text::new("Volume Down"),
+                            Text::new(reduced_motion_label(accessibility_settings.reduced_motion)),
                             TextFont {
                                 font: game_assets.font.clone(),
                                 font_size: 33.0,
@@ -112,20 +169,26 @@ fn spawn_system_menu(mut commands: Commands, game_assets: Res<GameAssets>) {
                     child_spawner
                         .commands()
                         .entity(text_entity)
-                        .observe(handle_volume_down)
+                        .observe(toggle_reduced_motion)
                         .observe(
                             move |_trigger: Trigger<Pointer<Over>>,
-                                  mut text_query: Query<&mut Text>| {
+                                  mut text_query: Query<&mut Text>,
+                                  accessibility_settings: Res<AccessibilitySettings>| {
                                 if let Ok(mut text) = text_query.get_mut(text_entity) {
-                                    **text = "Volume Down ◀".into();
+                                    **text = format!(
+                                        "{} ◀",
+                                        reduced_motion_label(accessibility_settings.reduced_motion)
+                                    );
                                 }
                             },
                         )
                         .observe(
                             move |_trigger: Trigger<Pointer<Out>>,
-                                  mut text_query: Query<&mut Text>| {
+                                  mut text_query: Query<&mut Text>,
+                                  accessibility_settings: Res<AccessibilitySettings>| {
                                 if let Ok(mut text) = text_query.get_mut(text_entity) {
-                                    **text = "Volume Down".into();
+                                    **text =
+                                        reduced_motion_label(accessibility_settings.reduced_motion);
                                 }
                             },
                         );
@@ -195,10 +258,150 @@ fn spawn_system_menu(mut commands: Commands, game_assets: Res<GameAssets>) {
                                 }
                             },
                         );
+
+                    let text_entity =
+                        spawn_menu_entry(child_spawner, game_assets.font.clone(), "Restart Level");
+                    child_spawner
+                        .commands()
+                        .entity(text_entity)
+                        .observe(restart_level);
+
+                    child_spawner.spawn((
+                        Text::new("controls"),
+                        TextFont {
+                            font: game_assets.font.clone(),
+                            font_size: 33.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.1, 0.1, 0.1)),
+                        Node {
+                            margin: UiRect::top(Val::Px(20.)),
+                            ..default()
+                        },
+                    ));
+
+                    for action in RebindableAction::ALL {
+                        child_spawner
+                            .spawn((
+                                Text::new(action.name()),
+                                TextFont {
+                                    font: game_assets.font.clone(),
+                                    font_size: 24.0,
+                                    ..default()
+                                },
+                                TextColor(Color::srgb(0.1, 0.1, 0.1)),
+                                RebindLabel(action),
+                            ))
+                            .observe(
+                                move |_trigger: Trigger<Pointer<Click>>,
+                                      mut rebind_state: ResMut<RebindState>| {
+                                    rebind_state.0 = Some(action);
+                                },
+                            )
+                            .observe(
+                                |trigger: Trigger<Pointer<Over>>, mut commands: Commands| {
+                                    commands.entity(trigger.target()).insert(RebindHovered);
+                                },
+                            )
+                            .observe(
+                                |trigger: Trigger<Pointer<Out>>, mut commands: Commands| {
+                                    commands.entity(trigger.target()).remove::<RebindHovered>();
+                                },
+                            );
+                    }
                 });
         });
 }
 
+// Spawns a clickable menu text entry with the usual hover arrow
+fn spawn_menu_entry(
+    child_spawner: &mut ChildSpawnerCommands,
+    font: Handle<Font>,
+    label: &'static str,
+) -> Entity {
+    let text_entity = child_spawner
+        .spawn((
+            Text::new(label),
+            TextFont {
+                font,
+                font_size: 33.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.1, 0.1, 0.1)),
+        ))
+        .id();
+
+    child_spawner
+        .commands()
+        .entity(text_entity)
+        .observe(
+            move |_trigger: Trigger<Pointer<Over>>, mut text_query: Query<&mut Text>| {
+                if let Ok(mut text) = text_query.get_mut(text_entity) {
+                    **text = format!("{} ◀", label);
+                }
+            },
+        )
+        .observe(
+            move |_trigger: Trigger<Pointer<Out>>, mut text_query: Query<&mut Text>| {
+                if let Ok(mut text) = text_query.get_mut(text_entity) {
+                    **text = label.into();
+                }
+            },
+        );
+
+    text_entity
+}
+
+fn cancel_rebind(mut rebind_state: ResMut<RebindState>) {
+    rebind_state.0 = None;
+}
+
+fn capture_rebind_input(
+    mut rebind_state: ResMut<RebindState>,
+    mut key_bindings: ResMut<KeyBindings>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+) {
+    let Some(action) = rebind_state.0 else {
+        return;
+    };
+
+    if let Some(&key) = keys.get_just_pressed().next() {
+        // escape is reserved for the menu, so it cancels instead
+        if key != KeyCode::Escape {
+            key_bindings.set(action, key.into());
+        }
+        rebind_state.0 = None;
+    } else if let Some(&button) = mouse_buttons.get_just_pressed().next() {
+        key_bindings.set(action, button.into());
+        rebind_state.0 = None;
+    }
+}
+
+fn update_rebind_labels(
+    rebind_state: Res<RebindState>,
+    key_bindings: Res<KeyBindings>,
+    mut q_labels: Query<(&mut Text, &RebindLabel, Has<RebindHovered>)>,
+) {
+    for (mut text, rebind_label, is_hovered) in &mut q_labels {
+        let binding = if rebind_state.0 == Some(rebind_label.0) {
+            String::from("press a key...")
+        } else {
+            input_label(key_bindings.get(rebind_label.0))
+        };
+
+        let label = if is_hovered {
+            format!("{}: {} ◀", rebind_label.0.name(), binding)
+        } else {
+            format!("{}: {}", rebind_label.0.name(), binding)
+        };
+
+        if text.0 != label {
+            text.0 = label;
+        }
+    }
+}
+
 fn respawn_player(
     _trigger: Trigger<Pointer<Click>>,
     mut commands: Commands,
@@ -210,47 +413,18 @@ fn respawn_player(
         .insert(Transform::from_translation(respawn.translation));
 }
 
+fn restart_level(_trigger: Trigger<Pointer<Click>>, mut commands: Commands) {
+    commands.trigger(RestartLevel);
+    commands.set_state(CrosshairState::Shown);
+}
+
 fn reset_all_objects(
     _trigger: Trigger<Pointer<Click>>,
     mut commands: Commands,
     q_dissolveable: Query<(Entity, &Dissolveable)>,
-    q_player: Query<&RightHand, With<Player>>,
 ) {
-    // Reset all dissolveable objects in the world
+    // Reset all dissolveable objects in the world, held ones included
     for (entity, dissolveable) in &q_dissolveable {
-        match &dissolveable.respawn_transform {
-            Some(respawn_transform) => {
-                // Respawn the entity at the specified transform
-                commands.entity(entity).try_insert(*respawn_transform);
-            }
-            None => {
-                // If no respawn transform, despawn the entity
-                if let Ok(mut ec) = commands.get_entity(entity) {
-                    ec.try_insert(Tombstone).despawn();
-                }
-            }
-        }
-    }
-
-    // Also reset any held objects that are dissolveable
-    for right_hand in &q_player {
-        if let Some(held_entity) = right_hand.held_object {
-            if let Ok((_, dissolveable)) = q_dissolveable.get(held_entity) {
-                match &dissolveable.respawn_transform {
-                    Some(respawn_transform) => {
-                        // Respawn the held entity at the specified transform and remove Held component
-                        commands
-                            .entity(held_entity)
-                            .try_insert(*respawn_transform)
-                            .remove::<Held>();
-                    }
-                    None => {
-                        if let Ok(mut ec) = commands.get_entity(held_entity) {
-                            ec.try_insert(Tombstone).despawn();
-                        }
-                    }
-                }
-            }
-        }
+        dissolve_or_respawn(&mut commands, entity, dissolveable);
     }
 }
@@ -1,4 +1,8 @@
-use crate::{asset_management::asset_loading::GameAssets, GameState};
+use crate::{
+    asset_management::asset_loading::{CurrentLevelMeta, GameAssets},
+    game::level_timer::{format_level_time, stop_level_timer, LevelTimer},
+    GameState,
+};
 use bevy::prelude::*;
 
 #[derive(Component)]
@@ -7,49 +11,92 @@ struct FadeInBackground {
 }
 
 pub fn you_win_plugin(app: &mut App) {
-    app.add_systems(OnEnter(GameState::Win), win)
+    app.add_systems(OnEnter(GameState::Win), win.after(stop_level_timer))
         .add_systems(Update, fade_in_background.run_if(in_state(GameState::Win)));
 }
 
-fn win(mut commands: Commands, game_assets: Res<GameAssets>) {
-    commands.spawn((
-        Node {
-            width: Val::Percent(100.0),
-            height: Val::Percent(100.0),
-            justify_content: JustifyContent::Center,
-            align_items: AlignItems::Center,
-            flex_direction: FlexDirection::Column,
-            position_type: PositionType::Absolute,
+fn win(
+    mut commands: Commands,
+    game_assets: Res<GameAssets>,
+    current_level_meta: Res<CurrentLevelMeta>,
+    level_timer: Res<LevelTimer>,
+) {
+    let win_screen = commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                flex_direction: FlexDirection::Column,
+                position_type: PositionType::Absolute,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.0)),
+            FadeInBackground {
+                timer: Timer::from_seconds(2.0, TimerMode::Once),
+            },
+            StateScoped(GameState::Win),
+            children![
+                (
+                    Text::new("congratulations."),
+                    TextFont {
+                        font: game_assets.font.clone(),
+                        font_size: 33.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                    TextShadow::default(),
+                ),
+                (
+                    Text::new("and thank you for playing."),
+                    TextFont {
+                        font: game_assets.font.clone(),
+                        font_size: 33.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                    TextShadow::default(),
+                )
+            ],
+        ))
+        .id();
+
+    let time_text = if level_timer.new_best {
+        format!(
+            "{} - new best!",
+            format_level_time(level_timer.stopwatch.elapsed())
+        )
+    } else {
+        format_level_time(level_timer.stopwatch.elapsed())
+    };
+
+    commands.entity(win_screen).with_child((
+        Text::new(time_text),
+        TextFont {
+            font: game_assets.font.clone(),
+            font_size: 26.0,
             ..default()
         },
-        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.0)),
-        FadeInBackground {
-            timer: Timer::from_seconds(2.0, TimerMode::Once),
-        },
-        StateScoped(GameState::Win),
-        children![
-            (
-                Text::new("congratulations."),
-                TextFont {
-                    font: game_assets.font.clone(),
-                    font_size: 33.0,
-                    ..default()
-                },
-                TextColor(Color::srgb(0.9, 0.9, 0.9)),
-                TextShadow::default(),
-            ),
-            (
-                Text::new("and thank you for playing."),
-                TextFont {
-                    font: game_assets.font.clone(),
-                    font_size: 33.0,
-                    ..default()
-                },
-                TextColor(Color::srgb(0.9, 0.9, 0.9)),
-                TextShadow::default(),
-            )
-        ],
+        TextColor(Color::srgb(0.9, 0.9, 0.9)),
+        TextShadow::default(),
     ));
+
+    if let Some(level_meta) = &current_level_meta.0 {
+        commands.entity(win_screen).with_child((
+            Text::new(format!(
+                "{} by {} - par {:.0}s",
+                level_meta.name, level_meta.author, level_meta.par_time
+            )),
+            TextFont {
+                font: game_assets.font.clone(),
+                font_size: 22.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.7, 0.7, 0.7)),
+            TextShadow::default(),
+        ));
+    }
 }
 
 fn fade_in_background(
@@ -0,0 +1,84 @@
+use bevy::prelude::*;
+use bevy_tween::tween::AnimationTarget;
+
+use crate::rendering::unlit_material::{
+    UnlitMaterial, UnlitMaterialExtension, POWERED_PULSE_SPEED,
+};
+
+// A copy of a device's material to make changes to. Without a renderer (headless apps) the
+// scene materials never show up, so hand back a blank one and let the gameplay side carry on.
+pub fn clone_unlit_material(
+    unlit_materials: &Assets<UnlitMaterial>,
+    material_handle: &MeshMaterial3d<UnlitMaterial>,
+) -> UnlitMaterial {
+    unlit_materials
+        .get(material_handle)
+        .cloned()
+        .unwrap_or_else(|| UnlitMaterial {
+            base: StandardMaterial::default(),
+            extension: UnlitMaterialExtension::default(),
+        })
+}
+
+// Gives a device child its own copy of its material so tweening its intensity
+// doesn't light up every other mesh sharing the original handle. Devices pulse while powered.
+pub fn make_child_animatable(
+    commands: &mut Commands,
+    child: Entity,
+    material_handle: &MeshMaterial3d<UnlitMaterial>,
+    unlit_materials: &mut Assets<UnlitMaterial>,
+) {
+    let mut new_material = clone_unlit_material(unlit_materials, material_handle);
+    new_material.extension.params.pulse_speed = POWERED_PULSE_SPEED;
+    commands.entity(child).insert((
+        AnimationTarget,
+        MeshMaterial3d(unlit_materials.add(new_material)),
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::test_app::test_unlit_material;
+
+    #[test]
+    fn animatable_child_gets_its_own_pulsing_material() {
+        let mut world = World::new();
+        world.init_resource::<Assets<UnlitMaterial>>();
+        let shared = MeshMaterial3d(
+            world
+                .resource_mut::<Assets<UnlitMaterial>>()
+                .add(test_unlit_material()),
+        );
+        let child = world.spawn(shared.clone()).id();
+
+        world.resource_scope(|world, mut unlit_materials: Mut<Assets<UnlitMaterial>>| {
+            let mut commands = world.commands();
+            make_child_animatable(&mut commands, child, &shared, &mut unlit_materials);
+        });
+        world.flush();
+
+        let unlit_materials = world.resource::<Assets<UnlitMaterial>>();
+        let own = world.get::<MeshMaterial3d<UnlitMaterial>>(child).unwrap();
+        assert_ne!(own.id(), shared.id());
+        assert!(world.get::<AnimationTarget>(child).is_some());
+        assert_eq!(
+            unlit_materials
+                .get(own)
+                .unwrap()
+                .extension
+                .params
+                .pulse_speed,
+            POWERED_PULSE_SPEED
+        );
+        assert_ne!(
+            unlit_materials
+                .get(&shared)
+                .unwrap()
+                .extension
+                .params
+                .pulse_speed,
+            POWERED_PULSE_SPEED
+        );
+    }
+}